@@ -0,0 +1,4 @@
+mod reader;
+mod writer;
+
+pub use self::{reader::Reader, writer::Writer};
@@ -0,0 +1,343 @@
+use std::str::FromStr;
+
+use futures::{stream, Stream};
+use noodles_bgzf as bgzf;
+use tokio::io::{self, AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncSeek};
+
+use crate::{line::is_header_line, record, Line, Record};
+
+/// An async BED reader.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> std::io::Result<()> {
+/// use futures::TryStreamExt;
+/// use noodles_bed as bed;
+/// use tokio::{fs::File, io::BufReader};
+///
+/// let mut reader = File::open("sample.bed")
+///     .await
+///     .map(BufReader::new)
+///     .map(bed::AsyncReader::new)?;
+///
+/// let mut records = reader.records::<3>();
+///
+/// while let Some(record) = records.try_next().await? {
+///     // ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Creates an async BED reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let data = [];
+    /// let reader = bed::AsyncReader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let data = [];
+    /// let reader = bed::AsyncReader::new(&data[..]);
+    /// assert!(reader.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let data = [];
+    /// let mut reader = bed::AsyncReader::new(&data[..]);
+    /// assert!(reader.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let data = [];
+    /// let reader = bed::AsyncReader::new(&data[..]);
+    /// assert!(reader.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads a raw BED line.
+    ///
+    /// This reads from the underlying stream until a newline is reached and appends it to the
+    /// given buffer, sans the final newline character. The buffer can subsequently be parsed as a
+    /// [`crate::Line`] or [`crate::Record`].
+    ///
+    /// If successful, the number of bytes read is returned. If the number of bytes read is 0, the
+    /// stream reached EOF.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"sq0\t8\t13\n";
+    /// let mut reader = bed::AsyncReader::new(&data[..]);
+    ///
+    /// let mut buf = String::new();
+    /// reader.read_line(&mut buf).await?;
+    ///
+    /// assert_eq!(buf, "sq0\t8\t13");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        read_line(&mut self.inner, buf).await
+    }
+
+    /// Returns an (async) stream over lines starting from the current (input) stream position.
+    ///
+    /// Unlike [`Self::records`], this includes track, browser, and comment lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// use futures::TryStreamExt;
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"track name=peaks\nsq0\t7\t13\n";
+    /// let mut reader = bed::AsyncReader::new(&data[..]);
+    ///
+    /// let mut lines = reader.lines::<3>();
+    ///
+    /// assert!(matches!(lines.try_next().await?, Some(bed::Line::Track(_))));
+    /// assert!(matches!(lines.try_next().await?, Some(bed::Line::Record(_))));
+    /// assert!(lines.try_next().await?.is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lines<const N: u8>(&mut self) -> impl Stream<Item = io::Result<Line<N>>> + '_
+    where
+        Record<N>: FromStr<Err = record::ParseError>,
+    {
+        Box::pin(stream::try_unfold(
+            (&mut self.inner, String::new()),
+            |(mut reader, mut buf)| async {
+                buf.clear();
+
+                match read_line(&mut reader, &mut buf).await? {
+                    0 => Ok(None),
+                    _ => buf
+                        .parse()
+                        .map(|line| Some((line, (reader, buf))))
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+                }
+            },
+        ))
+    }
+
+    /// Returns an (async) stream over records starting from the current (input) stream position.
+    ///
+    /// Track, browser, and comment lines are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// use futures::TryStreamExt;
+    /// use noodles_bed as bed;
+    /// use noodles_core::Position;
+    ///
+    /// let data = b"track name=peaks\nsq0\t7\t13\n# sq0\t20\t34\n";
+    /// let mut reader = bed::AsyncReader::new(&data[..]);
+    ///
+    /// let mut records = reader.records::<3>();
+    ///
+    /// let record = records.try_next().await?;
+    /// assert_eq!(record.map(|r| r.start_position()), Position::new(8));
+    /// // ...
+    ///
+    /// assert!(records.try_next().await?.is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn records<const N: u8>(&mut self) -> impl Stream<Item = io::Result<Record<N>>> + '_
+    where
+        Record<N>: FromStr<Err = record::ParseError>,
+    {
+        Box::pin(stream::try_unfold(
+            (&mut self.inner, String::new()),
+            |(mut reader, mut buf)| async {
+                loop {
+                    buf.clear();
+
+                    if read_line(&mut reader, &mut buf).await? == 0 {
+                        return Ok(None);
+                    }
+
+                    if !is_header_line(&buf) {
+                        break;
+                    }
+                }
+
+                buf.parse()
+                    .map(|record| Some((record, (reader, buf))))
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            },
+        ))
+    }
+}
+
+impl<R> Reader<bgzf::AsyncReader<R>>
+where
+    R: AsyncRead,
+{
+    /// Returns the current virtual position of the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let data = [];
+    /// let reader = bed::AsyncReader::new(bgzf::AsyncReader::new(&data[..]));
+    ///
+    /// assert_eq!(reader.virtual_position(), bgzf::VirtualPosition::default());
+    /// ```
+    pub fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+}
+
+impl<R> Reader<bgzf::AsyncReader<R>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Seeks the underlying BGZF stream to the given virtual position.
+    ///
+    /// Virtual positions typically come from an associated index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bed as bed;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let data = Cursor::new([]);
+    /// let mut reader = bed::AsyncReader::new(bgzf::AsyncReader::new(data));
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::default();
+    /// reader.seek(virtual_position).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
+        self.inner.seek(pos).await
+    }
+}
+
+async fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
+where
+    R: AsyncBufRead + Unpin,
+{
+    const LINE_FEED: char = '\n';
+    const CARRIAGE_RETURN: char = '\r';
+
+    match reader.read_line(buf).await {
+        Ok(0) => Ok(0),
+        Ok(n) => {
+            if buf.ends_with(LINE_FEED) {
+                buf.pop();
+
+                if buf.ends_with(CARRIAGE_RETURN) {
+                    buf.pop();
+                }
+            }
+
+            Ok(n)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_line() -> io::Result<()> {
+        async fn t(buf: &mut String, mut data: &[u8], expected: &str) -> io::Result<()> {
+            buf.clear();
+            read_line(&mut data, buf).await?;
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+
+        let mut buf = String::new();
+
+        t(&mut buf, b"sq0\t8\t13\n", "sq0\t8\t13").await?;
+        t(&mut buf, b"sq0\t8\t13\r\n", "sq0\t8\t13").await?;
+        t(&mut buf, b"sq0\t8\t13", "sq0\t8\t13").await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_records_with_bgzf_reader() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+
+        let mut writer = noodles_bgzf::Writer::new(Vec::new());
+        writer.write_all(b"browser position sq0:8-13\nsq0\t7\t13\nsq1\t20\t34\n")?;
+        let data = writer.finish()?;
+
+        let mut reader = Reader::new(bgzf::AsyncReader::new(&data[..]));
+        let records: Vec<Record<3>> = reader.records().try_collect().await?;
+
+        assert_eq!(
+            records,
+            ["sq0\t7\t13".parse::<Record<3>>()?, "sq1\t20\t34".parse()?]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+
+use tokio::io::{self, AsyncWrite, AsyncWriteExt};
+
+use crate::{Record, Track};
+
+const LINE_FEED: u8 = b'\n';
+
+/// An async BED writer.
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W> Writer<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Creates an async BED writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let writer = bed::AsyncWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let writer = bed::AsyncWriter::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let mut writer = bed::AsyncWriter::new(Vec::new());
+    /// assert!(writer.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let writer = bed::AsyncWriter::new(Vec::new());
+    /// assert!(writer.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Shuts down the output stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_bed as bed;
+    /// let mut writer = bed::AsyncWriter::new(Vec::new());
+    /// writer.shutdown().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&mut self) -> io::Result<()> {
+        self.inner.shutdown().await
+    }
+
+    /// Writes a BED record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use noodles_bed as bed;
+    /// use noodles_core::Position;
+    ///
+    /// let mut writer = bed::AsyncWriter::new(Vec::new());
+    ///
+    /// let record = bed::Record::<3>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// writer.write_record(&record).await?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"sq0\t7\t13\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_record<const N: u8>(&mut self, record: &Record<N>) -> io::Result<()>
+    where
+        Record<N>: fmt::Display,
+    {
+        write_line(&mut self.inner, record).await
+    }
+
+    /// Writes a track line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use noodles_bed::{self as bed, Track};
+    ///
+    /// let mut writer = bed::AsyncWriter::new(Vec::new());
+    ///
+    /// let track: Track = "track name=peaks".parse()?;
+    /// writer.write_track(&track).await?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"track name=peaks\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_track(&mut self, track: &Track) -> io::Result<()> {
+        write_line(&mut self.inner, track).await
+    }
+}
+
+async fn write_line<W, T>(writer: &mut W, value: &T) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: fmt::Display,
+{
+    let s = value.to_string();
+    writer.write_all(s.as_bytes()).await?;
+    writer.write_u8(LINE_FEED).await?;
+    Ok(())
+}
@@ -0,0 +1,479 @@
+//! autoSql table definition.
+//!
+//! An autoSql (`.as`) definition describes the columns of a BED file, including any columns
+//! beyond the standard BED fields (BEDn+m). It is used by bigBed files and to parse records with
+//! typed custom fields ([`crate::record::TypedRecord`]).
+
+pub mod field;
+
+pub use self::field::Field;
+
+use std::{error, fmt, iter::Peekable, str::FromStr};
+
+use self::field::{Size, Type};
+
+const DECLARATION: &str = "table";
+
+/// An autoSql table definition.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoSql {
+    name: String,
+    description: String,
+    fields: Vec<Field>,
+}
+
+impl AutoSql {
+    /// Creates an autoSql table definition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::auto_sql::{field::Type, AutoSql, Field};
+    ///
+    /// let auto_sql = AutoSql::new(
+    ///     "bed3",
+    ///     "Browser extensible data",
+    ///     vec![
+    ///         Field::new(Type::String, None, "chrom", "Reference sequence name"),
+    ///         Field::new(Type::Uint, None, "chromStart", "Start position"),
+    ///         Field::new(Type::Uint, None, "chromEnd", "End position"),
+    ///     ],
+    /// );
+    /// ```
+    pub fn new<N, D>(name: N, description: D, fields: Vec<Field>) -> Self
+    where
+        N: Into<String>,
+        D: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            fields,
+        }
+    }
+
+    /// Returns the table name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::auto_sql::AutoSql;
+    /// let auto_sql = AutoSql::new("bed3", "Browser extensible data", Vec::new());
+    /// assert_eq!(auto_sql.name(), "bed3");
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the table description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::auto_sql::AutoSql;
+    /// let auto_sql = AutoSql::new("bed3", "Browser extensible data", Vec::new());
+    /// assert_eq!(auto_sql.description(), "Browser extensible data");
+    /// ```
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns the fields.
+    ///
+    /// The first fields describe the standard BED fields, in order. Any remaining fields are
+    /// custom fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::auto_sql::AutoSql;
+    /// let auto_sql = AutoSql::new("bed3", "Browser extensible data", Vec::new());
+    /// assert!(auto_sql.fields().is_empty());
+    /// ```
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+}
+
+impl fmt::Display for AutoSql {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", DECLARATION, self.name)?;
+        writeln!(f, "\"{}\"", self.description)?;
+        writeln!(f, "(")?;
+
+        for field in &self.fields {
+            writeln!(f, "    {}", field)?;
+        }
+
+        write!(f, ")")
+    }
+}
+
+/// An error returned when a raw autoSql table definition fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input ended unexpectedly.
+    UnexpectedEof,
+    /// A token is unexpected.
+    UnexpectedToken(String),
+    /// A field type is invalid.
+    InvalidType(field::ty::ParseError),
+    /// A field size is invalid.
+    InvalidSize(String),
+    /// A field name is duplicated.
+    DuplicateName(String),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::UnexpectedEof => f.write_str("unexpected EOF"),
+            Self::UnexpectedToken(s) => write!(f, "unexpected token: {}", s),
+            Self::InvalidType(e) => write!(f, "invalid type: {}", e),
+            Self::InvalidSize(s) => write!(f, "invalid size: {}", s),
+            Self::DuplicateName(name) => write!(f, "duplicate name: {}", name),
+        }
+    }
+}
+
+impl FromStr for AutoSql {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut tokens = Tokenizer { s }.peekable();
+
+        match next_token(&mut tokens)? {
+            Token::Word(DECLARATION) => {}
+            token => return Err(ParseError::UnexpectedToken(token.to_string())),
+        }
+
+        let name = expect_word(&mut tokens)?;
+        let description = expect_quoted(&mut tokens)?;
+
+        expect_symbol(&mut tokens, '(')?;
+
+        let mut fields: Vec<Field> = Vec::new();
+
+        loop {
+            match next_token(&mut tokens)? {
+                Token::Symbol(')') => break,
+                Token::Word(ty) => {
+                    let field = parse_field(&mut tokens, ty, &fields)?;
+                    fields.push(field);
+                }
+                token => return Err(ParseError::UnexpectedToken(token.to_string())),
+            }
+        }
+
+        if let Some(token) = tokens.next().transpose()? {
+            return Err(ParseError::UnexpectedToken(token.to_string()));
+        }
+
+        Ok(Self::new(name, description, fields))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Token<'a> {
+    Word(&'a str),
+    Quoted(&'a str),
+    Symbol(char),
+}
+
+impl<'a> fmt::Display for Token<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Word(s) => f.write_str(s),
+            Self::Quoted(s) => write!(f, "\"{}\"", s),
+            Self::Symbol(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+struct Tokenizer<'a> {
+    s: &'a str,
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Result<Token<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        const QUOTE: char = '"';
+
+        fn is_symbol(c: char) -> bool {
+            matches!(c, '(' | ')' | '[' | ']' | ';' | ',')
+        }
+
+        self.s = self.s.trim_start();
+
+        let c = self.s.chars().next()?;
+
+        if c == QUOTE {
+            let rest = &self.s[1..];
+
+            match rest.find(QUOTE) {
+                Some(i) => {
+                    self.s = &rest[i + 1..];
+                    Some(Ok(Token::Quoted(&rest[..i])))
+                }
+                None => {
+                    self.s = "";
+                    Some(Err(ParseError::UnexpectedEof))
+                }
+            }
+        } else if is_symbol(c) {
+            self.s = &self.s[1..];
+            Some(Ok(Token::Symbol(c)))
+        } else {
+            let i = self
+                .s
+                .find(|c: char| c.is_whitespace() || c == QUOTE || is_symbol(c))
+                .unwrap_or(self.s.len());
+
+            let (word, rest) = self.s.split_at(i);
+            self.s = rest;
+
+            Some(Ok(Token::Word(word)))
+        }
+    }
+}
+
+fn next_token<'a, I>(tokens: &mut I) -> Result<Token<'a>, ParseError>
+where
+    I: Iterator<Item = Result<Token<'a>, ParseError>>,
+{
+    tokens.next().unwrap_or(Err(ParseError::UnexpectedEof))
+}
+
+fn expect_word<'a, I>(tokens: &mut I) -> Result<&'a str, ParseError>
+where
+    I: Iterator<Item = Result<Token<'a>, ParseError>>,
+{
+    match next_token(tokens)? {
+        Token::Word(s) => Ok(s),
+        token => Err(ParseError::UnexpectedToken(token.to_string())),
+    }
+}
+
+fn expect_quoted<'a, I>(tokens: &mut I) -> Result<&'a str, ParseError>
+where
+    I: Iterator<Item = Result<Token<'a>, ParseError>>,
+{
+    match next_token(tokens)? {
+        Token::Quoted(s) => Ok(s),
+        token => Err(ParseError::UnexpectedToken(token.to_string())),
+    }
+}
+
+fn expect_symbol<'a, I>(tokens: &mut I, c: char) -> Result<(), ParseError>
+where
+    I: Iterator<Item = Result<Token<'a>, ParseError>>,
+{
+    match next_token(tokens)? {
+        Token::Symbol(d) if d == c => Ok(()),
+        token => Err(ParseError::UnexpectedToken(token.to_string())),
+    }
+}
+
+fn parse_field<'a, I>(
+    tokens: &mut Peekable<I>,
+    raw_ty: &str,
+    fields: &[Field],
+) -> Result<Field, ParseError>
+where
+    I: Iterator<Item = Result<Token<'a>, ParseError>>,
+{
+    let ty = parse_type(tokens, raw_ty)?;
+
+    let size = if let Some(Ok(Token::Symbol('['))) = tokens.peek() {
+        tokens.next();
+        let raw_size = expect_word(tokens)?;
+        expect_symbol(tokens, ']')?;
+        Some(parse_size(raw_size, &ty, fields)?)
+    } else {
+        None
+    };
+
+    let name = expect_word(tokens)?;
+
+    if fields.iter().any(|field| field.name() == name) {
+        return Err(ParseError::DuplicateName(name.into()));
+    }
+
+    expect_symbol(tokens, ';')?;
+    let description = expect_quoted(tokens)?;
+
+    Ok(Field::new(ty, size, name, description))
+}
+
+fn parse_type<'a, I>(tokens: &mut Peekable<I>, raw_ty: &str) -> Result<Type, ParseError>
+where
+    I: Iterator<Item = Result<Token<'a>, ParseError>>,
+{
+    if let Some(Ok(Token::Symbol('('))) = tokens.peek() {
+        tokens.next();
+
+        let mut values = Vec::new();
+
+        loop {
+            values.push(expect_word(tokens)?);
+
+            match next_token(tokens)? {
+                Token::Symbol(',') => {}
+                Token::Symbol(')') => break,
+                token => return Err(ParseError::UnexpectedToken(token.to_string())),
+            }
+        }
+
+        format!("{}({})", raw_ty, values.join(","))
+            .parse()
+            .map_err(ParseError::InvalidType)
+    } else {
+        raw_ty.parse().map_err(ParseError::InvalidType)
+    }
+}
+
+fn parse_size(s: &str, ty: &Type, fields: &[Field]) -> Result<Size, ParseError> {
+    if matches!(ty, Type::Enum(_) | Type::Set(_)) {
+        return Err(ParseError::InvalidSize(s.into()));
+    }
+
+    if let Ok(n) = s.parse() {
+        return if n > 0 {
+            Ok(Size::Fixed(n))
+        } else {
+            Err(ParseError::InvalidSize(s.into()))
+        };
+    }
+
+    // The size field must be a preceding integer field.
+    let is_valid = fields
+        .iter()
+        .any(|field| field.name() == s && field.ty().is_integer() && field.size().is_none());
+
+    if is_valid {
+        Ok(Size::Field(s.into()))
+    } else {
+        Err(ParseError::InvalidSize(s.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BED6_PLUS_2: &str = r#"table bed6plus2
+"Variant annotations"
+    (
+    string chrom;        "Reference sequence chromosome or scaffold"
+    uint   chromStart;   "Start position in chromosome"
+    uint   chromEnd;     "End position in chromosome"
+    string name;         "Name of item"
+    uint   score;        "Score from 0-1000"
+    char[1] strand;      "+ or -"
+    enum(snv, indel) kind; "Variant kind"
+    int[2] depths;       "Allelic depths"
+    )
+"#;
+
+    #[test]
+    fn test_fmt() -> Result<(), ParseError> {
+        let auto_sql: AutoSql = BED6_PLUS_2.parse()?;
+
+        let expected = r#"table bed6plus2
+"Variant annotations"
+(
+    string chrom; "Reference sequence chromosome or scaffold"
+    uint chromStart; "Start position in chromosome"
+    uint chromEnd; "End position in chromosome"
+    string name; "Name of item"
+    uint score; "Score from 0-1000"
+    char[1] strand; "+ or -"
+    enum(snv, indel) kind; "Variant kind"
+    int[2] depths; "Allelic depths"
+)"#;
+
+        assert_eq!(auto_sql.to_string(), expected);
+        assert_eq!(expected.parse::<AutoSql>()?, auto_sql);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), ParseError> {
+        let auto_sql: AutoSql = BED6_PLUS_2.parse()?;
+
+        assert_eq!(auto_sql.name(), "bed6plus2");
+        assert_eq!(auto_sql.description(), "Variant annotations");
+
+        let fields = auto_sql.fields();
+        assert_eq!(fields.len(), 8);
+        assert_eq!(
+            fields[5],
+            Field::new(Type::Char, Some(Size::Fixed(1)), "strand", "+ or -")
+        );
+        assert_eq!(
+            fields[6],
+            Field::new(
+                Type::Enum(vec![String::from("snv"), String::from("indel")]),
+                None,
+                "kind",
+                "Variant kind"
+            )
+        );
+
+        let auto_sql: AutoSql = r#"table t "" ( uint n; "" int[n] xs; "" )"#.parse()?;
+        assert_eq!(
+            auto_sql.fields()[1].size(),
+            Some(&Size::Field(String::from("n")))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str_with_invalid_input() {
+        assert_eq!("".parse::<AutoSql>(), Err(ParseError::Empty));
+        assert_eq!(
+            r#"simple t "" ()"#.parse::<AutoSql>(),
+            Err(ParseError::UnexpectedToken(String::from("simple")))
+        );
+        assert_eq!(
+            r#"table t "" ( uint n; "" "#.parse::<AutoSql>(),
+            Err(ParseError::UnexpectedEof)
+        );
+        assert_eq!(
+            r#"table t "" ( uint n "" )"#.parse::<AutoSql>(),
+            Err(ParseError::UnexpectedToken(String::from("\"\"")))
+        );
+        assert!(matches!(
+            r#"table t "" ( integer n; "" )"#.parse::<AutoSql>(),
+            Err(ParseError::InvalidType(_))
+        ));
+        assert_eq!(
+            r#"table t "" ( int[m] xs; "" )"#.parse::<AutoSql>(),
+            Err(ParseError::InvalidSize(String::from("m")))
+        );
+        assert_eq!(
+            r#"table t "" ( enum(a, b)[2] xs; "" )"#.parse::<AutoSql>(),
+            Err(ParseError::InvalidSize(String::from("2")))
+        );
+        assert_eq!(
+            r#"table t "" ( uint n; "" int n; "" )"#.parse::<AutoSql>(),
+            Err(ParseError::DuplicateName(String::from("n")))
+        );
+        assert_eq!(
+            r#"table t "" ( ) )"#.parse::<AutoSql>(),
+            Err(ParseError::UnexpectedToken(String::from(")")))
+        );
+    }
+}
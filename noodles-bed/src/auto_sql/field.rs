@@ -0,0 +1,149 @@
+//! autoSql field.
+
+pub mod ty;
+
+pub use self::ty::Type;
+
+use std::fmt;
+
+/// The size of an autoSql array field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Size {
+    /// A fixed number of elements (e.g., `int[3]`).
+    Fixed(usize),
+    /// The number of elements is the value of another field (e.g., `int[blockCount]`).
+    Field(String),
+}
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed(n) => write!(f, "{}", n),
+            Self::Field(name) => f.write_str(name),
+        }
+    }
+}
+
+/// An autoSql field, i.e., a column definition.
+///
+/// A field with a size is an array, except `char[n]`, which is a string of up to `n` characters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Field {
+    ty: Type,
+    size: Option<Size>,
+    name: String,
+    description: String,
+}
+
+impl Field {
+    /// Creates an autoSql field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::auto_sql::{field::Type, Field};
+    /// let field = Field::new(Type::Float, None, "af", "Allele frequency");
+    /// ```
+    pub fn new<N, D>(ty: Type, size: Option<Size>, name: N, description: D) -> Self
+    where
+        N: Into<String>,
+        D: Into<String>,
+    {
+        Self {
+            ty,
+            size,
+            name: name.into(),
+            description: description.into(),
+        }
+    }
+
+    /// Returns the type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::auto_sql::{field::Type, Field};
+    /// let field = Field::new(Type::Float, None, "af", "Allele frequency");
+    /// assert_eq!(field.ty(), &Type::Float);
+    /// ```
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Returns the array size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::auto_sql::{field::{Size, Type}, Field};
+    ///
+    /// let field = Field::new(Type::Float, None, "af", "Allele frequency");
+    /// assert!(field.size().is_none());
+    ///
+    /// let field = Field::new(Type::Int, Some(Size::Fixed(2)), "ad", "Allelic depths");
+    /// assert_eq!(field.size(), Some(&Size::Fixed(2)));
+    /// ```
+    pub fn size(&self) -> Option<&Size> {
+        self.size.as_ref()
+    }
+
+    /// Returns the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::auto_sql::{field::Type, Field};
+    /// let field = Field::new(Type::Float, None, "af", "Allele frequency");
+    /// assert_eq!(field.name(), "af");
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::auto_sql::{field::Type, Field};
+    /// let field = Field::new(Type::Float, None, "af", "Allele frequency");
+    /// assert_eq!(field.description(), "Allele frequency");
+    /// ```
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ty)?;
+
+        if let Some(size) = &self.size {
+            write!(f, "[{}]", size)?;
+        }
+
+        write!(f, " {}; \"{}\"", self.name, self.description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let field = Field::new(Type::Float, None, "af", "Allele frequency");
+        assert_eq!(field.to_string(), r#"float af; "Allele frequency""#);
+
+        let field = Field::new(
+            Type::Int,
+            Some(Size::Field(String::from("blockCount"))),
+            "blockSizes",
+            "Block sizes",
+        );
+        assert_eq!(
+            field.to_string(),
+            r#"int[blockCount] blockSizes; "Block sizes""#
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! autoSql field type.
+
+use std::{error, fmt, str::FromStr};
+
+/// An autoSql field type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Type {
+    /// A signed 8-bit integer (`byte`).
+    Byte,
+    /// An unsigned 8-bit integer (`ubyte`).
+    Ubyte,
+    /// A signed 16-bit integer (`short`).
+    Short,
+    /// An unsigned 16-bit integer (`ushort`).
+    Ushort,
+    /// A signed 32-bit integer (`int`).
+    Int,
+    /// An unsigned 32-bit integer (`uint`).
+    Uint,
+    /// A signed 64-bit integer (`bigint`).
+    Bigint,
+    /// A single-precision floating-point (`float`).
+    Float,
+    /// A double-precision floating-point (`double`).
+    Double,
+    /// A character (`char`) or, when sized, a fixed-length string (`char[n]`).
+    Char,
+    /// A string of up to 255 characters (`string`).
+    String,
+    /// A long string (`lstring`).
+    Lstring,
+    /// One of a list of values (`enum(...)`).
+    Enum(Vec<String>),
+    /// Zero or more of a list of values (`set(...)`).
+    Set(Vec<String>),
+}
+
+impl Type {
+    /// Returns whether the type is an integer type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::auto_sql::field::Type;
+    /// assert!(Type::Uint.is_integer());
+    /// assert!(!Type::Float.is_integer());
+    /// ```
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            Self::Byte
+                | Self::Ubyte
+                | Self::Short
+                | Self::Ushort
+                | Self::Int
+                | Self::Uint
+                | Self::Bigint
+        )
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Byte => f.write_str("byte"),
+            Self::Ubyte => f.write_str("ubyte"),
+            Self::Short => f.write_str("short"),
+            Self::Ushort => f.write_str("ushort"),
+            Self::Int => f.write_str("int"),
+            Self::Uint => f.write_str("uint"),
+            Self::Bigint => f.write_str("bigint"),
+            Self::Float => f.write_str("float"),
+            Self::Double => f.write_str("double"),
+            Self::Char => f.write_str("char"),
+            Self::String => f.write_str("string"),
+            Self::Lstring => f.write_str("lstring"),
+            Self::Enum(values) => write!(f, "enum({})", values.join(", ")),
+            Self::Set(values) => write!(f, "set({})", values.join(", ")),
+        }
+    }
+}
+
+/// An error returned when a raw autoSql field type fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid(String),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty input"),
+            Self::Invalid(s) => write!(f, "invalid input: {}", s),
+        }
+    }
+}
+
+impl FromStr for Type {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(ParseError::Empty),
+            "byte" => Ok(Self::Byte),
+            "ubyte" => Ok(Self::Ubyte),
+            "short" => Ok(Self::Short),
+            "ushort" => Ok(Self::Ushort),
+            "int" => Ok(Self::Int),
+            "uint" => Ok(Self::Uint),
+            "bigint" => Ok(Self::Bigint),
+            "float" => Ok(Self::Float),
+            "double" => Ok(Self::Double),
+            "char" => Ok(Self::Char),
+            "string" => Ok(Self::String),
+            "lstring" => Ok(Self::Lstring),
+            _ => parse_list_type(s).ok_or_else(|| ParseError::Invalid(s.into())),
+        }
+    }
+}
+
+fn parse_list_type(s: &str) -> Option<Type> {
+    fn parse_values(s: &str) -> Option<Vec<String>> {
+        let values: Vec<_> = s
+            .strip_prefix('(')
+            .and_then(|t| t.strip_suffix(')'))?
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .collect();
+
+        if values.iter().any(|v| v.is_empty()) {
+            None
+        } else {
+            Some(values)
+        }
+    }
+
+    if let Some(t) = s.strip_prefix("enum") {
+        parse_values(t.trim_start()).map(Type::Enum)
+    } else if let Some(t) = s.strip_prefix("set") {
+        parse_values(t.trim_start()).map(Type::Set)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Type::Ubyte.to_string(), "ubyte");
+        assert_eq!(Type::Lstring.to_string(), "lstring");
+        assert_eq!(
+            Type::Enum(vec![String::from("snv"), String::from("indel")]).to_string(),
+            "enum(snv, indel)"
+        );
+        assert_eq!(Type::Set(vec![String::from("a")]).to_string(), "set(a)");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("int".parse(), Ok(Type::Int));
+        assert_eq!("double".parse(), Ok(Type::Double));
+        assert_eq!(
+            "enum(snv,indel)".parse(),
+            Ok(Type::Enum(vec![String::from("snv"), String::from("indel")]))
+        );
+        assert_eq!(
+            "set (a, b)".parse(),
+            Ok(Type::Set(vec![String::from("a"), String::from("b")]))
+        );
+
+        assert_eq!("".parse::<Type>(), Err(ParseError::Empty));
+        assert_eq!(
+            "integer".parse::<Type>(),
+            Err(ParseError::Invalid(String::from("integer")))
+        );
+        assert_eq!(
+            "enum()".parse::<Type>(),
+            Err(ParseError::Invalid(String::from("enum()")))
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! Big Binary Indexed (BBI) formats: bigBed and bigWig.
+//!
+//! bigBed and bigWig files are indexed binary forms of BED and wiggle (or bedGraph) files,
+//! respectively. Reference sequences (chromosomes) are looked up in a B+ tree, data blocks are
+//! located using an R-tree, and precomputed summaries are available at multiple zoom levels.
+//!
+//! Both bigBed and bigWig files can be read, and bigBed files can be written. Only little-endian
+//! files are supported.
+
+pub(crate) mod chromosome_tree;
+pub mod header;
+pub(crate) mod r_tree;
+pub mod reader;
+mod summary;
+mod value_interval;
+pub mod writer;
+pub mod zoom;
+
+pub use self::{
+    chromosome_tree::Chromosome, header::Header, reader::Reader, summary::Summary,
+    value_interval::ValueInterval, writer::Writer,
+};
+
+use std::io;
+
+use noodles_core::Position;
+
+// Converts a 0-based, half-open interval to 1-based, inclusive positions.
+pub(crate) fn interval_positions(start: u32, end: u32) -> io::Result<(Position, Position)> {
+    let start = usize::try_from(start)
+        .ok()
+        .and_then(|n| n.checked_add(1))
+        .and_then(Position::new);
+
+    let end = usize::try_from(end).ok().and_then(Position::new);
+
+    match (start, end) {
+        (Some(start), Some(end)) => Ok((start, end)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid interval",
+        )),
+    }
+}
@@ -0,0 +1,535 @@
+use std::{
+    collections::HashSet,
+    io::{self, Read, Seek, SeekFrom, Write},
+};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use super::r_tree::chunk_ranges;
+
+pub(crate) const MAGIC_NUMBER: u32 = 0x78ca8c91;
+
+const HEADER_SIZE: u64 = 32;
+const NODE_HEADER_SIZE: u64 = 4;
+const VALUE_SIZE: u32 = 8;
+
+// The maximum size of a key, i.e., the maximum length of a chromosome name.
+const MAX_KEY_SIZE: usize = 255;
+
+// The maximum number of children of each node.
+const BLOCK_SIZE: usize = 256;
+
+const NUL: u8 = 0x00;
+
+/// A BBI chromosome, i.e., a reference sequence.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Chromosome {
+    name: String,
+    id: u32,
+    size: u32,
+}
+
+impl Chromosome {
+    /// Creates a chromosome.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bbi::Chromosome;
+    /// let chromosome = Chromosome::new("sq0", 0, 8);
+    /// ```
+    pub fn new<N>(name: N, id: u32, size: u32) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            id,
+            size,
+        }
+    }
+
+    /// Returns the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bbi::Chromosome;
+    /// let chromosome = Chromosome::new("sq0", 0, 8);
+    /// assert_eq!(chromosome.name(), "sq0");
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bbi::Chromosome;
+    /// let chromosome = Chromosome::new("sq0", 0, 8);
+    /// assert_eq!(chromosome.id(), 0);
+    /// ```
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bbi::Chromosome;
+    /// let chromosome = Chromosome::new("sq0", 0, 8);
+    /// assert_eq!(chromosome.size(), 8);
+    /// ```
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+}
+
+struct TreeHeader {
+    key_size: usize,
+}
+
+fn read_tree_header<R>(reader: &mut R) -> io::Result<TreeHeader>
+where
+    R: Read,
+{
+    let magic_number = reader.read_u32::<LittleEndian>()?;
+
+    if magic_number != MAGIC_NUMBER {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid chromosome tree header",
+        ));
+    }
+
+    let _block_size = reader.read_u32::<LittleEndian>()?;
+    let key_size = reader.read_u32::<LittleEndian>().and_then(|n| {
+        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })?;
+
+    if key_size > MAX_KEY_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid chromosome tree key size",
+        ));
+    }
+
+    let value_size = reader.read_u32::<LittleEndian>()?;
+
+    if value_size != VALUE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid chromosome tree value size",
+        ));
+    }
+
+    let _item_count = reader.read_u64::<LittleEndian>()?;
+    let _reserved = reader.read_u64::<LittleEndian>()?;
+
+    Ok(TreeHeader { key_size })
+}
+
+enum Node {
+    Leaf(Vec<Chromosome>),
+    Internal(Vec<(Vec<u8>, u64)>),
+}
+
+fn read_node<R>(reader: &mut R, key_size: usize) -> io::Result<Node>
+where
+    R: Read,
+{
+    let is_leaf = reader.read_u8()? != 0;
+    let _reserved = reader.read_u8()?;
+    let count = reader.read_u16::<LittleEndian>()?;
+
+    let mut key = vec![0; key_size];
+
+    if is_leaf {
+        let mut chromosomes = Vec::with_capacity(usize::from(count));
+
+        for _ in 0..count {
+            reader.read_exact(&mut key)?;
+            let name = parse_key(&key)?;
+            let id = reader.read_u32::<LittleEndian>()?;
+            let size = reader.read_u32::<LittleEndian>()?;
+            chromosomes.push(Chromosome::new(name, id, size));
+        }
+
+        Ok(Node::Leaf(chromosomes))
+    } else {
+        let mut children = Vec::with_capacity(usize::from(count));
+
+        for _ in 0..count {
+            reader.read_exact(&mut key)?;
+            let offset = reader.read_u64::<LittleEndian>()?;
+            children.push((key.clone(), offset));
+        }
+
+        Ok(Node::Internal(children))
+    }
+}
+
+fn parse_key(buf: &[u8]) -> io::Result<&str> {
+    let len = buf.iter().position(|&b| b == NUL).unwrap_or(buf.len());
+    std::str::from_utf8(&buf[..len]).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Reads all the chromosomes in the tree at the given offset.
+///
+/// This returns an error if a node is visited more than once, i.e., the tree has a cycle.
+pub(crate) fn read_chromosomes<R>(reader: &mut R, offset: u64) -> io::Result<Vec<Chromosome>>
+where
+    R: Read + Seek,
+{
+    reader.seek(SeekFrom::Start(offset))?;
+    let header = read_tree_header(reader)?;
+
+    let mut chromosomes = Vec::new();
+    let mut offsets = vec![reader.stream_position()?];
+    let mut visited_offsets = HashSet::new();
+
+    while let Some(offset) = offsets.pop() {
+        if !visited_offsets.insert(offset) {
+            return Err(cycle_error());
+        }
+
+        reader.seek(SeekFrom::Start(offset))?;
+
+        match read_node(reader, header.key_size)? {
+            Node::Leaf(cs) => chromosomes.extend(cs),
+            Node::Internal(children) => {
+                offsets.extend(children.into_iter().rev().map(|(_, offset)| offset));
+            }
+        }
+    }
+
+    Ok(chromosomes)
+}
+
+/// Searches the tree at the given offset for the chromosome with the given name.
+///
+/// This returns an error if a node is visited more than once, i.e., the tree has a cycle.
+pub(crate) fn find_chromosome<R>(
+    reader: &mut R,
+    offset: u64,
+    name: &str,
+) -> io::Result<Option<Chromosome>>
+where
+    R: Read + Seek,
+{
+    reader.seek(SeekFrom::Start(offset))?;
+    let header = read_tree_header(reader)?;
+
+    if name.len() > header.key_size {
+        return Ok(None);
+    }
+
+    let mut key = name.as_bytes().to_vec();
+    key.resize(header.key_size, NUL);
+
+    let mut visited_offsets = HashSet::new();
+
+    loop {
+        if !visited_offsets.insert(reader.stream_position()?) {
+            return Err(cycle_error());
+        }
+
+        match read_node(reader, header.key_size)? {
+            Node::Leaf(chromosomes) => {
+                return Ok(chromosomes.into_iter().find(|c| c.name() == name));
+            }
+            Node::Internal(children) => {
+                // Each child key is the smallest key in its subtree.
+                let child = children
+                    .iter()
+                    .take_while(|(child_key, _)| *child_key <= key)
+                    .last();
+
+                match child {
+                    Some((_, offset)) => reader.seek(SeekFrom::Start(*offset))?,
+                    None => return Ok(None),
+                };
+            }
+        }
+    }
+}
+
+fn cycle_error() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "chromosome tree has a cycle")
+}
+
+/// Writes a chromosome B+ tree.
+///
+/// `offset` is the position of the writer in the output stream.
+pub(crate) fn write_chromosome_tree<W>(
+    writer: &mut W,
+    offset: u64,
+    chromosomes: &[Chromosome],
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut chromosomes: Vec<_> = chromosomes.iter().collect();
+    chromosomes.sort_by(|a, b| a.name().cmp(b.name()));
+
+    let key_size = chromosomes
+        .iter()
+        .map(|c| c.name().len())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    if key_size > MAX_KEY_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "chromosome name is too long",
+        ));
+    }
+
+    let item_size = (key_size + VALUE_SIZE as usize) as u64;
+
+    // Nodes are grouped by level, from the leaves to the root. Each node holds a range of items
+    // (chromosomes or nodes) of the level below and the index of its first chromosome.
+    let mut levels: Vec<Vec<(std::ops::Range<usize>, usize)>> = Vec::new();
+    let mut nodes: Vec<_> = chunk_ranges(chromosomes.len())
+        .map(|range| {
+            let first = range.start;
+            (range, first)
+        })
+        .collect();
+
+    loop {
+        let is_root = nodes.len() == 1;
+        levels.push(nodes);
+
+        if is_root {
+            break;
+        }
+
+        let children = &levels[levels.len() - 1];
+
+        nodes = chunk_ranges(children.len())
+            .map(|range| {
+                let (_, first) = children[range.start];
+                (range, first)
+            })
+            .collect();
+    }
+
+    // Nodes are written from the root to the leaves.
+    let mut node_offsets = vec![Vec::new(); levels.len()];
+    let mut position = offset + HEADER_SIZE;
+
+    for (i, nodes) in levels.iter().enumerate().rev() {
+        for (range, _) in nodes {
+            node_offsets[i].push(position);
+            position += NODE_HEADER_SIZE + range.len() as u64 * item_size;
+        }
+    }
+
+    writer.write_u32::<LittleEndian>(MAGIC_NUMBER)?;
+    writer.write_u32::<LittleEndian>(BLOCK_SIZE.min(chromosomes.len()).max(1) as u32)?;
+    writer.write_u32::<LittleEndian>(key_size as u32)?;
+    writer.write_u32::<LittleEndian>(VALUE_SIZE)?;
+    writer.write_u64::<LittleEndian>(chromosomes.len() as u64)?;
+    writer.write_u64::<LittleEndian>(0)?; // reserved
+
+    let write_key = |writer: &mut W, name: &str| -> io::Result<()> {
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(&vec![NUL; key_size - name.len()])
+    };
+
+    for (i, nodes) in levels.iter().enumerate().rev() {
+        let is_leaf = i == 0;
+
+        for (range, _) in nodes {
+            writer.write_u8(u8::from(is_leaf))?;
+            writer.write_u8(0)?; // reserved
+            writer.write_u16::<LittleEndian>(range.len() as u16)?;
+
+            if is_leaf {
+                for chromosome in &chromosomes[range.clone()] {
+                    write_key(writer, chromosome.name())?;
+                    writer.write_u32::<LittleEndian>(chromosome.id())?;
+                    writer.write_u32::<LittleEndian>(chromosome.size())?;
+                }
+            } else {
+                for j in range.clone() {
+                    let (_, first) = levels[i - 1][j];
+                    write_key(writer, chromosomes[first].name())?;
+                    writer.write_u64::<LittleEndian>(node_offsets[i - 1][j])?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn write_tree_header(buf: &mut Vec<u8>, key_size: u32, item_count: u64) {
+        buf.extend(MAGIC_NUMBER.to_le_bytes());
+        buf.extend(2u32.to_le_bytes()); // blockSize
+        buf.extend(key_size.to_le_bytes());
+        buf.extend(8u32.to_le_bytes()); // valSize
+        buf.extend(item_count.to_le_bytes());
+        buf.extend(0u64.to_le_bytes()); // reserved
+    }
+
+    fn write_leaf(buf: &mut Vec<u8>, chromosomes: &[(&[u8; 4], u32, u32)]) {
+        buf.extend([1, 0]);
+        buf.extend((chromosomes.len() as u16).to_le_bytes());
+
+        for (key, id, size) in chromosomes {
+            buf.extend(key.iter());
+            buf.extend(id.to_le_bytes());
+            buf.extend(size.to_le_bytes());
+        }
+    }
+
+    fn build_tree() -> Vec<u8> {
+        // root (internal) -> [leaf (sq0, sq1), leaf (sq2)]
+        const ROOT_SIZE: u64 = 4 + 2 * (4 + 8);
+        const LEAF_0_SIZE: u64 = 4 + 2 * (4 + 4 + 4);
+
+        let mut buf = Vec::new();
+        write_tree_header(&mut buf, 4, 3);
+
+        let root_offset = buf.len() as u64;
+        let leaf_0_offset = root_offset + ROOT_SIZE;
+        let leaf_1_offset = leaf_0_offset + LEAF_0_SIZE;
+
+        buf.extend([0, 0]);
+        buf.extend(2u16.to_le_bytes());
+        buf.extend(b"sq0\x00");
+        buf.extend(leaf_0_offset.to_le_bytes());
+        buf.extend(b"sq2\x00");
+        buf.extend(leaf_1_offset.to_le_bytes());
+
+        write_leaf(&mut buf, &[(b"sq0\x00", 0, 8), (b"sq1\x00", 1, 13)]);
+        write_leaf(&mut buf, &[(b"sq2\x00", 2, 21)]);
+
+        buf
+    }
+
+    fn build_tree_with_cycle() -> Vec<u8> {
+        // root (internal) -> [root]
+        let mut buf = Vec::new();
+        write_tree_header(&mut buf, 4, 1);
+
+        let root_offset = buf.len() as u64;
+
+        buf.extend([0, 0]);
+        buf.extend(1u16.to_le_bytes());
+        buf.extend(b"sq0\x00");
+        buf.extend(root_offset.to_le_bytes());
+
+        buf
+    }
+
+    #[test]
+    fn test_read_tree_header() {
+        let mut buf = Vec::new();
+        write_tree_header(&mut buf, u32::MAX, 0);
+
+        assert!(matches!(
+            read_tree_header(&mut &buf[..]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_read_chromosomes() -> io::Result<()> {
+        let mut reader = Cursor::new(build_tree());
+
+        assert_eq!(
+            read_chromosomes(&mut reader, 0)?,
+            [
+                Chromosome::new("sq0", 0, 8),
+                Chromosome::new("sq1", 1, 13),
+                Chromosome::new("sq2", 2, 21),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_chromosome() -> io::Result<()> {
+        let mut reader = Cursor::new(build_tree());
+
+        assert_eq!(
+            find_chromosome(&mut reader, 0, "sq1")?,
+            Some(Chromosome::new("sq1", 1, 13))
+        );
+        assert_eq!(
+            find_chromosome(&mut reader, 0, "sq2")?,
+            Some(Chromosome::new("sq2", 2, 21))
+        );
+        assert!(find_chromosome(&mut reader, 0, "sq")?.is_none());
+        assert!(find_chromosome(&mut reader, 0, "sq3")?.is_none());
+        assert!(find_chromosome(&mut reader, 0, "sq0_alt")?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_chromosomes_and_find_chromosome_with_cycle() {
+        let mut reader = Cursor::new(build_tree_with_cycle());
+
+        assert!(matches!(
+            read_chromosomes(&mut reader, 0),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            find_chromosome(&mut reader, 0, "sq0"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_write_chromosome_tree() -> io::Result<()> {
+        const OFFSET: u64 = 8;
+
+        // 2 levels
+        let chromosomes: Vec<_> = (0..300)
+            .map(|i| Chromosome::new(format!("sq{}", i), i, i + 1))
+            .collect();
+
+        let mut buf = vec![0; OFFSET as usize];
+        write_chromosome_tree(&mut buf, OFFSET, &chromosomes)?;
+
+        let mut reader = Cursor::new(buf);
+
+        let mut actual = read_chromosomes(&mut reader, OFFSET)?;
+        actual.sort_by_key(|c| c.id());
+        assert_eq!(actual, chromosomes);
+
+        for chromosome in &chromosomes {
+            assert_eq!(
+                find_chromosome(&mut reader, OFFSET, chromosome.name())?.as_ref(),
+                Some(chromosome)
+            );
+        }
+
+        assert!(find_chromosome(&mut reader, OFFSET, "sq300")?.is_none());
+
+        let mut buf = Vec::new();
+        write_chromosome_tree(&mut buf, 0, &[])?;
+        let mut reader = Cursor::new(buf);
+        assert!(read_chromosomes(&mut reader, 0)?.is_empty());
+        assert!(find_chromosome(&mut reader, 0, "sq0")?.is_none());
+
+        Ok(())
+    }
+}
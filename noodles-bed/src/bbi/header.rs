@@ -0,0 +1,216 @@
+//! BBI header.
+
+use std::io::{self, Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use super::zoom;
+
+pub(crate) const BIG_WIG_MAGIC_NUMBER: u32 = 0x888ffc26;
+pub(crate) const BIG_BED_MAGIC_NUMBER: u32 = 0x8789f2eb;
+
+pub(crate) const VERSION: u16 = 4;
+
+/// The size of the common header in bytes.
+pub(crate) const SIZE: u64 = 64;
+
+/// A BBI file type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// bigWig.
+    BigWig,
+    /// bigBed.
+    BigBed,
+}
+
+/// A BBI header.
+///
+/// This includes the zoom level headers that immediately follow the common header.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Header {
+    pub(crate) kind: Kind,
+    pub(crate) version: u16,
+    pub(crate) zoom_headers: Vec<zoom::Header>,
+    pub(crate) chromosome_tree_offset: u64,
+    pub(crate) full_data_offset: u64,
+    pub(crate) full_index_offset: u64,
+    pub(crate) field_count: u16,
+    pub(crate) defined_field_count: u16,
+    pub(crate) auto_sql_offset: u64,
+    pub(crate) total_summary_offset: u64,
+    pub(crate) uncompressed_buffer_size: u32,
+}
+
+impl Header {
+    /// Returns the file type.
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Returns the format version.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Returns the zoom level headers.
+    ///
+    /// These are ordered by increasing reduction level.
+    pub fn zoom_headers(&self) -> &[zoom::Header] {
+        &self.zoom_headers
+    }
+
+    /// Returns the number of fields in each record.
+    ///
+    /// This is only meaningful for bigBed files.
+    pub fn field_count(&self) -> u16 {
+        self.field_count
+    }
+
+    /// Returns the number of fields in each record that are standard BED fields.
+    ///
+    /// This is only meaningful for bigBed files.
+    pub fn defined_field_count(&self) -> u16 {
+        self.defined_field_count
+    }
+
+    /// Returns whether data blocks are zlib-compressed.
+    pub fn is_compressed(&self) -> bool {
+        self.uncompressed_buffer_size > 0
+    }
+
+    /// Returns the maximum size of a decompressed data block.
+    ///
+    /// This is 0 if data blocks are not compressed.
+    pub fn uncompressed_buffer_size(&self) -> u32 {
+        self.uncompressed_buffer_size
+    }
+}
+
+pub(crate) fn read_header<R>(reader: &mut R) -> io::Result<Header>
+where
+    R: Read,
+{
+    let kind = match reader.read_u32::<LittleEndian>()? {
+        BIG_WIG_MAGIC_NUMBER => Kind::BigWig,
+        BIG_BED_MAGIC_NUMBER => Kind::BigBed,
+        n if n.swap_bytes() == BIG_WIG_MAGIC_NUMBER || n.swap_bytes() == BIG_BED_MAGIC_NUMBER => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "big-endian BBI files are not supported",
+            ))
+        }
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid BBI header",
+            ))
+        }
+    };
+
+    let version = reader.read_u16::<LittleEndian>()?;
+    let zoom_level_count = reader.read_u16::<LittleEndian>()?;
+    let chromosome_tree_offset = reader.read_u64::<LittleEndian>()?;
+    let full_data_offset = reader.read_u64::<LittleEndian>()?;
+    let full_index_offset = reader.read_u64::<LittleEndian>()?;
+    let field_count = reader.read_u16::<LittleEndian>()?;
+    let defined_field_count = reader.read_u16::<LittleEndian>()?;
+    let auto_sql_offset = reader.read_u64::<LittleEndian>()?;
+    let total_summary_offset = reader.read_u64::<LittleEndian>()?;
+    let uncompressed_buffer_size = reader.read_u32::<LittleEndian>()?;
+    let _extension_offset = reader.read_u64::<LittleEndian>()?;
+
+    let zoom_headers = (0..zoom_level_count)
+        .map(|_| zoom::read_header(reader))
+        .collect::<io::Result<_>>()?;
+
+    Ok(Header {
+        kind,
+        version,
+        zoom_headers,
+        chromosome_tree_offset,
+        full_data_offset,
+        full_index_offset,
+        field_count,
+        defined_field_count,
+        auto_sql_offset,
+        total_summary_offset,
+        uncompressed_buffer_size,
+    })
+}
+
+/// Writes a BBI header, including the zoom level headers.
+pub(crate) fn write_header<W>(writer: &mut W, header: &Header) -> io::Result<()>
+where
+    W: Write,
+{
+    let magic_number = match header.kind {
+        Kind::BigWig => BIG_WIG_MAGIC_NUMBER,
+        Kind::BigBed => BIG_BED_MAGIC_NUMBER,
+    };
+
+    let zoom_level_count = u16::try_from(header.zoom_headers.len())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    writer.write_u32::<LittleEndian>(magic_number)?;
+    writer.write_u16::<LittleEndian>(header.version)?;
+    writer.write_u16::<LittleEndian>(zoom_level_count)?;
+    writer.write_u64::<LittleEndian>(header.chromosome_tree_offset)?;
+    writer.write_u64::<LittleEndian>(header.full_data_offset)?;
+    writer.write_u64::<LittleEndian>(header.full_index_offset)?;
+    writer.write_u16::<LittleEndian>(header.field_count)?;
+    writer.write_u16::<LittleEndian>(header.defined_field_count)?;
+    writer.write_u64::<LittleEndian>(header.auto_sql_offset)?;
+    writer.write_u64::<LittleEndian>(header.total_summary_offset)?;
+    writer.write_u32::<LittleEndian>(header.uncompressed_buffer_size)?;
+    writer.write_u64::<LittleEndian>(0)?; // extensionOffset
+
+    for zoom_header in &header.zoom_headers {
+        zoom::write_header(writer, zoom_header)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_and_write_header() -> io::Result<()> {
+        let mut data = Vec::new();
+        data.extend(BIG_BED_MAGIC_NUMBER.to_le_bytes());
+        data.extend(4u16.to_le_bytes()); // version
+        data.extend(1u16.to_le_bytes()); // zoomLevels
+        data.extend(88u64.to_le_bytes()); // chromosomeTreeOffset
+        data.extend(144u64.to_le_bytes()); // fullDataOffset
+        data.extend(233u64.to_le_bytes()); // fullIndexOffset
+        data.extend(4u16.to_le_bytes()); // fieldCount
+        data.extend(3u16.to_le_bytes()); // definedFieldCount
+        data.extend(0u64.to_le_bytes()); // autoSqlOffset
+        data.extend(0u64.to_le_bytes()); // totalSummaryOffset
+        data.extend(16384u32.to_le_bytes()); // uncompressBufSize
+        data.extend(0u64.to_le_bytes()); // reserved
+        data.extend(10u32.to_le_bytes()); // reductionLevel
+        data.extend(0u32.to_le_bytes()); // reserved
+        data.extend(377u64.to_le_bytes()); // dataOffset
+        data.extend(610u64.to_le_bytes()); // indexOffset
+
+        let header = read_header(&mut &data[..])?;
+
+        assert_eq!(header.kind(), Kind::BigBed);
+        assert_eq!(header.version(), 4);
+        assert_eq!(header.zoom_headers(), [zoom::Header::new(10, 377, 610)]);
+        assert_eq!(header.field_count(), 4);
+        assert_eq!(header.defined_field_count(), 3);
+        assert!(header.is_compressed());
+
+        let mut buf = Vec::new();
+        write_header(&mut buf, &header)?;
+        assert_eq!(buf, data);
+
+        data[..4].copy_from_slice(&BIG_BED_MAGIC_NUMBER.to_be_bytes());
+        assert!(read_header(&mut &data[..]).is_err());
+
+        Ok(())
+    }
+}
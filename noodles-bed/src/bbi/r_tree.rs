@@ -0,0 +1,421 @@
+use std::{
+    collections::HashSet,
+    io::{self, Read, Seek, SeekFrom, Write},
+    ops::Range,
+};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+pub(crate) const MAGIC_NUMBER: u32 = 0x2468ace0;
+
+const HEADER_SIZE: u64 = 48;
+const NODE_HEADER_SIZE: u64 = 4;
+const LEAF_ITEM_SIZE: u64 = 32;
+const INTERNAL_ITEM_SIZE: u64 = 24;
+
+// The maximum number of children of each node.
+const BLOCK_SIZE: usize = 256;
+
+/// A location of a data block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Block {
+    pub(crate) offset: u64,
+    pub(crate) size: u64,
+}
+
+/// A bounding box of (chromosome ID, 0-based base position) pairs: [start, end).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Bounds {
+    pub(crate) start: (u32, u32),
+    pub(crate) end: (u32, u32),
+}
+
+impl Bounds {
+    fn union(&self, other: &Self) -> Self {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    fn write<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writer.write_u32::<LittleEndian>(self.start.0)?;
+        writer.write_u32::<LittleEndian>(self.start.1)?;
+        writer.write_u32::<LittleEndian>(self.end.0)?;
+        writer.write_u32::<LittleEndian>(self.end.1)?;
+        Ok(())
+    }
+
+    fn read<R>(reader: &mut R) -> io::Result<Self>
+    where
+        R: Read,
+    {
+        let start_chromosome_id = reader.read_u32::<LittleEndian>()?;
+        let start_base = reader.read_u32::<LittleEndian>()?;
+        let end_chromosome_id = reader.read_u32::<LittleEndian>()?;
+        let end_base = reader.read_u32::<LittleEndian>()?;
+
+        Ok(Self {
+            start: (start_chromosome_id, start_base),
+            end: (end_chromosome_id, end_base),
+        })
+    }
+
+    fn overlaps(&self, chromosome_id: u32, start: u32, end: u32) -> bool {
+        (chromosome_id, start) < self.end && self.start < (chromosome_id, end)
+    }
+}
+
+/// Searches the R-tree at the given offset for data blocks that overlap the given interval.
+///
+/// The interval is 0-based, half-open.
+///
+/// This returns an error if a node is visited more than once, i.e., the tree has a cycle.
+pub(crate) fn search<R>(
+    reader: &mut R,
+    offset: u64,
+    chromosome_id: u32,
+    start: u32,
+    end: u32,
+) -> io::Result<Vec<Block>>
+where
+    R: Read + Seek,
+{
+    reader.seek(SeekFrom::Start(offset))?;
+    read_tree_header(reader)?;
+
+    let mut blocks = Vec::new();
+    let mut offsets = vec![reader.stream_position()?];
+    let mut visited_offsets = HashSet::new();
+
+    while let Some(offset) = offsets.pop() {
+        if !visited_offsets.insert(offset) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "R-tree index has a cycle",
+            ));
+        }
+
+        reader.seek(SeekFrom::Start(offset))?;
+
+        let is_leaf = reader.read_u8()? != 0;
+        let _reserved = reader.read_u8()?;
+        let count = reader.read_u16::<LittleEndian>()?;
+
+        let mut children = Vec::new();
+
+        for _ in 0..count {
+            let bounds = Bounds::read(reader)?;
+            let data_offset = reader.read_u64::<LittleEndian>()?;
+
+            if is_leaf {
+                let size = reader.read_u64::<LittleEndian>()?;
+
+                if bounds.overlaps(chromosome_id, start, end) {
+                    blocks.push(Block {
+                        offset: data_offset,
+                        size,
+                    });
+                }
+            } else if bounds.overlaps(chromosome_id, start, end) {
+                children.push(data_offset);
+            }
+        }
+
+        offsets.extend(children.into_iter().rev());
+    }
+
+    Ok(blocks)
+}
+
+/// An indexed data block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Leaf {
+    pub(crate) bounds: Bounds,
+    pub(crate) block: Block,
+}
+
+/// Writes an R-tree index of the given data blocks.
+///
+/// `offset` is the position of the writer in the output stream. The blocks must be sorted.
+pub(crate) fn write_r_tree<W>(
+    writer: &mut W,
+    offset: u64,
+    leaves: &[Leaf],
+    items_per_slot: u32,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    // Nodes are grouped by level, from the leaves to the root. Each node holds a range of items
+    // (leaves or nodes) of the level below.
+    let mut levels: Vec<Vec<(Range<usize>, Bounds)>> = Vec::new();
+
+    let mut nodes: Vec<_> = chunk_ranges(leaves.len())
+        .map(|range| {
+            let bounds = union(leaves[range.clone()].iter().map(|leaf| leaf.bounds));
+            (range, bounds)
+        })
+        .collect();
+
+    loop {
+        let is_root = nodes.len() == 1;
+        levels.push(nodes);
+
+        if is_root {
+            break;
+        }
+
+        let children = &levels[levels.len() - 1];
+
+        nodes = chunk_ranges(children.len())
+            .map(|range| {
+                let bounds = union(children[range.clone()].iter().map(|(_, bounds)| *bounds));
+                (range, bounds)
+            })
+            .collect();
+    }
+
+    // Nodes are written from the root to the leaves.
+    let mut node_offsets = vec![Vec::new(); levels.len()];
+    let mut position = offset + HEADER_SIZE;
+
+    for (i, nodes) in levels.iter().enumerate().rev() {
+        let item_size = if i == 0 {
+            LEAF_ITEM_SIZE
+        } else {
+            INTERNAL_ITEM_SIZE
+        };
+
+        for (range, _) in nodes {
+            node_offsets[i].push(position);
+            position += NODE_HEADER_SIZE + range.len() as u64 * item_size;
+        }
+    }
+
+    let (_, root_bounds) = levels[levels.len() - 1][0];
+
+    writer.write_u32::<LittleEndian>(MAGIC_NUMBER)?;
+    writer.write_u32::<LittleEndian>(BLOCK_SIZE as u32)?;
+    writer.write_u64::<LittleEndian>(leaves.len() as u64)?;
+    root_bounds.write(writer)?;
+    writer.write_u64::<LittleEndian>(offset)?; // endFileOffset
+    writer.write_u32::<LittleEndian>(items_per_slot)?;
+    writer.write_u32::<LittleEndian>(0)?; // reserved
+
+    for (i, nodes) in levels.iter().enumerate().rev() {
+        let is_leaf = i == 0;
+
+        for (range, _) in nodes {
+            writer.write_u8(u8::from(is_leaf))?;
+            writer.write_u8(0)?; // reserved
+            writer.write_u16::<LittleEndian>(range.len() as u16)?;
+
+            if is_leaf {
+                for leaf in &leaves[range.clone()] {
+                    leaf.bounds.write(writer)?;
+                    writer.write_u64::<LittleEndian>(leaf.block.offset)?;
+                    writer.write_u64::<LittleEndian>(leaf.block.size)?;
+                }
+            } else {
+                for j in range.clone() {
+                    let (_, bounds) = levels[i - 1][j];
+                    bounds.write(writer)?;
+                    writer.write_u64::<LittleEndian>(node_offsets[i - 1][j])?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Splits `len` items into consecutive ranges of at most `BLOCK_SIZE` items. There is always at
+// least one range.
+pub(crate) fn chunk_ranges(len: usize) -> impl Iterator<Item = Range<usize>> {
+    let chunk_count = ((len + BLOCK_SIZE - 1) / BLOCK_SIZE).max(1);
+    (0..chunk_count).map(move |i| i * BLOCK_SIZE..((i + 1) * BLOCK_SIZE).min(len))
+}
+
+fn union<I>(mut bounds: I) -> Bounds
+where
+    I: Iterator<Item = Bounds>,
+{
+    let first = bounds.next().unwrap_or_default();
+    bounds.fold(first, |acc, b| acc.union(&b))
+}
+
+fn read_tree_header<R>(reader: &mut R) -> io::Result<()>
+where
+    R: Read,
+{
+    let magic_number = reader.read_u32::<LittleEndian>()?;
+
+    if magic_number != MAGIC_NUMBER {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid R-tree index header",
+        ));
+    }
+
+    let _block_size = reader.read_u32::<LittleEndian>()?;
+    let _item_count = reader.read_u64::<LittleEndian>()?;
+    let _bounds = Bounds::read(reader)?;
+    let _end_file_offset = reader.read_u64::<LittleEndian>()?;
+    let _items_per_slot = reader.read_u32::<LittleEndian>()?;
+    let _reserved = reader.read_u32::<LittleEndian>()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn write_bounds(buf: &mut Vec<u8>, bounds: [u32; 4]) {
+        for n in bounds {
+            buf.extend(n.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn test_search() -> io::Result<()> {
+        // root (internal) -> [leaf (sq0:0-8, sq0:13-21), leaf (sq1:0-34)]
+        const HEADER_SIZE: u64 = 48;
+        const ROOT_SIZE: u64 = 4 + 2 * 24;
+        const LEAF_0_SIZE: u64 = 4 + 2 * 32;
+
+        let leaf_0_offset = HEADER_SIZE + ROOT_SIZE;
+        let leaf_1_offset = leaf_0_offset + LEAF_0_SIZE;
+
+        let mut buf = Vec::new();
+        buf.extend(MAGIC_NUMBER.to_le_bytes());
+        buf.extend(2u32.to_le_bytes()); // blockSize
+        buf.extend(3u64.to_le_bytes()); // itemCount
+        write_bounds(&mut buf, [0, 0, 1, 34]);
+        buf.extend(1024u64.to_le_bytes()); // endFileOffset
+        buf.extend(1u32.to_le_bytes()); // itemsPerSlot
+        buf.extend(0u32.to_le_bytes()); // reserved
+
+        buf.extend([0, 0]);
+        buf.extend(2u16.to_le_bytes());
+        write_bounds(&mut buf, [0, 0, 0, 21]);
+        buf.extend(leaf_0_offset.to_le_bytes());
+        write_bounds(&mut buf, [1, 0, 1, 34]);
+        buf.extend(leaf_1_offset.to_le_bytes());
+
+        buf.extend([1, 0]);
+        buf.extend(2u16.to_le_bytes());
+        write_bounds(&mut buf, [0, 0, 0, 8]);
+        buf.extend(1000u64.to_le_bytes());
+        buf.extend(8u64.to_le_bytes());
+        write_bounds(&mut buf, [0, 13, 0, 21]);
+        buf.extend(1008u64.to_le_bytes());
+        buf.extend(8u64.to_le_bytes());
+
+        buf.extend([1, 0]);
+        buf.extend(1u16.to_le_bytes());
+        write_bounds(&mut buf, [1, 0, 1, 34]);
+        buf.extend(1016u64.to_le_bytes());
+        buf.extend(8u64.to_le_bytes());
+
+        let mut reader = Cursor::new(buf);
+
+        assert_eq!(
+            search(&mut reader, 0, 0, 5, 15)?,
+            [
+                Block {
+                    offset: 1000,
+                    size: 8
+                },
+                Block {
+                    offset: 1008,
+                    size: 8
+                },
+            ]
+        );
+
+        assert!(search(&mut reader, 0, 0, 8, 13)?.is_empty());
+
+        assert_eq!(
+            search(&mut reader, 0, 1, 0, 1)?,
+            [Block {
+                offset: 1016,
+                size: 8
+            }]
+        );
+
+        assert!(search(&mut reader, 0, 2, 0, 1)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_with_cycle() {
+        const HEADER_SIZE: u64 = 48;
+
+        let mut buf = Vec::new();
+        buf.extend(MAGIC_NUMBER.to_le_bytes());
+        buf.extend(1u32.to_le_bytes()); // blockSize
+        buf.extend(0u64.to_le_bytes()); // itemCount
+        write_bounds(&mut buf, [0, 0, 0, 8]);
+        buf.extend(1024u64.to_le_bytes()); // endFileOffset
+        buf.extend(1u32.to_le_bytes()); // itemsPerSlot
+        buf.extend(0u32.to_le_bytes()); // reserved
+
+        // root (internal) -> [root]
+        buf.extend([0, 0]);
+        buf.extend(1u16.to_le_bytes());
+        write_bounds(&mut buf, [0, 0, 0, 8]);
+        buf.extend(HEADER_SIZE.to_le_bytes());
+
+        let mut reader = Cursor::new(buf);
+
+        assert!(matches!(
+            search(&mut reader, 0, 0, 0, 8),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_write_r_tree() -> io::Result<()> {
+        const OFFSET: u64 = 8;
+
+        // 2 levels
+        let leaves: Vec<_> = (0..300)
+            .map(|i| Leaf {
+                bounds: Bounds {
+                    start: (0, i * 10),
+                    end: (0, i * 10 + 5),
+                },
+                block: Block {
+                    offset: u64::from(i),
+                    size: 1,
+                },
+            })
+            .collect();
+
+        let mut buf = vec![0; OFFSET as usize];
+        write_r_tree(&mut buf, OFFSET, &leaves, 512)?;
+
+        let mut reader = Cursor::new(buf);
+
+        assert_eq!(
+            search(&mut reader, OFFSET, 0, 2553, 2570)?,
+            [leaves[255].block, leaves[256].block]
+        );
+        assert_eq!(search(&mut reader, OFFSET, 0, 0, 3000)?.len(), 300);
+        assert!(search(&mut reader, OFFSET, 1, 0, 3000)?.is_empty());
+
+        let mut buf = Vec::new();
+        write_r_tree(&mut buf, 0, &[], 512)?;
+        assert!(search(&mut Cursor::new(buf), 0, 0, 0, 1)?.is_empty());
+
+        Ok(())
+    }
+}
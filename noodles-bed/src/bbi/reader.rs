@@ -0,0 +1,718 @@
+//! BBI reader.
+
+mod query;
+
+pub use self::query::Query;
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Seek, SeekFrom},
+    str::FromStr,
+};
+
+use noodles_core::Region;
+
+use self::query::Context;
+use super::{
+    chromosome_tree,
+    header::{self, Kind},
+    r_tree, summary, zoom, Chromosome, Header, Summary, ValueInterval,
+};
+use crate::{
+    record::{self, BedN},
+    Record,
+};
+
+/// A BBI (bigBed or bigWig) reader.
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R>
+where
+    R: Read + Seek,
+{
+    /// Creates a BBI reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bed::bbi;
+    /// let reader = bbi::Reader::new(Cursor::new(Vec::new()));
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bed::bbi;
+    /// let reader = bbi::Reader::new(Cursor::new(Vec::new()));
+    /// assert!(reader.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bed::bbi;
+    /// let mut reader = bbi::Reader::new(Cursor::new(Vec::new()));
+    /// assert!(reader.get_mut().get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bed::bbi;
+    /// let reader = bbi::Reader::new(Cursor::new(Vec::new()));
+    /// assert!(reader.into_inner().into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads the BBI header.
+    ///
+    /// This seeks to the start of the stream. The header includes the zoom level headers.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bed::bbi;
+    ///
+    /// let mut reader = File::open("sample.bb").map(bbi::Reader::new)?;
+    /// let header = reader.read_header()?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_header(&mut self) -> io::Result<Header> {
+        self.inner.seek(SeekFrom::Start(0))?;
+        header::read_header(&mut self.inner)
+    }
+
+    /// Reads all chromosomes (reference sequences) listed in the chromosome B+ tree.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bed::bbi;
+    ///
+    /// let mut reader = File::open("sample.bb").map(bbi::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// for chromosome in reader.read_chromosomes(&header)? {
+    ///     println!("{}\t{}", chromosome.name(), chromosome.size());
+    /// }
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_chromosomes(&mut self, header: &Header) -> io::Result<Vec<Chromosome>> {
+        chromosome_tree::read_chromosomes(&mut self.inner, header.chromosome_tree_offset)
+    }
+
+    /// Searches the chromosome B+ tree for a chromosome with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bed::bbi;
+    ///
+    /// let mut reader = File::open("sample.bb").map(bbi::Reader::new)?;
+    /// let header = reader.read_header()?;
+    /// let chromosome = reader.get_chromosome(&header, "sq0")?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get_chromosome(
+        &mut self,
+        header: &Header,
+        name: &str,
+    ) -> io::Result<Option<Chromosome>> {
+        chromosome_tree::find_chromosome(&mut self.inner, header.chromosome_tree_offset, name)
+    }
+
+    /// Reads the autoSql definition of the records.
+    ///
+    /// This is only present in bigBed files.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bed::bbi;
+    ///
+    /// let mut reader = File::open("sample.bb").map(bbi::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// if let Some(auto_sql) = reader.read_auto_sql(&header)? {
+    ///     println!("{}", auto_sql);
+    /// }
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_auto_sql(&mut self, header: &Header) -> io::Result<Option<String>> {
+        const NUL: u8 = 0x00;
+
+        if header.auto_sql_offset == 0 {
+            return Ok(None);
+        }
+
+        self.inner.seek(SeekFrom::Start(header.auto_sql_offset))?;
+
+        let mut buf = Vec::new();
+        BufReader::new(&mut self.inner).read_until(NUL, &mut buf)?;
+
+        if buf.last() == Some(&NUL) {
+            buf.pop();
+        }
+
+        String::from_utf8(buf)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Reads the summary of all the data in the file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bed::bbi;
+    ///
+    /// let mut reader = File::open("sample.bw").map(bbi::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// if let Some(summary) = reader.read_total_summary(&header)? {
+    ///     println!("{:?}", summary.mean());
+    /// }
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_total_summary(&mut self, header: &Header) -> io::Result<Option<Summary>> {
+        if header.total_summary_offset == 0 {
+            return Ok(None);
+        }
+
+        self.inner
+            .seek(SeekFrom::Start(header.total_summary_offset))?;
+
+        summary::read_summary(&mut self.inner).map(Some)
+    }
+
+    /// Returns an iterator over bigBed records that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bed::bbi;
+    /// use noodles_core::Region;
+    ///
+    /// let mut reader = File::open("sample.bb").map(bbi::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let region: Region = "sq0:8-13".parse()?;
+    ///
+    /// for result in reader.query::<6>(&header, &region)? {
+    ///     let record = result?;
+    ///     println!("{}", record);
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<const N: u8>(
+        &mut self,
+        header: &Header,
+        region: &Region,
+    ) -> io::Result<Query<'_, R, Record<N>>>
+    where
+        Record<N>: BedN<3> + FromStr<Err = record::ParseError>,
+    {
+        validate_kind(header, Kind::BigBed)?;
+
+        let context = self.resolve_region(header, region)?;
+        let blocks = self.search(header.full_index_offset, &context)?;
+
+        Ok(query::bed_records(self, blocks, context))
+    }
+
+    /// Returns an iterator over bigWig value intervals that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bed::bbi;
+    /// use noodles_core::Region;
+    ///
+    /// let mut reader = File::open("sample.bw").map(bbi::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let region: Region = "sq0:8-13".parse()?;
+    ///
+    /// for result in reader.query_values(&header, &region)? {
+    ///     let interval = result?;
+    ///     println!("{}", interval.value());
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_values(
+        &mut self,
+        header: &Header,
+        region: &Region,
+    ) -> io::Result<Query<'_, R, ValueInterval>> {
+        validate_kind(header, Kind::BigWig)?;
+
+        let context = self.resolve_region(header, region)?;
+        let blocks = self.search(header.full_index_offset, &context)?;
+
+        Ok(query::value_intervals(self, blocks, context))
+    }
+
+    /// Returns an iterator over zoom records at the given zoom level that intersect the given
+    /// region.
+    ///
+    /// The zoom level header is one from [`Header::zoom_headers`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bed::bbi;
+    /// use noodles_core::Region;
+    ///
+    /// let mut reader = File::open("sample.bw").map(bbi::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let region: Region = "sq0".parse()?;
+    ///
+    /// if let Some(zoom_header) = header.zoom_headers().first() {
+    ///     for result in reader.query_zoom(&header, zoom_header, &region)? {
+    ///         let record = result?;
+    ///         println!("{:?}", record.mean());
+    ///     }
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_zoom(
+        &mut self,
+        header: &Header,
+        zoom_header: &zoom::Header,
+        region: &Region,
+    ) -> io::Result<Query<'_, R, zoom::Record>> {
+        let context = self.resolve_region(header, region)?;
+        let blocks = self.search(zoom_header.index_offset(), &context)?;
+        Ok(query::zoom_records(self, blocks, context))
+    }
+
+    fn resolve_region(&mut self, header: &Header, region: &Region) -> io::Result<Context> {
+        let chromosome = self.get_chromosome(header, region.name())?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid reference sequence name",
+            )
+        })?;
+
+        let interval = region.interval();
+
+        let start = interval
+            .start()
+            .map(|position| usize::from(position) - 1)
+            .unwrap_or(0);
+
+        let end = interval
+            .end()
+            .map(usize::from)
+            .unwrap_or(chromosome.size() as usize);
+
+        Ok(Context {
+            chromosome,
+            start: u32::try_from(start).unwrap_or(u32::MAX),
+            end: u32::try_from(end).unwrap_or(u32::MAX),
+            uncompressed_buffer_size: if header.is_compressed() {
+                Some(header.uncompressed_buffer_size())
+            } else {
+                None
+            },
+        })
+    }
+
+    fn search(&mut self, index_offset: u64, context: &Context) -> io::Result<Vec<r_tree::Block>> {
+        r_tree::search(
+            &mut self.inner,
+            index_offset,
+            context.chromosome.id(),
+            context.start,
+            context.end,
+        )
+    }
+}
+
+fn validate_kind(header: &Header, kind: Kind) -> io::Result<()> {
+    if header.kind() == kind {
+        Ok(())
+    } else {
+        let message = match kind {
+            Kind::BigWig => "not a bigWig file",
+            Kind::BigBed => "not a bigBed file",
+        };
+
+        Err(io::Error::new(io::ErrorKind::InvalidInput, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use flate2::{write::ZlibEncoder, Compression};
+    use noodles_core::Position;
+
+    use super::*;
+    use crate::bbi::header::{BIG_BED_MAGIC_NUMBER, BIG_WIG_MAGIC_NUMBER};
+
+    type Blocks<'a> = &'a [([u32; 4], Vec<u8>)];
+
+    fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data)?;
+        encoder.finish()
+    }
+
+    // Writes compressed data blocks followed by an R-tree index of them. This returns the index
+    // offset.
+    fn write_blocks(buf: &mut Vec<u8>, blocks: Blocks<'_>) -> io::Result<u64> {
+        let mut leaves = Vec::new();
+
+        for (bounds, data) in blocks {
+            let data = compress(data)?;
+            leaves.push((*bounds, buf.len() as u64, data.len() as u64));
+            buf.extend(data);
+        }
+
+        let index_offset = buf.len() as u64;
+
+        buf.extend(r_tree::MAGIC_NUMBER.to_le_bytes());
+        buf.extend(256u32.to_le_bytes()); // blockSize
+        buf.extend((leaves.len() as u64).to_le_bytes()); // itemCount
+        buf.extend([0; 16]); // bounds
+        buf.extend(0u64.to_le_bytes()); // endFileOffset
+        buf.extend(1u32.to_le_bytes()); // itemsPerSlot
+        buf.extend(0u32.to_le_bytes()); // reserved
+
+        buf.extend([1, 0]);
+        buf.extend((leaves.len() as u16).to_le_bytes());
+
+        for (bounds, offset, size) in leaves {
+            for n in bounds {
+                buf.extend(n.to_le_bytes());
+            }
+
+            buf.extend(offset.to_le_bytes());
+            buf.extend(size.to_le_bytes());
+        }
+
+        Ok(index_offset)
+    }
+
+    fn build(
+        magic_number: u32,
+        field_count: u16,
+        blocks: Blocks<'_>,
+        zoom_blocks: Blocks<'_>,
+    ) -> io::Result<Vec<u8>> {
+        // header + 1 zoom header
+        let mut buf = vec![0; 64 + 24];
+
+        let total_summary_offset = buf.len() as u64;
+        buf.extend(20u64.to_le_bytes()); // basesCovered
+        buf.extend(0.5f64.to_le_bytes()); // minVal
+        buf.extend(2.0f64.to_le_bytes()); // maxVal
+        buf.extend(25.0f64.to_le_bytes()); // sumData
+        buf.extend(40.0f64.to_le_bytes()); // sumSquares
+
+        let auto_sql_offset = buf.len() as u64;
+        buf.extend(b"table bed3\n\"BED3\"\n(\n)\x00");
+
+        let chromosome_tree_offset = buf.len() as u64;
+        buf.extend(chromosome_tree::MAGIC_NUMBER.to_le_bytes());
+        buf.extend(256u32.to_le_bytes()); // blockSize
+        buf.extend(4u32.to_le_bytes()); // keySize
+        buf.extend(8u32.to_le_bytes()); // valSize
+        buf.extend(2u64.to_le_bytes()); // itemCount
+        buf.extend(0u64.to_le_bytes()); // reserved
+        buf.extend([1, 0]);
+        buf.extend(2u16.to_le_bytes());
+        buf.extend(b"sq0\x00");
+        buf.extend(0u32.to_le_bytes());
+        buf.extend(1000u32.to_le_bytes());
+        buf.extend(b"sq1\x00");
+        buf.extend(1u32.to_le_bytes());
+        buf.extend(500u32.to_le_bytes());
+
+        let full_data_offset = buf.len() as u64;
+        buf.extend((blocks.len() as u64).to_le_bytes());
+        let full_index_offset = write_blocks(&mut buf, blocks)?;
+
+        let zoom_data_offset = buf.len() as u64;
+        buf.extend((zoom_blocks.len() as u32).to_le_bytes());
+        let zoom_index_offset = write_blocks(&mut buf, zoom_blocks)?;
+
+        let mut header = Vec::new();
+        header.extend(magic_number.to_le_bytes());
+        header.extend(4u16.to_le_bytes()); // version
+        header.extend(1u16.to_le_bytes()); // zoomLevels
+        header.extend(chromosome_tree_offset.to_le_bytes());
+        header.extend(full_data_offset.to_le_bytes());
+        header.extend(full_index_offset.to_le_bytes());
+        header.extend(field_count.to_le_bytes());
+        header.extend(field_count.min(12).to_le_bytes()); // definedFieldCount
+        header.extend(auto_sql_offset.to_le_bytes());
+        header.extend(total_summary_offset.to_le_bytes());
+        header.extend(16384u32.to_le_bytes()); // uncompressBufSize
+        header.extend(0u64.to_le_bytes()); // extensionOffset
+        header.extend(100u32.to_le_bytes()); // reductionLevel
+        header.extend(0u32.to_le_bytes()); // reserved
+        header.extend(zoom_data_offset.to_le_bytes());
+        header.extend(zoom_index_offset.to_le_bytes());
+
+        buf[..header.len()].copy_from_slice(&header);
+
+        Ok(buf)
+    }
+
+    fn bed_record(chromosome_id: u32, start: u32, end: u32, rest: &str) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend(chromosome_id.to_le_bytes());
+        buf.extend(start.to_le_bytes());
+        buf.extend(end.to_le_bytes());
+        buf.extend(rest.as_bytes());
+        buf.push(0x00);
+        buf
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn zoom_record(
+        chromosome_id: u32,
+        start: u32,
+        end: u32,
+        valid_count: u32,
+        min_value: f32,
+        max_value: f32,
+        sum: f32,
+        sum_squares: f32,
+    ) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        for n in [chromosome_id, start, end, valid_count] {
+            buf.extend(n.to_le_bytes());
+        }
+
+        for n in [min_value, max_value, sum, sum_squares] {
+            buf.extend(n.to_le_bytes());
+        }
+
+        buf
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let blocks = [
+            (
+                [0, 7, 0, 34],
+                [
+                    bed_record(0, 7, 13, "r0\t0\t+"),
+                    bed_record(0, 20, 34, "r1\t0\t-"),
+                ]
+                .concat(),
+            ),
+            ([1, 7, 1, 13], bed_record(1, 7, 13, "r2\t0\t+")),
+        ];
+
+        let zoom_blocks = [(
+            [0, 7, 1, 13],
+            [
+                zoom_record(0, 7, 34, 20, 1.0, 1.0, 20.0, 20.0),
+                zoom_record(1, 7, 13, 6, 1.0, 1.0, 6.0, 6.0),
+            ]
+            .concat(),
+        )];
+
+        let data = build(BIG_BED_MAGIC_NUMBER, 6, &blocks, &zoom_blocks)?;
+        let mut reader = Reader::new(Cursor::new(data));
+
+        let header = reader.read_header()?;
+        assert_eq!(header.kind(), Kind::BigBed);
+        assert_eq!(header.field_count(), 6);
+
+        assert_eq!(
+            reader.read_chromosomes(&header)?,
+            [
+                Chromosome::new("sq0", 0, 1000),
+                Chromosome::new("sq1", 1, 500)
+            ]
+        );
+
+        assert_eq!(
+            reader.read_auto_sql(&header)?.as_deref(),
+            Some("table bed3\n\"BED3\"\n(\n)")
+        );
+
+        let summary = reader.read_total_summary(&header)?;
+        assert_eq!(summary.and_then(|s| s.mean()), Some(1.25));
+
+        let region = "sq0:10-25".parse()?;
+        let actual: Vec<_> = reader
+            .query::<6>(&header, &region)?
+            .collect::<io::Result<_>>()?;
+        let expected: Vec<Record<6>> = vec![
+            "sq0\t7\t13\tr0\t0\t+".parse()?,
+            "sq0\t20\t34\tr1\t0\t-".parse()?,
+        ];
+        assert_eq!(actual, expected);
+
+        let region = "sq0:14-21".parse()?;
+        let actual: Vec<_> = reader
+            .query::<4>(&header, &region)?
+            .collect::<io::Result<_>>()?;
+        let expected: Vec<Record<4>> = vec!["sq0\t20\t34\tr1\t0\t-".parse()?];
+        assert_eq!(actual, expected);
+
+        let region = "sq1".parse()?;
+        let actual: Vec<_> = reader
+            .query::<3>(&header, &region)?
+            .collect::<io::Result<_>>()?;
+        let expected: Vec<Record<3>> = vec!["sq1\t7\t13\tr2\t0\t+".parse()?];
+        assert_eq!(actual, expected);
+
+        let region = "sq2".parse()?;
+        assert!(reader.query::<3>(&header, &region).is_err());
+        assert!(reader.query_values(&header, &"sq0".parse()?).is_err());
+
+        let zoom_header = header.zoom_headers()[0];
+        assert_eq!(zoom_header.reduction_level(), 100);
+
+        let region = "sq1:1-100".parse()?;
+        let actual: Vec<_> = reader
+            .query_zoom(&header, &zoom_header, &region)?
+            .collect::<io::Result<_>>()?;
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].start_position(), Position::try_from(8)?);
+        assert_eq!(actual[0].end_position(), Position::try_from(13)?);
+        assert_eq!(actual[0].valid_count(), 6);
+        assert_eq!(actual[0].mean(), Some(1.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_values() -> Result<(), Box<dyn std::error::Error>> {
+        fn section(header: [u32; 5], ty: u8, items: &[u32], values: &[f32]) -> Vec<u8> {
+            let mut buf = Vec::new();
+
+            for n in header {
+                buf.extend(n.to_le_bytes());
+            }
+
+            buf.extend([ty, 0]);
+            buf.extend((values.len() as u16).to_le_bytes());
+
+            let positions_per_item = items.len() / values.len();
+
+            for (i, value) in values.iter().enumerate() {
+                for n in &items[i * positions_per_item..(i + 1) * positions_per_item] {
+                    buf.extend(n.to_le_bytes());
+                }
+
+                buf.extend(value.to_le_bytes());
+            }
+
+            buf
+        }
+
+        fn interval(start: usize, end: usize, value: f32) -> ValueInterval {
+            ValueInterval::new(
+                Position::new(start).unwrap(),
+                Position::new(end).unwrap(),
+                value,
+            )
+        }
+
+        let blocks = [
+            // bedGraph
+            (
+                [0, 0, 0, 30],
+                section([0, 0, 30, 0, 0], 1, &[0, 10, 20, 30], &[1.0, 2.0]),
+            ),
+            // fixedStep
+            (
+                [0, 100, 0, 125],
+                section([0, 100, 125, 10, 5], 3, &[], &[0.5, 1.5, 2.5]),
+            ),
+            // variableStep
+            (
+                [1, 5, 1, 53],
+                section([1, 5, 53, 0, 3], 2, &[5, 50], &[3.0, 4.0]),
+            ),
+        ];
+
+        let zoom_blocks = [(
+            [0, 0, 0, 200],
+            [
+                zoom_record(0, 0, 100, 20, 1.0, 2.0, 30.0, 50.0),
+                zoom_record(0, 100, 200, 15, 0.5, 2.5, 22.5, 40.0),
+            ]
+            .concat(),
+        )];
+
+        let data = build(BIG_WIG_MAGIC_NUMBER, 0, &blocks, &zoom_blocks)?;
+        let mut reader = Reader::new(Cursor::new(data));
+
+        let header = reader.read_header()?;
+        assert_eq!(header.kind(), Kind::BigWig);
+
+        let region = "sq0:5-25".parse()?;
+        let actual: Vec<_> = reader
+            .query_values(&header, &region)?
+            .collect::<io::Result<_>>()?;
+        assert_eq!(actual, [interval(1, 10, 1.0), interval(21, 30, 2.0)]);
+
+        let region = "sq0:111-121".parse()?;
+        let actual: Vec<_> = reader
+            .query_values(&header, &region)?
+            .collect::<io::Result<_>>()?;
+        assert_eq!(actual, [interval(111, 115, 1.5), interval(121, 125, 2.5)]);
+
+        let region = "sq1".parse()?;
+        let actual: Vec<_> = reader
+            .query_values(&header, &region)?
+            .collect::<io::Result<_>>()?;
+        assert_eq!(actual, [interval(6, 8, 3.0), interval(51, 53, 4.0)]);
+
+        assert!(reader.query::<3>(&header, &region).is_err());
+
+        let zoom_header = header.zoom_headers()[0];
+        let region = "sq0:150-160".parse()?;
+        let actual: Vec<_> = reader
+            .query_zoom(&header, &zoom_header, &region)?
+            .collect::<io::Result<_>>()?;
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].min_value(), 0.5);
+        assert_eq!(actual[0].max_value(), 2.5);
+        assert_eq!(actual[0].mean(), Some(1.5));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,339 @@
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    str::FromStr,
+    vec,
+};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use flate2::read::ZlibDecoder;
+
+use super::Reader;
+use crate::{
+    bbi::{interval_positions, r_tree::Block, zoom, Chromosome, ValueInterval},
+    record::{self, BedN},
+    Record,
+};
+
+const NUL: u8 = 0x00;
+
+const BED_GRAPH_SECTION: u8 = 1;
+const VARIABLE_STEP_SECTION: u8 = 2;
+const FIXED_STEP_SECTION: u8 = 3;
+
+pub(super) struct Context {
+    pub(super) chromosome: Chromosome,
+    // 0-based, half-open
+    pub(super) start: u32,
+    pub(super) end: u32,
+    // The maximum size of an inflated data block, if data blocks are compressed.
+    pub(super) uncompressed_buffer_size: Option<u32>,
+}
+
+impl Context {
+    fn contains(&self, chromosome_id: u32, start: u32, end: u32) -> bool {
+        chromosome_id == self.chromosome.id() && start < self.end && self.start < end
+    }
+}
+
+type Decode<T> = fn(&[u8], &Context) -> io::Result<Vec<T>>;
+
+/// An iterator over items of a BBI file that intersect a given region.
+///
+/// This is created by calling [`Reader::query`], [`Reader::query_values`], or
+/// [`Reader::query_zoom`].
+pub struct Query<'r, R, T> {
+    reader: &'r mut Reader<R>,
+    blocks: vec::IntoIter<Block>,
+    items: vec::IntoIter<T>,
+    context: Context,
+    decode: Decode<T>,
+}
+
+impl<'r, R, T> Query<'r, R, T>
+where
+    R: Read + Seek,
+{
+    fn new(
+        reader: &'r mut Reader<R>,
+        blocks: Vec<Block>,
+        context: Context,
+        decode: Decode<T>,
+    ) -> Self {
+        Self {
+            reader,
+            blocks: blocks.into_iter(),
+            items: Vec::new().into_iter(),
+            context,
+            decode,
+        }
+    }
+
+    fn read_block(&mut self, block: Block) -> io::Result<Vec<u8>> {
+        let reader = &mut self.reader.inner;
+        reader.seek(SeekFrom::Start(block.offset))?;
+
+        // The block size is untrusted, so the buffer is not preallocated.
+        let mut buf = Vec::new();
+        reader.by_ref().take(block.size).read_to_end(&mut buf)?;
+
+        if (buf.len() as u64) < block.size {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+
+        if let Some(uncompressed_buffer_size) = self.context.uncompressed_buffer_size {
+            let max_len = u64::from(uncompressed_buffer_size);
+
+            let mut decoder = ZlibDecoder::new(&buf[..]).take(max_len + 1);
+            let mut dst = Vec::new();
+            decoder.read_to_end(&mut dst)?;
+
+            if dst.len() as u64 > max_len {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "data block is larger than the uncompressed buffer size",
+                ));
+            }
+
+            Ok(dst)
+        } else {
+            Ok(buf)
+        }
+    }
+}
+
+impl<'r, R, T> Iterator for Query<'r, R, T>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.next() {
+                return Some(Ok(item));
+            }
+
+            let block = self.blocks.next()?;
+
+            let result = self
+                .read_block(block)
+                .and_then(|buf| (self.decode)(&buf, &self.context));
+
+            match result {
+                Ok(items) => self.items = items.into_iter(),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+pub(super) fn bed_records<'r, R, const N: u8>(
+    reader: &'r mut Reader<R>,
+    blocks: Vec<Block>,
+    context: Context,
+) -> Query<'r, R, Record<N>>
+where
+    R: Read + Seek,
+    Record<N>: BedN<3> + FromStr<Err = record::ParseError>,
+{
+    Query::new(reader, blocks, context, decode_bed_records::<N>)
+}
+
+pub(super) fn value_intervals<R>(
+    reader: &mut Reader<R>,
+    blocks: Vec<Block>,
+    context: Context,
+) -> Query<'_, R, ValueInterval>
+where
+    R: Read + Seek,
+{
+    Query::new(reader, blocks, context, decode_value_intervals)
+}
+
+pub(super) fn zoom_records<R>(
+    reader: &mut Reader<R>,
+    blocks: Vec<Block>,
+    context: Context,
+) -> Query<'_, R, zoom::Record>
+where
+    R: Read + Seek,
+{
+    Query::new(reader, blocks, context, decode_zoom_records)
+}
+
+// A bigBed data block is a list of records, each with a chromosome ID, start, end, and the
+// remaining tab-delimited fields as a NUL-terminated string.
+fn decode_bed_records<const N: u8>(mut buf: &[u8], context: &Context) -> io::Result<Vec<Record<N>>>
+where
+    Record<N>: BedN<3> + FromStr<Err = record::ParseError>,
+{
+    let mut records = Vec::new();
+
+    while !buf.is_empty() {
+        let chromosome_id = buf.read_u32::<LittleEndian>()?;
+        let start = buf.read_u32::<LittleEndian>()?;
+        let end = buf.read_u32::<LittleEndian>()?;
+
+        let len = buf.iter().position(|&b| b == NUL).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "unterminated bigBed record")
+        })?;
+
+        let rest = &buf[..len];
+        buf = &buf[len + 1..];
+
+        if !context.contains(chromosome_id, start, end) {
+            continue;
+        }
+
+        let rest =
+            std::str::from_utf8(rest).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut line = format!("{}\t{}\t{}", context.chromosome.name(), start, end);
+
+        if !rest.is_empty() {
+            line.push('\t');
+            line.push_str(rest);
+        }
+
+        let record = line
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+// A bigWig data block is a section header followed by items in bedGraph, variableStep, or
+// fixedStep form.
+fn decode_value_intervals(mut buf: &[u8], context: &Context) -> io::Result<Vec<ValueInterval>> {
+    let chromosome_id = buf.read_u32::<LittleEndian>()?;
+    let section_start = buf.read_u32::<LittleEndian>()?;
+    let _section_end = buf.read_u32::<LittleEndian>()?;
+    let item_step = buf.read_u32::<LittleEndian>()?;
+    let item_span = buf.read_u32::<LittleEndian>()?;
+    let ty = buf.read_u8()?;
+    let _reserved = buf.read_u8()?;
+    let item_count = buf.read_u16::<LittleEndian>()?;
+
+    let mut intervals = Vec::new();
+
+    for i in 0..u32::from(item_count) {
+        let (start, end) = match ty {
+            BED_GRAPH_SECTION => {
+                let start = buf.read_u32::<LittleEndian>()?;
+                let end = buf.read_u32::<LittleEndian>()?;
+                (start, end)
+            }
+            VARIABLE_STEP_SECTION => {
+                let start = buf.read_u32::<LittleEndian>()?;
+                (start, start.saturating_add(item_span))
+            }
+            FIXED_STEP_SECTION => {
+                let start = section_start.saturating_add(i.saturating_mul(item_step));
+                (start, start.saturating_add(item_span))
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid bigWig section type",
+                ))
+            }
+        };
+
+        let value = buf.read_f32::<LittleEndian>()?;
+
+        if context.contains(chromosome_id, start, end) {
+            let (start_position, end_position) = interval_positions(start, end)?;
+            intervals.push(ValueInterval::new(start_position, end_position, value));
+        }
+    }
+
+    Ok(intervals)
+}
+
+fn decode_zoom_records(mut buf: &[u8], context: &Context) -> io::Result<Vec<zoom::Record>> {
+    let mut records = Vec::with_capacity(buf.len() / zoom::RECORD_SIZE);
+
+    while !buf.is_empty() {
+        let record = zoom::read_record(&mut buf)?;
+
+        let start = (usize::from(record.start_position()) - 1) as u32;
+        let end = usize::from(record.end_position()) as u32;
+
+        if context.contains(record.reference_sequence_id, start, end) {
+            records.push(record);
+        }
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use super::*;
+
+    fn build_query(
+        reader: &mut Reader<Cursor<Vec<u8>>>,
+        uncompressed_buffer_size: Option<u32>,
+    ) -> Query<'_, Cursor<Vec<u8>>, Record<3>> {
+        let context = Context {
+            chromosome: Chromosome::new("sq0", 0, 8),
+            start: 0,
+            end: 8,
+            uncompressed_buffer_size,
+        };
+
+        bed_records(reader, Vec::new(), context)
+    }
+
+    #[test]
+    fn test_read_block() -> io::Result<()> {
+        let data = b"noodles".to_vec();
+
+        let mut reader = Reader::new(Cursor::new(data.clone()));
+        let mut query = build_query(&mut reader, None);
+        let block = Block { offset: 0, size: 7 };
+        assert_eq!(query.read_block(block)?, data);
+
+        let block = Block {
+            offset: 0,
+            size: u64::MAX,
+        };
+        assert!(matches!(
+            query.read_block(block),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_block_with_compressed_data() -> io::Result<()> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&[0; 1 << 20])?;
+        let data = encoder.finish()?;
+        let block = Block {
+            offset: 0,
+            size: data.len() as u64,
+        };
+
+        let mut reader = Reader::new(Cursor::new(data));
+
+        let mut query = build_query(&mut reader, Some(1 << 20));
+        assert_eq!(query.read_block(block)?.len(), 1 << 20);
+
+        let mut query = build_query(&mut reader, Some(1024));
+        assert!(matches!(
+            query.read_block(block),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
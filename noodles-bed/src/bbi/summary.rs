@@ -0,0 +1,98 @@
+use std::io::{self, Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+/// The size of a total summary in bytes.
+pub(crate) const SIZE: u64 = 40;
+
+/// A BBI total summary.
+///
+/// This summarizes the values of all the data in the file.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Summary {
+    pub(crate) bases_covered: u64,
+    pub(crate) min_value: f64,
+    pub(crate) max_value: f64,
+    pub(crate) sum: f64,
+    pub(crate) sum_squares: f64,
+}
+
+impl Summary {
+    /// Returns the number of bases with data.
+    pub fn bases_covered(&self) -> u64 {
+        self.bases_covered
+    }
+
+    /// Returns the minimum value.
+    pub fn min_value(&self) -> f64 {
+        self.min_value
+    }
+
+    /// Returns the maximum value.
+    pub fn max_value(&self) -> f64 {
+        self.max_value
+    }
+
+    /// Returns the sum of values.
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Returns the sum of the squares of values.
+    pub fn sum_squares(&self) -> f64 {
+        self.sum_squares
+    }
+
+    /// Returns the mean value.
+    ///
+    /// This is `None` if there are no bases with data.
+    pub fn mean(&self) -> Option<f64> {
+        if self.bases_covered == 0 {
+            None
+        } else {
+            Some(self.sum / self.bases_covered as f64)
+        }
+    }
+
+    /// Adds a value that spans `len` bases.
+    pub(crate) fn add(&mut self, value: f64, len: u64) {
+        if self.bases_covered == 0 {
+            self.min_value = value;
+            self.max_value = value;
+        } else {
+            self.min_value = self.min_value.min(value);
+            self.max_value = self.max_value.max(value);
+        }
+
+        let n = len as f64;
+
+        self.bases_covered += len;
+        self.sum += value * n;
+        self.sum_squares += value * value * n;
+    }
+}
+
+pub(crate) fn read_summary<R>(reader: &mut R) -> io::Result<Summary>
+where
+    R: Read,
+{
+    Ok(Summary {
+        bases_covered: reader.read_u64::<LittleEndian>()?,
+        min_value: reader.read_f64::<LittleEndian>()?,
+        max_value: reader.read_f64::<LittleEndian>()?,
+        sum: reader.read_f64::<LittleEndian>()?,
+        sum_squares: reader.read_f64::<LittleEndian>()?,
+    })
+}
+
+pub(crate) fn write_summary<W>(writer: &mut W, summary: &Summary) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u64::<LittleEndian>(summary.bases_covered)?;
+    writer.write_f64::<LittleEndian>(summary.min_value)?;
+    writer.write_f64::<LittleEndian>(summary.max_value)?;
+    writer.write_f64::<LittleEndian>(summary.sum)?;
+    writer.write_f64::<LittleEndian>(summary.sum_squares)?;
+    Ok(())
+}
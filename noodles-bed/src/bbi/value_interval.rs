@@ -0,0 +1,86 @@
+use noodles_core::Position;
+
+/// A bigWig value interval.
+///
+/// This is a value over a range of positions on a reference sequence.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ValueInterval {
+    start_position: Position,
+    end_position: Position,
+    value: f32,
+}
+
+impl ValueInterval {
+    /// Creates a value interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bbi::ValueInterval;
+    /// use noodles_core::Position;
+    ///
+    /// let interval = ValueInterval::new(
+    ///     Position::try_from(8)?,
+    ///     Position::try_from(13)?,
+    ///     0.5,
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn new(start_position: Position, end_position: Position, value: f32) -> Self {
+        Self {
+            start_position,
+            end_position,
+            value,
+        }
+    }
+
+    /// Returns the start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bbi::ValueInterval;
+    /// use noodles_core::Position;
+    ///
+    /// let start = Position::try_from(8)?;
+    /// let interval = ValueInterval::new(start, Position::try_from(13)?, 0.5);
+    /// assert_eq!(interval.start_position(), start);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn start_position(&self) -> Position {
+        self.start_position
+    }
+
+    /// Returns the end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bbi::ValueInterval;
+    /// use noodles_core::Position;
+    ///
+    /// let end = Position::try_from(13)?;
+    /// let interval = ValueInterval::new(Position::try_from(8)?, end, 0.5);
+    /// assert_eq!(interval.end_position(), end);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn end_position(&self) -> Position {
+        self.end_position
+    }
+
+    /// Returns the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::bbi::ValueInterval;
+    /// use noodles_core::Position;
+    ///
+    /// let interval = ValueInterval::new(Position::try_from(8)?, Position::try_from(13)?, 0.5);
+    /// assert_eq!(interval.value(), 0.5);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+}
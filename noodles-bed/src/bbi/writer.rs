@@ -0,0 +1,732 @@
+//! bigBed writer.
+
+mod builder;
+mod coverage;
+mod zoom_level;
+
+pub use self::builder::Builder;
+
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{self, Seek, SeekFrom, Write},
+};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use flate2::{write::ZlibEncoder, Compression};
+
+use self::{
+    coverage::{Coverage, Segment},
+    zoom_level::ZoomLevel,
+};
+use super::{
+    chromosome_tree::write_chromosome_tree,
+    header::{self, Kind},
+    r_tree::{write_r_tree, Block, Bounds, Leaf},
+    summary, zoom, Chromosome, Header, Summary,
+};
+use crate::{auto_sql::AutoSql, record::BedN, Record};
+
+pub(crate) const DEFAULT_ITEMS_PER_SLOT: usize = 512;
+
+const MAX_ZOOM_LEVEL_COUNT: usize = 10;
+const ZOOM_INCREMENT: u32 = 4;
+
+const NUL: u8 = 0x00;
+
+#[derive(Default)]
+struct DataBlock {
+    chromosome_id: u32,
+    buf: Vec<u8>,
+    // 0-based, half-open
+    intervals: Vec<(u32, u32)>,
+}
+
+/// A bigBed writer.
+///
+/// Records must be sorted by chromosome, in the order the chromosome sizes are given, and then
+/// by start position. Data blocks are written as records are added. The header, R-tree index,
+/// and zoom levels are written by [`Self::finish`], which must be called to produce a valid
+/// file.
+///
+/// Zoom levels summarize the coverage depth of the records.
+pub struct Writer<W>
+where
+    W: Write + Seek,
+{
+    inner: W,
+    chromosomes: Vec<Chromosome>,
+    chromosome_indices: HashMap<String, usize>,
+    auto_sql: Option<String>,
+    items_per_slot: usize,
+
+    is_initialized: bool,
+    auto_sql_offset: u64,
+    chromosome_tree_offset: u64,
+    full_data_offset: u64,
+
+    field_counts: Option<(u16, u16)>,
+    record_count: u64,
+    last_position: Option<(u32, u32)>,
+
+    block: DataBlock,
+    leaves: Vec<Leaf>,
+    max_block_size: usize,
+
+    coverage: Coverage,
+    segments: Vec<Segment>,
+    summary: Summary,
+    zoom_levels: Option<Vec<ZoomLevel>>,
+}
+
+impl<W> Writer<W>
+where
+    W: Write + Seek,
+{
+    /// Returns a builder for a bigBed writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bed::bbi;
+    ///
+    /// let writer = bbi::Writer::builder(Cursor::new(Vec::new()))
+    ///     .set_chromosome_sizes([("sq0", 8)])
+    ///     .build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
+    }
+
+    fn new(
+        inner: W,
+        chromosomes: Vec<Chromosome>,
+        auto_sql: Option<String>,
+        items_per_slot: usize,
+    ) -> Self {
+        Self {
+            inner,
+            chromosomes,
+            chromosome_indices: HashMap::new(),
+            auto_sql,
+            items_per_slot,
+
+            is_initialized: false,
+            auto_sql_offset: 0,
+            chromosome_tree_offset: 0,
+            full_data_offset: 0,
+
+            field_counts: None,
+            record_count: 0,
+            last_position: None,
+
+            block: DataBlock::default(),
+            leaves: Vec::new(),
+            max_block_size: 0,
+
+            coverage: Coverage::default(),
+            segments: Vec::new(),
+            summary: Summary::default(),
+            zoom_levels: None,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bed::bbi;
+    /// let writer = bbi::Writer::builder(Cursor::new(Vec::new())).build();
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writes a BED record.
+    ///
+    /// All records must have the same number of fields. If an autoSql definition is set, this is
+    /// also the number of fields it defines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// use noodles_bed::{self as bed, bbi};
+    ///
+    /// let mut writer = bbi::Writer::builder(Cursor::new(Vec::new()))
+    ///     .set_chromosome_sizes([("sq0", 8)])
+    ///     .build();
+    ///
+    /// let record: bed::Record<3> = "sq0\t1\t5".parse()?;
+    /// writer.write_record(&record)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_record<const N: u8>(&mut self, record: &Record<N>) -> io::Result<()>
+    where
+        Record<N>: BedN<3> + fmt::Display,
+    {
+        self.initialize()?;
+
+        let chromosome = self
+            .chromosome_indices
+            .get(record.reference_sequence_name())
+            .map(|&i| &self.chromosomes[i])
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid reference sequence name",
+                )
+            })?;
+
+        let chromosome_id = chromosome.id();
+
+        let start = u32::try_from(usize::from(record.start_position()) - 1)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let end = u32::try_from(usize::from(record.end_position()))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        if end > chromosome.size() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record end position is greater than the reference sequence length",
+            ));
+        }
+
+        if let Some(last_position) = self.last_position {
+            if (chromosome_id, start) < last_position {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "records are not sorted",
+                ));
+            }
+        }
+
+        self.last_position = Some((chromosome_id, start));
+
+        let line = record.to_string();
+        let rest = line.splitn(4, '\t').nth(3).unwrap_or_default();
+
+        let optional_field_count = if rest.is_empty() {
+            0
+        } else {
+            rest.split('\t').count()
+        };
+
+        let field_count = u16::try_from(3 + optional_field_count)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        match self.field_counts {
+            Some((expected_field_count, _)) => {
+                if field_count != expected_field_count {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "record field count does not match the field count of previous records",
+                    ));
+                }
+            }
+            None => {
+                if let Some(auto_sql) = &self.auto_sql {
+                    validate_auto_sql_field_count(auto_sql, field_count)?;
+                }
+
+                let defined_field_count = u16::from(N);
+                self.field_counts = Some((field_count, defined_field_count));
+            }
+        }
+
+        if self.block.intervals.len() >= self.items_per_slot
+            || (!self.block.intervals.is_empty() && self.block.chromosome_id != chromosome_id)
+        {
+            self.flush_block()?;
+        }
+
+        let buf = &mut self.block.buf;
+        buf.write_u32::<LittleEndian>(chromosome_id)?;
+        buf.write_u32::<LittleEndian>(start)?;
+        buf.write_u32::<LittleEndian>(end)?;
+        buf.write_all(rest.as_bytes())?;
+        buf.write_u8(NUL)?;
+
+        self.block.chromosome_id = chromosome_id;
+        self.block.intervals.push((start, end));
+        self.record_count += 1;
+
+        Ok(())
+    }
+
+    /// Writes the index and zoom levels and finalizes the header.
+    ///
+    /// This returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bed::bbi;
+    ///
+    /// let writer = bbi::Writer::builder(Cursor::new(Vec::new()))
+    ///     .set_chromosome_sizes([("sq0", 8)])
+    ///     .build();
+    ///
+    /// let data = writer.finish()?;
+    ///
+    /// let mut reader = bbi::Reader::new(data);
+    /// let header = reader.read_header()?;
+    /// assert_eq!(header.kind(), bbi::header::Kind::BigBed);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(mut self) -> io::Result<W> {
+        self.initialize()?;
+        self.flush_block()?;
+
+        self.coverage.finish(&mut self.segments);
+        self.add_segments()?;
+
+        let full_index_offset = self.inner.stream_position()?;
+        write_r_tree(
+            &mut self.inner,
+            full_index_offset,
+            &self.leaves,
+            self.items_per_slot as u32,
+        )?;
+
+        let mut zoom_headers = Vec::new();
+        let mut max_block_size = self.max_block_size;
+        let mut previous_record_count = self.record_count;
+
+        // A zoom level is only written if it at least halves the number of records of the
+        // previous level.
+        for mut zoom_level in self.zoom_levels.take().unwrap_or_default() {
+            zoom_level.finish()?;
+
+            let record_count = u64::from(zoom_level.record_count());
+
+            if record_count == 0 || record_count * 2 > previous_record_count {
+                break;
+            }
+
+            previous_record_count = record_count;
+            max_block_size = max_block_size.max(zoom_level.max_block_size());
+
+            let reduction_level = zoom_level.reduction_level();
+            let data_offset = self.inner.stream_position()?;
+            let index_offset = zoom_level.write(&mut self.inner, data_offset)?;
+
+            zoom_headers.push(zoom::Header::new(
+                reduction_level,
+                data_offset,
+                index_offset,
+            ));
+        }
+
+        let (field_count, defined_field_count) = self.field_counts.unwrap_or((3, 3));
+
+        let header = Header {
+            kind: Kind::BigBed,
+            version: header::VERSION,
+            zoom_headers,
+            chromosome_tree_offset: self.chromosome_tree_offset,
+            full_data_offset: self.full_data_offset,
+            full_index_offset,
+            field_count,
+            defined_field_count,
+            auto_sql_offset: self.auto_sql_offset,
+            total_summary_offset: total_summary_offset(),
+            uncompressed_buffer_size: u32::try_from(max_block_size)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?,
+        };
+
+        self.inner.seek(SeekFrom::Start(0))?;
+        header::write_header(&mut self.inner, &header)?;
+
+        self.inner
+            .seek(SeekFrom::Start(header.total_summary_offset))?;
+        summary::write_summary(&mut self.inner, &self.summary)?;
+
+        self.inner.seek(SeekFrom::Start(self.full_data_offset))?;
+        self.inner.write_u64::<LittleEndian>(self.record_count)?;
+
+        self.inner.seek(SeekFrom::End(0))?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+
+    // Writes the reserved header space, autoSql definition, chromosome tree, and start of the
+    // data section.
+    fn initialize(&mut self) -> io::Result<()> {
+        if self.is_initialized {
+            return Ok(());
+        }
+
+        for (i, chromosome) in self.chromosomes.iter().enumerate() {
+            if self
+                .chromosome_indices
+                .insert(chromosome.name().into(), i)
+                .is_some()
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("duplicate reference sequence name: {}", chromosome.name()),
+                ));
+            }
+        }
+
+        self.inner.seek(SeekFrom::Start(0))?;
+
+        let reserved_size = total_summary_offset() + summary::SIZE;
+        self.inner.write_all(&vec![0; reserved_size as usize])?;
+
+        if let Some(auto_sql) = &self.auto_sql {
+            self.auto_sql_offset = self.inner.stream_position()?;
+            self.inner.write_all(auto_sql.as_bytes())?;
+            self.inner.write_u8(NUL)?;
+        }
+
+        self.chromosome_tree_offset = self.inner.stream_position()?;
+        write_chromosome_tree(
+            &mut self.inner,
+            self.chromosome_tree_offset,
+            &self.chromosomes,
+        )?;
+
+        // The record count is written by `finish`.
+        self.full_data_offset = self.inner.stream_position()?;
+        self.inner.write_u64::<LittleEndian>(0)?;
+
+        self.is_initialized = true;
+
+        Ok(())
+    }
+
+    fn flush_block(&mut self) -> io::Result<()> {
+        if self.block.intervals.is_empty() {
+            return Ok(());
+        }
+
+        if self.zoom_levels.is_none() {
+            self.zoom_levels = Some(build_zoom_levels(
+                &self.block.intervals,
+                &self.chromosomes,
+                self.items_per_slot,
+            ));
+        }
+
+        let chromosome_id = self.block.chromosome_id;
+        let (start, _) = self.block.intervals[0];
+        let end = self
+            .block
+            .intervals
+            .iter()
+            .map(|(_, end)| *end)
+            .max()
+            .unwrap_or(start);
+
+        let data = compress(&self.block.buf)?;
+        let offset = self.inner.stream_position()?;
+        self.inner.write_all(&data)?;
+
+        self.leaves.push(Leaf {
+            bounds: Bounds {
+                start: (chromosome_id, start),
+                end: (chromosome_id, end),
+            },
+            block: Block {
+                offset,
+                size: data.len() as u64,
+            },
+        });
+
+        self.max_block_size = self.max_block_size.max(self.block.buf.len());
+
+        for &(start, end) in &self.block.intervals {
+            self.coverage
+                .add(chromosome_id, start, end, &mut self.segments);
+        }
+
+        self.block.buf.clear();
+        self.block.intervals.clear();
+
+        self.add_segments()
+    }
+
+    fn add_segments(&mut self) -> io::Result<()> {
+        let zoom_levels = self.zoom_levels.as_deref_mut().unwrap_or_default();
+
+        for segment in self.segments.drain(..) {
+            let len = u64::from(segment.end - segment.start);
+            self.summary.add(f64::from(segment.depth), len);
+
+            for zoom_level in zoom_levels.iter_mut() {
+                zoom_level.add(&segment)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn total_summary_offset() -> u64 {
+    header::SIZE + MAX_ZOOM_LEVEL_COUNT as u64 * zoom::HEADER_SIZE
+}
+
+// The first zoom level summarizes bins of 10 times the average length of the first block of
+// records. Each following level is `ZOOM_INCREMENT` times larger than the previous.
+fn build_zoom_levels(
+    intervals: &[(u32, u32)],
+    chromosomes: &[Chromosome],
+    items_per_slot: usize,
+) -> Vec<ZoomLevel> {
+    let total_len: u64 = intervals
+        .iter()
+        .map(|(start, end)| u64::from(end - start))
+        .sum();
+
+    let mean_len = (total_len / intervals.len() as u64).max(1);
+    let max_chromosome_size = chromosomes.iter().map(|c| c.size()).max().unwrap_or(0);
+
+    let mut reduction_level = u32::try_from(mean_len * 10).unwrap_or(u32::MAX);
+    let mut zoom_levels = Vec::new();
+
+    while zoom_levels.len() < MAX_ZOOM_LEVEL_COUNT {
+        zoom_levels.push(ZoomLevel::new(reduction_level, items_per_slot));
+
+        if reduction_level >= max_chromosome_size {
+            break;
+        }
+
+        reduction_level = match reduction_level.checked_mul(ZOOM_INCREMENT) {
+            Some(n) => n,
+            None => break,
+        };
+    }
+
+    zoom_levels
+}
+
+fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+fn validate_auto_sql_field_count(auto_sql: &str, field_count: u16) -> io::Result<()> {
+    let auto_sql: AutoSql = auto_sql
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    if auto_sql.fields().len() == usize::from(field_count) {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "record field count does not match the autoSql field count",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::bbi::Reader;
+
+    const BED4_AUTO_SQL: &str = r#"table bed4
+"BED4"
+(
+    string chrom; "Reference sequence chromosome or scaffold"
+    uint chromStart; "Start position in chromosome"
+    uint chromEnd; "End position in chromosome"
+    string name; "Name of item"
+)"#;
+
+    fn build_records() -> Result<Vec<Record<4>>, Box<dyn std::error::Error>> {
+        let mut records = Vec::new();
+
+        for i in 0..1000 {
+            let start = i * 50;
+            let end = start + 100;
+            records.push(format!("sq0\t{}\t{}\tr{}", start, end, i).parse()?);
+        }
+
+        records.push("sq1\t10\t20\tr1000".parse()?);
+        records.push("sq1\t30\t40\tr1001".parse()?);
+
+        Ok(records)
+    }
+
+    #[test]
+    fn test_write_record() -> Result<(), Box<dyn std::error::Error>> {
+        let records = build_records()?;
+
+        let mut writer = Writer::builder(Cursor::new(Vec::new()))
+            .set_chromosome_sizes([("sq0", 100000), ("sq1", 500)])
+            .set_auto_sql(BED4_AUTO_SQL)
+            .set_items_per_slot(3)
+            .build();
+
+        for record in &records {
+            writer.write_record(record)?;
+        }
+
+        let mut reader = Reader::new(writer.finish()?);
+        let header = reader.read_header()?;
+
+        assert_eq!(header.kind(), Kind::BigBed);
+        assert_eq!(header.field_count(), 4);
+        assert_eq!(header.defined_field_count(), 4);
+        assert!(header.is_compressed());
+
+        assert_eq!(
+            reader.read_chromosomes(&header)?,
+            [
+                Chromosome::new("sq0", 0, 100000),
+                Chromosome::new("sq1", 1, 500)
+            ]
+        );
+
+        assert_eq!(
+            reader.read_auto_sql(&header)?.as_deref(),
+            Some(BED4_AUTO_SQL)
+        );
+
+        let summary = reader.read_total_summary(&header)?.unwrap_or_default();
+        assert_eq!(summary.bases_covered(), 50070);
+        assert_eq!(summary.min_value(), 1.0);
+        assert_eq!(summary.max_value(), 2.0);
+        assert_eq!(summary.sum(), 100020.0);
+
+        let region = "sq0:20001-20100".parse()?;
+        let actual: Vec<_> = reader
+            .query::<4>(&header, &region)?
+            .collect::<io::Result<_>>()?;
+        assert_eq!(actual, &records[399..402]);
+
+        let region = "sq1".parse()?;
+        let actual: Vec<_> = reader
+            .query::<4>(&header, &region)?
+            .collect::<io::Result<_>>()?;
+        assert_eq!(actual, &records[1000..]);
+
+        let reduction_levels: Vec<_> = header
+            .zoom_headers()
+            .iter()
+            .map(|h| h.reduction_level())
+            .collect();
+        assert_eq!(reduction_levels, [1000, 4000, 16000, 64000]);
+
+        for zoom_header in header.zoom_headers() {
+            let region = "sq0".parse()?;
+            let zoom_records: Vec<_> = reader
+                .query_zoom(&header, zoom_header, &region)?
+                .collect::<io::Result<_>>()?;
+
+            let valid_count: u32 = zoom_records.iter().map(|r| r.valid_count()).sum();
+            assert_eq!(valid_count, 50050);
+
+            let sum: f32 = zoom_records.iter().map(|r| r.sum()).sum();
+            assert_eq!(sum, 100000.0);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_invalid_records() -> Result<(), Box<dyn std::error::Error>> {
+        fn build_writer() -> Writer<Cursor<Vec<u8>>> {
+            Writer::builder(Cursor::new(Vec::new()))
+                .set_chromosome_sizes([("sq0", 8), ("sq1", 13)])
+                .build()
+        }
+
+        let record: Record<3> = "sq2\t0\t5".parse()?;
+        assert!(build_writer().write_record(&record).is_err());
+
+        let record: Record<3> = "sq0\t0\t13".parse()?;
+        assert!(build_writer().write_record(&record).is_err());
+
+        let mut writer = build_writer();
+        writer.write_record(&"sq1\t0\t5".parse::<Record<3>>()?)?;
+        assert!(writer
+            .write_record(&"sq0\t0\t5".parse::<Record<3>>()?)
+            .is_err());
+
+        let mut writer = build_writer();
+        writer.write_record(&"sq0\t2\t5".parse::<Record<3>>()?)?;
+        assert!(writer
+            .write_record(&"sq0\t1\t5".parse::<Record<3>>()?)
+            .is_err());
+
+        let mut writer = Writer::builder(Cursor::new(Vec::new()))
+            .set_chromosome_sizes([("sq0", 8), ("sq0", 13)])
+            .build();
+        assert!(writer
+            .write_record(&"sq0\t1\t5".parse::<Record<3>>()?)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_mismatched_field_counts() -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = Writer::builder(Cursor::new(Vec::new()))
+            .set_chromosome_sizes([("sq0", 8)])
+            .build();
+
+        writer.write_record(&"sq0\t0\t5\tr0".parse::<Record<3>>()?)?;
+        assert!(matches!(
+            writer.write_record(&"sq0\t1\t5".parse::<Record<3>>()?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+        assert!(matches!(
+            writer.write_record(&"sq0\t1\t5\tr1\t0".parse::<Record<3>>()?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+        writer.write_record(&"sq0\t1\t5\tr1".parse::<Record<3>>()?)?;
+
+        let mut writer = Writer::builder(Cursor::new(Vec::new()))
+            .set_chromosome_sizes([("sq0", 8)])
+            .set_auto_sql(BED4_AUTO_SQL)
+            .build();
+
+        assert!(matches!(
+            writer.write_record(&"sq0\t0\t5".parse::<Record<3>>()?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let mut writer = Writer::builder(Cursor::new(Vec::new()))
+            .set_chromosome_sizes([("sq0", 8)])
+            .set_auto_sql("table bed4")
+            .build();
+
+        assert!(matches!(
+            writer.write_record(&"sq0\t0\t5\tr0".parse::<Record<4>>()?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finish_with_no_records() -> Result<(), Box<dyn std::error::Error>> {
+        let writer = Writer::builder(Cursor::new(Vec::new()))
+            .set_chromosome_sizes([("sq0", 8)])
+            .build();
+
+        let mut reader = Reader::new(writer.finish()?);
+        let header = reader.read_header()?;
+
+        assert!(header.zoom_headers().is_empty());
+        assert!(reader.read_auto_sql(&header)?.is_none());
+
+        let region = "sq0".parse()?;
+        assert_eq!(reader.query::<3>(&header, &region)?.count(), 0);
+
+        Ok(())
+    }
+}
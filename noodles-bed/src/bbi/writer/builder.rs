@@ -0,0 +1,122 @@
+use std::io::{Seek, Write};
+
+use super::{Writer, DEFAULT_ITEMS_PER_SLOT};
+use crate::bbi::Chromosome;
+
+/// A bigBed writer builder.
+pub struct Builder<W> {
+    inner: W,
+    chromosomes: Vec<Chromosome>,
+    auto_sql: Option<String>,
+    items_per_slot: usize,
+}
+
+impl<W> Builder<W>
+where
+    W: Write + Seek,
+{
+    pub(super) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            chromosomes: Vec::new(),
+            auto_sql: None,
+            items_per_slot: DEFAULT_ITEMS_PER_SLOT,
+        }
+    }
+
+    /// Sets the chromosome (reference sequence) names and lengths.
+    ///
+    /// Records must be sorted by chromosome in this order and then by start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bed::bbi;
+    ///
+    /// let writer = bbi::Writer::builder(Cursor::new(Vec::new()))
+    ///     .set_chromosome_sizes([("sq0", 8), ("sq1", 13)])
+    ///     .build();
+    /// ```
+    pub fn set_chromosome_sizes<I, N>(mut self, chromosome_sizes: I) -> Self
+    where
+        I: IntoIterator<Item = (N, u32)>,
+        N: Into<String>,
+    {
+        self.chromosomes = chromosome_sizes
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, size))| Chromosome::new(name, i as u32, size))
+            .collect();
+
+        self
+    }
+
+    /// Sets the autoSql definition of the records.
+    ///
+    /// By default, no autoSql definition is written. When set, the number of fields of each
+    /// record written must match the number of fields it defines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bed::bbi;
+    ///
+    /// let auto_sql = r#"table bed3
+    /// "BED3"
+    /// (
+    ///     string chrom; "Reference sequence chromosome or scaffold"
+    ///     uint chromStart; "Start position in chromosome"
+    ///     uint chromEnd; "End position in chromosome"
+    /// )"#;
+    ///
+    /// let writer = bbi::Writer::builder(Cursor::new(Vec::new()))
+    ///     .set_auto_sql(auto_sql)
+    ///     .build();
+    /// ```
+    pub fn set_auto_sql<S>(mut self, auto_sql: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.auto_sql = Some(auto_sql.into());
+        self
+    }
+
+    /// Sets the maximum number of items in each data block.
+    ///
+    /// By default, this is 512.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bed::bbi;
+    ///
+    /// let writer = bbi::Writer::builder(Cursor::new(Vec::new()))
+    ///     .set_items_per_slot(64)
+    ///     .build();
+    /// ```
+    pub fn set_items_per_slot(mut self, items_per_slot: usize) -> Self {
+        self.items_per_slot = items_per_slot.max(1);
+        self
+    }
+
+    /// Builds a bigBed writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use noodles_bed::bbi;
+    /// let writer = bbi::Writer::builder(Cursor::new(Vec::new())).build();
+    /// ```
+    pub fn build(self) -> Writer<W> {
+        Writer::new(
+            self.inner,
+            self.chromosomes,
+            self.auto_sql,
+            self.items_per_slot,
+        )
+    }
+}
@@ -0,0 +1,117 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// An interval of constant coverage depth.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) struct Segment {
+    pub(super) chromosome_id: u32,
+    // 0-based, half-open
+    pub(super) start: u32,
+    pub(super) end: u32,
+    pub(super) depth: u32,
+}
+
+/// A coverage depth calculator over a stream of sorted intervals.
+///
+/// Only the end positions of intervals that overlap the current position are kept.
+#[derive(Debug, Default)]
+pub(super) struct Coverage {
+    chromosome_id: u32,
+    position: u32,
+    ends: BinaryHeap<Reverse<u32>>,
+}
+
+impl Coverage {
+    /// Adds a 0-based, half-open interval.
+    ///
+    /// Intervals must be sorted by chromosome ID and start. Segments that can no longer change
+    /// are appended to `segments`.
+    pub(super) fn add(
+        &mut self,
+        chromosome_id: u32,
+        start: u32,
+        end: u32,
+        segments: &mut Vec<Segment>,
+    ) {
+        if chromosome_id != self.chromosome_id {
+            self.finish(segments);
+            self.chromosome_id = chromosome_id;
+            self.position = 0;
+        }
+
+        self.advance_to(start, segments);
+        self.ends.push(Reverse(end));
+    }
+
+    /// Appends all remaining segments to `segments`.
+    pub(super) fn finish(&mut self, segments: &mut Vec<Segment>) {
+        self.advance_to(u32::MAX, segments);
+    }
+
+    fn advance_to(&mut self, position: u32, segments: &mut Vec<Segment>) {
+        while let Some(&Reverse(end)) = self.ends.peek() {
+            if end > position {
+                break;
+            }
+
+            self.push_segment(end, segments);
+            self.ends.pop();
+        }
+
+        self.push_segment(position, segments);
+        self.position = self.position.max(position);
+    }
+
+    fn push_segment(&mut self, end: u32, segments: &mut Vec<Segment>) {
+        if self.ends.is_empty() || self.position >= end {
+            return;
+        }
+
+        segments.push(Segment {
+            chromosome_id: self.chromosome_id,
+            start: self.position,
+            end,
+            depth: self.ends.len() as u32,
+        });
+
+        self.position = end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() {
+        fn segment(chromosome_id: u32, start: u32, end: u32, depth: u32) -> Segment {
+            Segment {
+                chromosome_id,
+                start,
+                end,
+                depth,
+            }
+        }
+
+        let mut coverage = Coverage::default();
+        let mut segments = Vec::new();
+
+        coverage.add(0, 5, 10, &mut segments);
+        coverage.add(0, 8, 20, &mut segments);
+        coverage.add(0, 8, 9, &mut segments);
+        coverage.add(0, 30, 40, &mut segments);
+        coverage.add(1, 0, 5, &mut segments);
+        coverage.finish(&mut segments);
+
+        assert_eq!(
+            segments,
+            [
+                segment(0, 5, 8, 1),
+                segment(0, 8, 9, 3),
+                segment(0, 9, 10, 2),
+                segment(0, 10, 20, 1),
+                segment(0, 30, 40, 1),
+                segment(1, 0, 5, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::{compress, coverage::Segment};
+use crate::bbi::r_tree::{write_r_tree, Block, Bounds, Leaf};
+
+// An accumulating zoom record of a bin.
+struct BinRecord {
+    chromosome_id: u32,
+    bin_start: u32,
+    start: u32,
+    end: u32,
+    valid_count: u32,
+    min_value: f64,
+    max_value: f64,
+    sum: f64,
+    sum_squares: f64,
+}
+
+/// A zoom level builder.
+///
+/// Coverage segments are summarized into bins of `reduction_level` bases. Zoom records are
+/// compressed into blocks and held in memory until the zoom level is written.
+pub(super) struct ZoomLevel {
+    reduction_level: u32,
+    items_per_slot: usize,
+
+    record: Option<BinRecord>,
+
+    block_buf: Vec<u8>,
+    block_bounds: Bounds,
+    block_record_count: usize,
+
+    data: Vec<u8>,
+    leaves: Vec<Leaf>,
+
+    record_count: u32,
+    max_block_size: usize,
+}
+
+impl ZoomLevel {
+    pub(super) fn new(reduction_level: u32, items_per_slot: usize) -> Self {
+        Self {
+            reduction_level,
+            items_per_slot,
+
+            record: None,
+
+            block_buf: Vec::new(),
+            block_bounds: Bounds::default(),
+            block_record_count: 0,
+
+            data: Vec::new(),
+            leaves: Vec::new(),
+
+            record_count: 0,
+            max_block_size: 0,
+        }
+    }
+
+    pub(super) fn reduction_level(&self) -> u32 {
+        self.reduction_level
+    }
+
+    pub(super) fn record_count(&self) -> u32 {
+        self.record_count
+    }
+
+    /// Returns the size of the largest uncompressed block.
+    pub(super) fn max_block_size(&self) -> usize {
+        self.max_block_size
+    }
+
+    pub(super) fn add(&mut self, segment: &Segment) -> io::Result<()> {
+        let value = f64::from(segment.depth);
+        let mut start = segment.start;
+
+        while start < segment.end {
+            let bin_start = start - start % self.reduction_level;
+            let end = bin_start
+                .saturating_add(self.reduction_level)
+                .min(segment.end);
+
+            let is_same_bin = self
+                .record
+                .as_ref()
+                .map(|r| (r.chromosome_id, r.bin_start) == (segment.chromosome_id, bin_start))
+                .unwrap_or(false);
+
+            if !is_same_bin {
+                self.flush_record()?;
+            }
+
+            let record = self.record.get_or_insert(BinRecord {
+                chromosome_id: segment.chromosome_id,
+                bin_start,
+                start,
+                end,
+                valid_count: 0,
+                min_value: value,
+                max_value: value,
+                sum: 0.0,
+                sum_squares: 0.0,
+            });
+
+            let len = end - start;
+            let n = f64::from(len);
+
+            record.end = end;
+            record.valid_count += len;
+            record.min_value = record.min_value.min(value);
+            record.max_value = record.max_value.max(value);
+            record.sum += value * n;
+            record.sum_squares += value * value * n;
+
+            start = end;
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the pending zoom record and block.
+    pub(super) fn finish(&mut self) -> io::Result<()> {
+        self.flush_record()?;
+        self.flush_block()
+    }
+
+    /// Writes the zoom level data and its R-tree index.
+    ///
+    /// `offset` is the position of the writer in the output stream. This returns the offset of
+    /// the index.
+    pub(super) fn write<W>(mut self, writer: &mut W, offset: u64) -> io::Result<u64>
+    where
+        W: Write,
+    {
+        writer.write_u32::<LittleEndian>(self.record_count)?;
+        writer.write_all(&self.data)?;
+
+        // Block offsets are relative to the start of the data.
+        let data_offset = offset + 4;
+
+        for leaf in &mut self.leaves {
+            leaf.block.offset += data_offset;
+        }
+
+        let index_offset = data_offset + self.data.len() as u64;
+        write_r_tree(
+            writer,
+            index_offset,
+            &self.leaves,
+            self.items_per_slot as u32,
+        )?;
+
+        Ok(index_offset)
+    }
+
+    fn flush_record(&mut self) -> io::Result<()> {
+        let record = match self.record.take() {
+            Some(record) => record,
+            None => return Ok(()),
+        };
+
+        let is_same_chromosome = self.block_bounds.start.0 == record.chromosome_id;
+
+        if self.block_record_count >= self.items_per_slot
+            || (self.block_record_count > 0 && !is_same_chromosome)
+        {
+            self.flush_block()?;
+        }
+
+        if self.block_record_count == 0 {
+            self.block_bounds.start = (record.chromosome_id, record.start);
+        }
+
+        self.block_bounds.end = (record.chromosome_id, record.end);
+
+        let buf = &mut self.block_buf;
+        buf.write_u32::<LittleEndian>(record.chromosome_id)?;
+        buf.write_u32::<LittleEndian>(record.start)?;
+        buf.write_u32::<LittleEndian>(record.end)?;
+        buf.write_u32::<LittleEndian>(record.valid_count)?;
+        buf.write_f32::<LittleEndian>(record.min_value as f32)?;
+        buf.write_f32::<LittleEndian>(record.max_value as f32)?;
+        buf.write_f32::<LittleEndian>(record.sum as f32)?;
+        buf.write_f32::<LittleEndian>(record.sum_squares as f32)?;
+
+        self.block_record_count += 1;
+        self.record_count += 1;
+
+        Ok(())
+    }
+
+    fn flush_block(&mut self) -> io::Result<()> {
+        if self.block_record_count == 0 {
+            return Ok(());
+        }
+
+        let data = compress(&self.block_buf)?;
+
+        self.leaves.push(Leaf {
+            bounds: self.block_bounds,
+            block: Block {
+                offset: self.data.len() as u64,
+                size: data.len() as u64,
+            },
+        });
+
+        self.data.extend(data);
+
+        self.max_block_size = self.max_block_size.max(self.block_buf.len());
+        self.block_buf.clear();
+        self.block_record_count = 0;
+
+        Ok(())
+    }
+}
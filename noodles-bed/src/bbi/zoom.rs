@@ -0,0 +1,145 @@
+//! BBI zoom levels.
+
+use std::io::{self, Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use noodles_core::Position;
+
+pub(crate) const RECORD_SIZE: usize = 32;
+
+/// A BBI zoom level header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Header {
+    reduction_level: u32,
+    data_offset: u64,
+    index_offset: u64,
+}
+
+impl Header {
+    pub(crate) fn new(reduction_level: u32, data_offset: u64, index_offset: u64) -> Self {
+        Self {
+            reduction_level,
+            data_offset,
+            index_offset,
+        }
+    }
+
+    /// Returns the number of bases summarized by each zoom record.
+    pub fn reduction_level(&self) -> u32 {
+        self.reduction_level
+    }
+
+    pub(crate) fn index_offset(&self) -> u64 {
+        self.index_offset
+    }
+}
+
+pub(crate) fn read_header<R>(reader: &mut R) -> io::Result<Header>
+where
+    R: Read,
+{
+    let reduction_level = reader.read_u32::<LittleEndian>()?;
+    let _reserved = reader.read_u32::<LittleEndian>()?;
+    let data_offset = reader.read_u64::<LittleEndian>()?;
+    let index_offset = reader.read_u64::<LittleEndian>()?;
+
+    Ok(Header::new(reduction_level, data_offset, index_offset))
+}
+
+/// The size of a zoom level header in bytes.
+pub(crate) const HEADER_SIZE: u64 = 24;
+
+pub(crate) fn write_header<W>(writer: &mut W, header: &Header) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u32::<LittleEndian>(header.reduction_level)?;
+    writer.write_u32::<LittleEndian>(0)?; // reserved
+    writer.write_u64::<LittleEndian>(header.data_offset)?;
+    writer.write_u64::<LittleEndian>(header.index_offset)?;
+    Ok(())
+}
+
+/// A BBI zoom record.
+///
+/// A zoom record summarizes the values of a reference sequence interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Record {
+    pub(crate) reference_sequence_id: u32,
+    pub(crate) start_position: Position,
+    pub(crate) end_position: Position,
+    pub(crate) valid_count: u32,
+    pub(crate) min_value: f32,
+    pub(crate) max_value: f32,
+    pub(crate) sum: f32,
+    pub(crate) sum_squares: f32,
+}
+
+impl Record {
+    /// Returns the start position.
+    pub fn start_position(&self) -> Position {
+        self.start_position
+    }
+
+    /// Returns the end position.
+    pub fn end_position(&self) -> Position {
+        self.end_position
+    }
+
+    /// Returns the number of bases with data.
+    pub fn valid_count(&self) -> u32 {
+        self.valid_count
+    }
+
+    /// Returns the minimum value.
+    pub fn min_value(&self) -> f32 {
+        self.min_value
+    }
+
+    /// Returns the maximum value.
+    pub fn max_value(&self) -> f32 {
+        self.max_value
+    }
+
+    /// Returns the sum of values.
+    pub fn sum(&self) -> f32 {
+        self.sum
+    }
+
+    /// Returns the sum of the squares of values.
+    pub fn sum_squares(&self) -> f32 {
+        self.sum_squares
+    }
+
+    /// Returns the mean value.
+    ///
+    /// This is `None` if there are no bases with data.
+    pub fn mean(&self) -> Option<f32> {
+        if self.valid_count == 0 {
+            None
+        } else {
+            Some(self.sum / self.valid_count as f32)
+        }
+    }
+}
+
+pub(crate) fn read_record<R>(reader: &mut R) -> io::Result<Record>
+where
+    R: Read,
+{
+    let reference_sequence_id = reader.read_u32::<LittleEndian>()?;
+    let start = reader.read_u32::<LittleEndian>()?;
+    let end = reader.read_u32::<LittleEndian>()?;
+    let (start_position, end_position) = super::interval_positions(start, end)?;
+
+    Ok(Record {
+        reference_sequence_id,
+        start_position,
+        end_position,
+        valid_count: reader.read_u32::<LittleEndian>()?,
+        min_value: reader.read_f32::<LittleEndian>()?,
+        max_value: reader.read_f32::<LittleEndian>()?,
+        sum: reader.read_f32::<LittleEndian>()?,
+        sum_squares: reader.read_f32::<LittleEndian>()?,
+    })
+}
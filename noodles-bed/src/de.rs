@@ -1,9 +1,19 @@
+use std::{io::BufRead, marker::PhantomData, str::Split};
+
 use crate::error;
+use crate::line::is_header_line;
+use crate::reader::read_line;
 use crate::record::{BedN, SerdeRecordWrapper};
 use error::{Error, Result};
-use serde::de::{DeserializeSeed, SeqAccess, Visitor};
+use serde::de::{
+    DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
 use serde::{de, forward_to_deserialize_any, ser, Deserialize};
 
+const FIELD_DELIMITER: char = '\t';
+const LIST_DELIMITER: char = ',';
+const MISSING: &str = ".";
+
 pub struct RecordDeserializer<'de> {
     input: &'de str,
 }
@@ -39,11 +49,56 @@ where
     Ok(srw_vec.into_iter().map(|wrap| wrap.0).collect())
 }
 
-pub fn from_bytes<'a, T>(_records: &'a [u8]) -> Result<T>
+/// Deserializes a value from BED bytes.
+///
+/// See [`Deserializer`] for how values map to lines and fields.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bed::{self as bed, record::SerdeRecordWrapper};
+///
+/// let data = b"sq0\t7\t13\nsq1\t13\t18\n";
+/// let records: Vec<SerdeRecordWrapper<bed::Record<3>>> = bed::from_bytes(data)?;
+/// assert_eq!(records.len(), 2);
+/// # Ok::<_, bed::Error>(())
+/// ```
+pub fn from_bytes<'a, T>(records: &'a [u8]) -> Result<T>
 where
     T: Deserialize<'a>,
 {
-    todo!()
+    let mut deserializer = Deserializer::from_reader(records);
+    let t = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(t)
+}
+
+/// Deserializes a value from a BED reader.
+///
+/// See [`Deserializer`] for how values map to lines and fields.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bed as bed;
+///
+/// let data = b"sq0\t7\t13\tndls1\n";
+/// let (name, start, end, id): (String, u64, u64, String) = bed::from_reader(&data[..])?;
+///
+/// assert_eq!(name, "sq0");
+/// assert_eq!((start, end), (7, 13));
+/// assert_eq!(id, "ndls1");
+/// # Ok::<_, bed::Error>(())
+/// ```
+pub fn from_reader<R, T>(reader: R) -> Result<T>
+where
+    R: BufRead,
+    T: DeserializeOwned,
+{
+    let mut deserializer = Deserializer::from_reader(reader);
+    let t = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(t)
 }
 
 impl<'de> From<&'de str> for RecordDeserializer<'de> {
@@ -72,11 +127,11 @@ impl<'de> RecordDeserializer<'de> {
 impl<'de, 'a> de::Deserializer<'de> for &'a mut RecordDeserializer<'de> {
     type Error = Error;
 
-    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        unimplemented!()
+        self.deserialize_str(visitor)
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
@@ -122,10 +177,661 @@ impl<'de> SeqAccess<'de> for RecordDeserializer<'de> {
     }
 }
 
+/// A streaming BED deserializer.
+///
+/// This reads lines from a [`BufRead`] on demand. Blank lines, comment lines (prefixed with `#`),
+/// and track and browser lines are skipped.
+///
+/// A sequence (e.g., a `Vec<T>`) is deserialized from the remaining lines of the input, and any
+/// other value is deserialized from a single line. A line is deserialized as
+///
+///   * a string, e.g., through [`SerdeRecordWrapper`], which parses a [`crate::Record`] using
+///     its [`std::str::FromStr`] implementation;
+///   * a sequence or tuple of tab-separated fields; or
+///   * a struct, where fields are matched with struct fields in declaration order. This allows
+///     user-defined structs with custom trailing columns.
+///
+/// A field can be deserialized as a string, number, boolean, or unit variant of an enum. A
+/// missing value (`.`) deserializes as `None` for optional fields, and a sequence field is a
+/// comma-separated list, e.g., `blockSizes`.
+///
+/// Errors that occur while deserializing a line include its 1-based line number.
+pub struct Deserializer<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+}
+
+impl<R> Deserializer<R>
+where
+    R: BufRead,
+{
+    /// Creates a BED deserializer from a reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let data = b"sq0\t7\t13\n";
+    /// let deserializer = bed::Deserializer::from_reader(&data[..]);
+    /// ```
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_number: 0,
+        }
+    }
+
+    /// Returns an iterator that deserializes each line of the input as a `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Peak {
+    ///     chrom: String,
+    ///     start: u64,
+    ///     end: u64,
+    ///     name: Option<String>,
+    ///     signal_value: f32,
+    /// }
+    ///
+    /// let data = b"sq0\t7\t13\t.\t0.5\n# comment\nsq1\t13\t18\tpeak1\t2.0\n";
+    /// let deserializer = bed::Deserializer::from_reader(&data[..]);
+    ///
+    /// let peaks = deserializer
+    ///     .into_iter::<Peak>()
+    ///     .collect::<bed::Result<Vec<_>>>()?;
+    ///
+    /// assert_eq!(peaks.len(), 2);
+    /// assert!(peaks[0].name.is_none());
+    /// assert_eq!(peaks[1].name.as_deref(), Some("peak1"));
+    /// # Ok::<_, bed::Error>(())
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn into_iter<T>(self) -> StreamDeserializer<R, T>
+    where
+        T: DeserializeOwned,
+    {
+        StreamDeserializer {
+            deserializer: self,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over BED records.
+    ///
+    /// Each line is parsed as a `T`, e.g., a [`crate::Record`], using its
+    /// [`std::str::FromStr`] implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_core::Position;
+    ///
+    /// let data = b"sq0\t7\t13\nsq1\t13\t18\n";
+    /// let deserializer = bed::Deserializer::from_reader(&data[..]);
+    ///
+    /// let records = deserializer
+    ///     .records::<bed::Record<3>>()
+    ///     .collect::<bed::Result<Vec<_>>>()?;
+    ///
+    /// assert_eq!(records.len(), 2);
+    /// assert_eq!(records[1].start_position(), Position::try_from(14)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn records<T>(self) -> impl Iterator<Item = Result<T>>
+    where
+        T: BedN<3> + std::str::FromStr + std::fmt::Display,
+        <T as std::str::FromStr>::Err: std::fmt::Display,
+    {
+        self.into_iter::<SerdeRecordWrapper<T>>()
+            .map(|result| result.map(|srw| srw.0))
+    }
+
+    /// Checks that the input has no more lines to deserialize.
+    pub fn end(&mut self) -> Result<()> {
+        if self.next_line()? {
+            Err(de::Error::custom("trailing lines"))
+        } else {
+            Ok(())
+        }
+    }
+
+    // Reads the next line that is neither blank nor a header line. This returns `false` at EOF.
+    fn next_line(&mut self) -> Result<bool> {
+        loop {
+            self.line.clear();
+
+            if read_line(&mut self.reader, &mut self.line)? == 0 {
+                return Ok(false);
+            }
+
+            self.line_number += 1;
+
+            if !self.line.is_empty() && !is_header_line(&self.line) {
+                return Ok(true);
+            }
+        }
+    }
+
+    fn deserialize_line<'de, T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if !self.next_line()? {
+            return Ok(None);
+        }
+
+        seed.deserialize(LineDeserializer::new(&self.line))
+            .map(Some)
+            .map_err(|e| e.at_line(self.line_number))
+    }
+
+    fn deserialize_single_line<'de, V>(&mut self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        struct VisitorSeed<V>(V);
+
+        impl<'de, V> DeserializeSeed<'de> for VisitorSeed<V>
+        where
+            V: Visitor<'de>,
+        {
+            type Value = V::Value;
+
+            fn deserialize<D>(self, deserializer: D) -> std::result::Result<V::Value, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                deserializer.deserialize_any(self.0)
+            }
+        }
+
+        self.deserialize_line(VisitorSeed(visitor))?
+            .ok_or_else(|| de::Error::custom("unexpected end of input"))
+    }
+}
+
+impl<'de, R> de::Deserializer<'de> for &mut Deserializer<R>
+where
+    R: BufRead,
+{
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_single_line(visitor)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(self)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_line(LineSeed::Str(visitor))?
+            .ok_or_else(|| de::Error::custom("unexpected end of input"))
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_line(LineSeed::Seq(visitor))?
+            .ok_or_else(|| de::Error::custom("unexpected end of input"))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_line(LineSeed::Struct(fields, visitor))?
+            .ok_or_else(|| de::Error::custom("unexpected end of input"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char
+        bytes byte_buf option unit unit_struct map enum identifier ignored_any
+    }
+}
+
+impl<'de, R> SeqAccess<'de> for Deserializer<R>
+where
+    R: BufRead,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.deserialize_line(seed)
+    }
+}
+
+// A seed that deserializes a line using the visitor of the value requested from the top-level
+// deserializer.
+enum LineSeed<V> {
+    Str(V),
+    Seq(V),
+    Struct(&'static [&'static str], V),
+}
+
+impl<'de, V> DeserializeSeed<'de> for LineSeed<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> std::result::Result<V::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        match self {
+            Self::Str(visitor) => deserializer.deserialize_str(visitor),
+            Self::Seq(visitor) => deserializer.deserialize_seq(visitor),
+            Self::Struct(fields, visitor) => deserializer.deserialize_struct("", fields, visitor),
+        }
+    }
+}
+
+/// An iterator that deserializes each line of a BED reader.
+///
+/// This is created by calling [`Deserializer::into_iter`].
+pub struct StreamDeserializer<R, T> {
+    deserializer: Deserializer<R>,
+    _marker: PhantomData<T>,
+}
+
+impl<R, T> Iterator for StreamDeserializer<R, T>
+where
+    R: BufRead,
+    T: DeserializeOwned,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deserializer
+            .deserialize_line(PhantomData::<T>)
+            .transpose()
+    }
+}
+
+// Deserializes a single line.
+struct LineDeserializer<'a> {
+    line: &'a str,
+}
+
+impl<'a> LineDeserializer<'a> {
+    fn new(line: &'a str) -> Self {
+        Self { line }
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for LineDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.line)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let mut fields = Fields::new(self.line.split(FIELD_DELIMITER));
+        let value = visitor.visit_seq(&mut fields)?;
+        fields.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let mut fields = StructFields {
+            names: fields.iter(),
+            fields: Fields::new(self.line.split(FIELD_DELIMITER)),
+        };
+
+        let value = visitor.visit_map(&mut fields)?;
+        fields.fields.end()?;
+        Ok(value)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char
+        bytes byte_buf option unit unit_struct map enum identifier ignored_any
+    }
+}
+
+// The tab-separated fields of a line.
+struct Fields<'a> {
+    fields: Split<'a, char>,
+    field_number: usize,
+}
+
+impl<'a> Fields<'a> {
+    fn new(fields: Split<'a, char>) -> Self {
+        Self {
+            fields,
+            field_number: 0,
+        }
+    }
+
+    fn next_field<'de, T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.fields.next() {
+            Some(field) => {
+                self.field_number += 1;
+
+                seed.deserialize(FieldDeserializer::new(field))
+                    .map(Some)
+                    .map_err(|e| {
+                        de::Error::custom(format!("invalid field {}: {}", self.field_number, e))
+                    })
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn end(&mut self) -> Result<()> {
+        if self.fields.next().is_some() {
+            Err(de::Error::custom("trailing fields"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'de, 'a> SeqAccess<'de> for Fields<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.next_field(seed)
+    }
+}
+
+// The fields of a line matched with the fields of a struct in declaration order.
+struct StructFields<'a> {
+    names: std::slice::Iter<'static, &'static str>,
+    fields: Fields<'a>,
+}
+
+impl<'de, 'a> MapAccess<'de> for StructFields<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.fields.fields.clone().next().is_none() {
+            return Ok(None);
+        }
+
+        match self.names.next() {
+            Some(name) => seed.deserialize(name.into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.fields
+            .next_field(seed)?
+            .ok_or_else(|| de::Error::custom("missing field"))
+    }
+}
+
+// Deserializes a single field.
+struct FieldDeserializer<'a> {
+    field: &'a str,
+}
+
+impl<'a> FieldDeserializer<'a> {
+    fn new(field: &'a str) -> Self {
+        Self { field }
+    }
+
+    fn parse<T>(&self) -> Result<T>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.field.parse().map_err(de::Error::custom)
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for FieldDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.field)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.field == MISSING {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.field == MISSING {
+            visitor.visit_unit()
+        } else {
+            Err(de::Error::custom("expected missing value"))
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        // Lists may have a trailing delimiter, e.g., `1,2,3,`.
+        let field = self
+            .field
+            .strip_suffix(LIST_DELIMITER)
+            .unwrap_or(self.field);
+
+        let values: Vec<_> = if field.is_empty() {
+            Vec::new()
+        } else {
+            field.split(LIST_DELIMITER).collect()
+        };
+
+        visitor.visit_seq(de::value::SeqDeserializer::new(
+            values.into_iter().map(FieldDeserializer::new),
+        ))
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self.field.into_deserializer())
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de, 'a> IntoDeserializer<'de, Error> for FieldDeserializer<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
 #[cfg(test)]
 mod serde_tests {
     use crate::{
-        record::{Color, Name, Score, Strand},
+        record::{BedGraph, BroadPeak, Color, Name, NarrowPeak, Score, Strand},
         Record,
     };
 
@@ -295,4 +1001,142 @@ mod serde_tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_from_bytes() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let data = b"# comment\nsq0\t7\t13\n\nsq1\t13\t18\n";
+        let actual: Vec<SerdeRecordWrapper<Record<3>>> = from_bytes(data)?;
+
+        let expected = [
+            Record::<3>::builder()
+                .set_reference_sequence_name("sq0")
+                .set_start_position(noodles_core::Position::try_from(8)?)
+                .set_end_position(noodles_core::Position::try_from(13)?)
+                .build()?,
+            Record::<3>::builder()
+                .set_reference_sequence_name("sq1")
+                .set_start_position(noodles_core::Position::try_from(14)?)
+                .set_end_position(noodles_core::Position::try_from(18)?)
+                .build()?,
+        ];
+
+        assert_eq!(actual.len(), expected.len());
+
+        for (a, b) in actual.iter().zip(&expected) {
+            assert_eq!(&a.0, b);
+        }
+
+        let data = b"sq0\t7\t13\n";
+        let actual: SerdeRecordWrapper<Record<3>> = from_bytes(data)?;
+        assert_eq!(actual.0, expected[0]);
+
+        let data = b"sq0\t7\t13\nsq1\t13\t18\n";
+        assert!(from_bytes::<SerdeRecordWrapper<Record<3>>>(data).is_err());
+
+        assert!(from_bytes::<SerdeRecordWrapper<Record<3>>>(b"").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserializer_into_iter_with_custom_fields() -> Result<()> {
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(rename_all = "lowercase")]
+        enum Kind {
+            Exon,
+            Intron,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Feature {
+            chrom: String,
+            start: u64,
+            end: u64,
+            name: Option<String>,
+            kind: Kind,
+            sizes: Vec<u32>,
+            #[serde(default)]
+            comment: Option<String>,
+        }
+
+        let data = b"sq0\t7\t13\t.\texon\t1,2,3,\nsq1\t13\t18\tf1\tintron\t\tndls\n";
+        let deserializer = Deserializer::from_reader(&data[..]);
+        let actual = deserializer.into_iter().collect::<Result<Vec<Feature>>>()?;
+
+        let expected = [
+            Feature {
+                chrom: String::from("sq0"),
+                start: 7,
+                end: 13,
+                name: None,
+                kind: Kind::Exon,
+                sizes: vec![1, 2, 3],
+                comment: None,
+            },
+            Feature {
+                chrom: String::from("sq1"),
+                start: 13,
+                end: 18,
+                name: Some(String::from("f1")),
+                kind: Kind::Intron,
+                sizes: Vec::new(),
+                comment: Some(String::from("ndls")),
+            },
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserializer_records() -> Result<()> {
+        let data = b"sq0\t7\t13\tndls1\n";
+        let deserializer = Deserializer::from_reader(&data[..]);
+        let actual = deserializer
+            .records::<Record<4>>()
+            .collect::<Result<Vec<_>>>()?;
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].name().map(|name| name.as_ref()), Some("ndls1"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_bytes_with_peak_records() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let data = b"track type=bedGraph\nsq0\t7\t13\t0.5\nsq0\t13\t21\t2\n";
+        let records: Vec<SerdeRecordWrapper<BedGraph>> = from_bytes(data)?;
+        let values: Vec<_> = records.iter().map(|r| r.0.value()).collect();
+        assert_eq!(values, [0.5, 2.0]);
+
+        let data = b"sq0\t7\t13\tpeak0\t500\t.\t5.5\t3.2\t-1\t2\n";
+        let records: Vec<SerdeRecordWrapper<NarrowPeak>> = from_bytes(data)?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].0.peak(), Some(2));
+        assert!(records[0].0.q_value().is_none());
+
+        let data = b"sq0\t7\t13\tpeak0\t500\t.\t5.5\t3.2\t-1\n";
+        let record: BroadPeak = record_from_str(std::str::from_utf8(data)?)?;
+        assert_eq!(record.p_value(), Some(3.2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserializer_errors() {
+        let data = b"sq0\t7\t13\n# comment\nsq0\tndls\t21\n";
+        let deserializer = Deserializer::from_reader(&data[..]);
+        let result = deserializer
+            .into_iter::<(String, u64, u64)>()
+            .collect::<Result<Vec<_>>>();
+
+        let e = result.unwrap_err();
+        assert_eq!(e.line_number(), Some(3));
+        assert!(e.to_string().starts_with("line 3: invalid field 2:"));
+
+        let data = b"sq0\t7\t13\tndls\n";
+        let result: Result<(String, u64, u64)> = from_bytes(data);
+        assert_eq!(result.unwrap_err().line_number(), Some(1));
+    }
 }
@@ -9,6 +9,47 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Debug)]
 pub enum Error {
     Error(io::Error),
+    /// An error at a line of the input.
+    Line {
+        /// The 1-based line number of the line that caused the error.
+        ///
+        /// Every line of the input is counted, including blank, comment, track, and browser lines.
+        line_number: usize,
+        /// The error that occurred while processing the line.
+        error: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Returns the 1-based line number of the input that caused the error, if known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"sq0\t7\t13\nsq0\tndls\t21\n";
+    /// let result: bed::Result<Vec<bed::record::SerdeRecordWrapper<bed::Record<3>>>> =
+    ///     bed::from_bytes(data);
+    ///
+    /// assert_eq!(result.err().and_then(|e| e.line_number()), Some(2));
+    /// ```
+    pub fn line_number(&self) -> Option<usize> {
+        match self {
+            Error::Error(_) => None,
+            Error::Line { line_number, .. } => Some(*line_number),
+        }
+    }
+
+    pub(crate) fn at_line(self, line_number: usize) -> Self {
+        match self {
+            Error::Line { .. } => self,
+            _ => Error::Line {
+                line_number,
+                error: Box::new(self),
+            },
+        }
+    }
 }
 
 impl ser::Error for Error {
@@ -27,8 +68,24 @@ impl Display for Error {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::Error(err) => formatter.write_str(&err.to_string()),
+            Error::Line { line_number, error } => {
+                write!(formatter, "line {}: {}", line_number, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Error(err) => Some(err),
+            Error::Line { error, .. } => Some(error.as_ref()),
         }
     }
 }
 
-impl std::error::Error for Error {}
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Error(err)
+    }
+}
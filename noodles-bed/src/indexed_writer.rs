@@ -0,0 +1,189 @@
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::index::reference_sequence::bin::Chunk;
+use noodles_tabix as tabix;
+
+use super::{record::BedN, Record, Track, Writer};
+
+/// A bgzip-compressed BED writer that builds a tabix index of the records it writes.
+///
+/// Records must be coordinate sorted, i.e., grouped by reference sequence and sorted by start
+/// position. The index uses the BED preset (0-based, half-open coordinates; `#` comments).
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::Writer<W>>,
+    indexer: tabix::index::Indexer,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexed BED writer.
+    ///
+    /// The output is bgzip-compressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let writer = bed::IndexedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        let mut indexer = tabix::Index::indexer();
+        indexer.set_header(tabix::index::header::Builder::bed().build());
+
+        Self {
+            inner: Writer::new(bgzf::Writer::new(inner)),
+            indexer,
+        }
+    }
+
+    /// Returns a reference to the underlying BGZF writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let writer = bed::IndexedWriter::new(Vec::new());
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &bgzf::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Writes a BED track definition line.
+    ///
+    /// Track lines are not indexed and should be written before any records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let mut writer = bed::IndexedWriter::new(Vec::new());
+    ///
+    /// let mut track = bed::Track::default();
+    /// track.insert("name", "peaks");
+    /// writer.write_track(&track)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_track(&mut self, track: &Track) -> io::Result<()> {
+        self.inner.write_track(track)
+    }
+
+    /// Writes and indexes a BED record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_core::Position;
+    ///
+    /// let mut writer = bed::IndexedWriter::new(Vec::new());
+    ///
+    /// let record = bed::Record::<3>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// writer.write_record(&record)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_record<const N: u8>(&mut self, record: &Record<N>) -> io::Result<()>
+    where
+        Record<N>: BedN<3> + fmt::Display,
+    {
+        let start_position = self.inner.get_ref().virtual_position();
+        self.inner.write_record(record)?;
+        let end_position = self.inner.get_ref().virtual_position();
+
+        self.indexer.add_record(
+            record.reference_sequence_name(),
+            record.start_position(),
+            record.end_position(),
+            Chunk::new(start_position, end_position),
+        );
+
+        Ok(())
+    }
+
+    /// Finishes the output stream and returns the underlying writer and the built index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    /// let writer = bed::IndexedWriter::new(Vec::new());
+    /// let (data, index) = writer.finish()?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(self) -> io::Result<(W, tabix::Index)> {
+        let inner = self.inner.into_inner().finish()?;
+        let index = self.indexer.build();
+        Ok((inner, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_core::Region;
+
+    use super::*;
+    use crate::Reader;
+
+    #[test]
+    fn test_write_record_and_query() -> Result<(), Box<dyn std::error::Error>> {
+        let records: Vec<Record<4>> = [
+            "sq0\t7\t13\tr0",
+            "sq0\t20\t34\tr1",
+            "sq0\t121393\t196418\tr2",
+            "sq1\t7\t13\tr3",
+        ]
+        .iter()
+        .map(|s| s.parse())
+        .collect::<Result<_, _>>()?;
+
+        let mut writer = IndexedWriter::new(Vec::new());
+
+        let mut track = Track::default();
+        track.insert("name", "ndls");
+        writer.write_track(&track)?;
+
+        for record in &records {
+            writer.write_record(record)?;
+        }
+
+        let (data, index) = writer.finish()?;
+
+        let mut reader = Reader::new(bgzf::Reader::new(Cursor::new(data)));
+
+        let region: Region = "sq0:10-25".parse()?;
+        let actual = reader
+            .query::<_, 4>(&index, &region)?
+            .collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(actual, &records[0..2]);
+
+        let region: Region = "sq1".parse()?;
+        let actual = reader
+            .query::<_, 4>(&index, &region)?
+            .collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(actual, &records[3..]);
+
+        let region: Region = "sq2".parse()?;
+        assert!(reader.query::<_, 4>(&index, &region).is_err());
+
+        Ok(())
+    }
+}
@@ -2,17 +2,36 @@
 
 //! **noodles-bed** handles the reading and writing of the BED (Browser Extensible Data) format.
 
-mod reader;
+#[cfg(feature = "async")]
+mod r#async;
+
+pub mod auto_sql;
+pub mod bbi;
+mod indexed_writer;
+pub mod line;
+pub mod ops;
+pub mod reader;
 pub mod record;
+pub mod sort;
+pub mod track;
 mod writer;
 
-pub use self::{reader::Reader, record::Record, writer::Writer};
+pub use self::{
+    indexed_writer::IndexedWriter, line::Line, reader::Reader, record::Record, track::Track,
+    writer::Writer,
+};
+
+#[cfg(feature = "async")]
+pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
 
 // SerDe
 mod de;
 mod error;
 mod ser;
 
-pub use de::{from_bytes, record_from_str, vec_record_from_str, RecordDeserializer};
+pub use de::{
+    from_bytes, from_reader, record_from_str, vec_record_from_str, Deserializer,
+    RecordDeserializer, StreamDeserializer,
+};
 pub use error::{Error, Result};
 pub use ser::{record_to_string, to_bytes, vec_record_to_string, RecordSerializer};
@@ -0,0 +1,161 @@
+//! BED line.
+
+use std::{error, fmt, str::FromStr};
+
+use super::{record, track, Record, Track};
+
+const BROWSER_PREFIX: &str = "browser";
+const COMMENT_PREFIX: char = '#';
+
+/// A BED line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Line<const N: u8> {
+    /// A track definition line (`track ...`).
+    Track(Track),
+    /// A browser line (`browser ...`).
+    ///
+    /// This holds the text following the `browser` prefix, e.g., `position sq0:8-13`.
+    Browser(String),
+    /// A comment line (`#...`).
+    ///
+    /// This holds the text following the `#` prefix.
+    Comment(String),
+    /// A record.
+    Record(Record<N>),
+}
+
+impl<const N: u8> fmt::Display for Line<N>
+where
+    Record<N>: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Track(track) => write!(f, "{}", track),
+            Self::Browser(s) => write!(f, "{} {}", BROWSER_PREFIX, s),
+            Self::Comment(s) => write!(f, "{}{}", COMMENT_PREFIX, s),
+            Self::Record(record) => write!(f, "{}", record),
+        }
+    }
+}
+
+/// An error returned when a raw BED line fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The track line is invalid.
+    InvalidTrack(track::ParseError),
+    /// The record is invalid.
+    InvalidRecord(record::ParseError),
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidTrack(e) => Some(e),
+            Self::InvalidRecord(e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTrack(_) => f.write_str("invalid track"),
+            Self::InvalidRecord(_) => f.write_str("invalid record"),
+        }
+    }
+}
+
+impl<const N: u8> FromStr for Line<N>
+where
+    Record<N>: FromStr<Err = record::ParseError>,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(t) = s.strip_prefix(COMMENT_PREFIX) {
+            Ok(Self::Comment(t.into()))
+        } else if has_keyword(s, track::PREFIX) {
+            s.parse().map(Self::Track).map_err(ParseError::InvalidTrack)
+        } else if has_keyword(s, BROWSER_PREFIX) {
+            let t = &s[BROWSER_PREFIX.len()..];
+            Ok(Self::Browser(t.trim_start().into()))
+        } else {
+            s.parse()
+                .map(Self::Record)
+                .map_err(ParseError::InvalidRecord)
+        }
+    }
+}
+
+/// Returns whether the line is a header line, i.e., a comment, track, or browser line.
+pub(crate) fn is_header_line(s: &str) -> bool {
+    s.starts_with(COMMENT_PREFIX) || has_keyword(s, track::PREFIX) || has_keyword(s, BROWSER_PREFIX)
+}
+
+fn has_keyword(s: &str, keyword: &str) -> bool {
+    match s.strip_prefix(keyword) {
+        Some(t) => t.is_empty() || t.starts_with(char::is_whitespace),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let line: Line<3> = Line::Track("track name=peaks".parse()?);
+        assert_eq!(line.to_string(), "track name=peaks");
+
+        let line: Line<3> = Line::Browser(String::from("position sq0:8-13"));
+        assert_eq!(line.to_string(), "browser position sq0:8-13");
+
+        let line: Line<3> = Line::Comment(String::from(" noodles"));
+        assert_eq!(line.to_string(), "# noodles");
+
+        let line: Line<3> = Line::Record("sq0\t7\t13".parse()?);
+        assert_eq!(line.to_string(), "sq0\t7\t13");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), Box<dyn std::error::Error>> {
+        let mut track = Track::default();
+        track.insert("name", "peaks");
+        assert_eq!("track name=peaks".parse(), Ok(Line::<3>::Track(track)));
+
+        assert_eq!(
+            "browser position sq0:8-13".parse(),
+            Ok(Line::<3>::Browser(String::from("position sq0:8-13")))
+        );
+
+        assert_eq!(
+            "# noodles".parse(),
+            Ok(Line::<3>::Comment(String::from(" noodles")))
+        );
+
+        let record = Record::<3>::builder()
+            .set_reference_sequence_name("tracks")
+            .set_start_position(Position::try_from(8)?)
+            .set_end_position(Position::try_from(13)?)
+            .build()?;
+
+        assert_eq!("tracks\t7\t13".parse(), Ok(Line::Record(record)));
+
+        assert!(matches!(
+            "track name".parse::<Line<3>>(),
+            Err(ParseError::InvalidTrack(_))
+        ));
+
+        assert!(matches!(
+            "sq0\t7".parse::<Line<3>>(),
+            Err(ParseError::InvalidRecord(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,395 @@
+//! Genomic interval set operations.
+//!
+//! These operate on streams of features (e.g., [`crate::Reader::records`]) that are sorted by
+//! reference sequence and then by start position, similar to `bedtools` with `-sorted`. Only
+//! features that can still overlap the current feature are kept in memory.
+//!
+//! Operations on two streams take a [`Genome`], which defines the order of reference sequences
+//! that both streams must follow. Unsorted input and reference sequences missing from the genome
+//! are errors.
+
+mod closest;
+mod complement;
+mod coverage;
+mod genome;
+mod intersect;
+mod interval;
+mod merge;
+mod subtract;
+
+pub use self::{
+    closest::closest, complement::complement, coverage::coverage, coverage::Coverage,
+    genome::Genome, intersect::intersect, interval::Interval, merge::merge, merge::merge_stranded,
+    subtract::subtract,
+};
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+};
+
+use noodles_core::Position;
+
+use crate::{
+    record::{BedGraph, BedN, BroadPeak, NarrowPeak, Strand, TypedRecord},
+    Record,
+};
+
+/// A feature on a reference sequence.
+pub trait Feature {
+    /// Returns the reference sequence name.
+    fn reference_sequence_name(&self) -> &str;
+
+    /// Returns the start position.
+    fn start_position(&self) -> Position;
+
+    /// Returns the end position.
+    fn end_position(&self) -> Position;
+
+    /// Returns the strand.
+    ///
+    /// By default, features have no strand.
+    fn strand(&self) -> Option<Strand> {
+        None
+    }
+}
+
+// Implements `Feature` for BED records, with or without a strand, and for records that wrap a
+// BED record.
+macro_rules! impl_feature {
+    (unstranded: $($n:literal),+) => {
+        $(impl_feature!(@record Record<$n>, {});)+
+    };
+    (stranded: $($n:literal),+) => {
+        $(impl_feature!(@record Record<$n>, {
+            fn strand(&self) -> Option<Strand> {
+                self.strand()
+            }
+        });)+
+    };
+    (wrapped: $($ty:ty),+) => {
+        $(
+            impl Feature for $ty {
+                fn reference_sequence_name(&self) -> &str {
+                    Feature::reference_sequence_name(self.record())
+                }
+
+                fn start_position(&self) -> Position {
+                    Feature::start_position(self.record())
+                }
+
+                fn end_position(&self) -> Position {
+                    Feature::end_position(self.record())
+                }
+
+                fn strand(&self) -> Option<Strand> {
+                    Feature::strand(self.record())
+                }
+            }
+        )+
+    };
+    (@record $ty:ty, { $($strand:tt)* }) => {
+        impl Feature for $ty {
+            fn reference_sequence_name(&self) -> &str {
+                self.reference_sequence_name()
+            }
+
+            fn start_position(&self) -> Position {
+                self.start_position()
+            }
+
+            fn end_position(&self) -> Position {
+                self.end_position()
+            }
+
+            $($strand)*
+        }
+    };
+}
+
+impl_feature!(unstranded: 3, 4, 5);
+impl_feature!(stranded: 6, 7, 8, 9, 12);
+impl_feature!(wrapped: BedGraph, NarrowPeak, BroadPeak);
+
+impl<const N: u8> Feature for TypedRecord<N>
+where
+    Record<N>: BedN<3> + Feature,
+{
+    fn reference_sequence_name(&self) -> &str {
+        Feature::reference_sequence_name(self.record())
+    }
+
+    fn start_position(&self) -> Position {
+        Feature::start_position(self.record())
+    }
+
+    fn end_position(&self) -> Position {
+        Feature::end_position(self.record())
+    }
+
+    fn strand(&self) -> Option<Strand> {
+        Feature::strand(self.record())
+    }
+}
+
+/// The strand requirement for two features to be related.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strandedness {
+    /// Strands are ignored.
+    Ignore,
+    /// Features must be on the same strand.
+    Same,
+    /// Features must be on opposite strands.
+    Opposite,
+}
+
+impl Default for Strandedness {
+    fn default() -> Self {
+        Self::Ignore
+    }
+}
+
+impl Strandedness {
+    fn matches(self, a: Option<Strand>, b: Option<Strand>) -> bool {
+        match self {
+            Self::Ignore => true,
+            Self::Same => a == b,
+            Self::Opposite => matches!(
+                (a, b),
+                (Some(Strand::Forward), Some(Strand::Reverse))
+                    | (Some(Strand::Reverse), Some(Strand::Forward))
+            ),
+        }
+    }
+}
+
+// A feature with its reference sequence ID and 0-based, half-open interval.
+struct Entry<F> {
+    reference_sequence_id: usize,
+    start: usize,
+    end: usize,
+    strand: Option<Strand>,
+    feature: F,
+}
+
+impl<F> Entry<F> {
+    fn overlaps(&self, start: usize, end: usize) -> bool {
+        self.start < end && start < self.end
+    }
+}
+
+// A stream of features that validates sort order.
+//
+// Reference sequence IDs are given by the genome, if any, or the order the reference sequences
+// are first seen.
+struct Features<'g, I, F> {
+    inner: I,
+    genome: Option<&'g Genome>,
+    reference_sequence_ids: HashMap<String, usize>,
+    last: Option<(usize, usize)>,
+    peeked: Option<Entry<F>>,
+}
+
+impl<'g, I, F> Features<'g, I, F>
+where
+    I: Iterator<Item = io::Result<F>>,
+    F: Feature,
+{
+    fn new(inner: I, genome: Option<&'g Genome>) -> Self {
+        Self {
+            inner,
+            genome,
+            reference_sequence_ids: HashMap::new(),
+            last: None,
+            peeked: None,
+        }
+    }
+
+    fn peek(&mut self) -> io::Result<Option<&Entry<F>>> {
+        if self.peeked.is_none() {
+            self.peeked = self.read_entry()?;
+        }
+
+        Ok(self.peeked.as_ref())
+    }
+
+    fn next_entry(&mut self) -> io::Result<Option<Entry<F>>> {
+        match self.peeked.take() {
+            Some(entry) => Ok(Some(entry)),
+            None => self.read_entry(),
+        }
+    }
+
+    fn read_entry(&mut self) -> io::Result<Option<Entry<F>>> {
+        let feature = match self.inner.next().transpose()? {
+            Some(feature) => feature,
+            None => return Ok(None),
+        };
+
+        let reference_sequence_id =
+            self.reference_sequence_id(feature.reference_sequence_name())?;
+        let start = usize::from(feature.start_position()) - 1;
+        let end = usize::from(feature.end_position());
+
+        if let Some(last) = self.last {
+            if (reference_sequence_id, start) < last {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "features are not sorted: {}:{}",
+                        feature.reference_sequence_name(),
+                        feature.start_position()
+                    ),
+                ));
+            }
+        }
+
+        self.last = Some((reference_sequence_id, start));
+
+        Ok(Some(Entry {
+            reference_sequence_id,
+            start,
+            end,
+            strand: feature.strand(),
+            feature,
+        }))
+    }
+
+    fn reference_sequence_id(&mut self, name: &str) -> io::Result<usize> {
+        if let Some(genome) = self.genome {
+            return genome.get_index_of(name).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("reference sequence not in genome: {}", name),
+                )
+            });
+        }
+
+        if let Some(&id) = self.reference_sequence_ids.get(name) {
+            // Features on an earlier reference sequence are caught by the sort order check.
+            return Ok(id);
+        }
+
+        let id = self.reference_sequence_ids.len();
+        self.reference_sequence_ids.insert(name.into(), id);
+        Ok(id)
+    }
+}
+
+// A window of features that can overlap the current or any later query interval.
+struct Window<'g, I, F> {
+    features: Features<'g, I, F>,
+    entries: VecDeque<Entry<F>>,
+}
+
+impl<'g, I, F> Window<'g, I, F>
+where
+    I: Iterator<Item = io::Result<F>>,
+    F: Feature,
+{
+    fn new(inner: I, genome: &'g Genome) -> Self {
+        Self {
+            features: Features::new(inner, Some(genome)),
+            entries: VecDeque::new(),
+        }
+    }
+
+    // Moves the window to the given query interval.
+    //
+    // Query intervals must be sorted by reference sequence ID and start.
+    fn advance(
+        &mut self,
+        reference_sequence_id: usize,
+        start: usize,
+        end: usize,
+    ) -> io::Result<()> {
+        self.entries
+            .retain(|e| e.reference_sequence_id == reference_sequence_id && e.end > start);
+
+        loop {
+            match self.features.peek()? {
+                Some(entry)
+                    if entry.reference_sequence_id < reference_sequence_id
+                        || (entry.reference_sequence_id == reference_sequence_id
+                            && entry.start < end) => {}
+                _ => break,
+            }
+
+            if let Some(entry) = self.features.next_entry()? {
+                if entry.reference_sequence_id == reference_sequence_id && entry.end > start {
+                    self.entries.push_back(entry);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn overlapping(
+        &self,
+        start: usize,
+        end: usize,
+        strand: Option<Strand>,
+        strandedness: Strandedness,
+    ) -> impl Iterator<Item = &Entry<F>> {
+        self.entries
+            .iter()
+            .filter(move |e| e.overlaps(start, end) && strandedness.matches(strand, e.strand))
+    }
+}
+
+// Builds an interval from a 0-based, half-open interval.
+fn build_interval(
+    reference_sequence_name: &str,
+    start: usize,
+    end: usize,
+    strand: Option<Strand>,
+) -> io::Result<Interval> {
+    let start_position = start.checked_add(1).and_then(Position::new);
+    let end_position = Position::new(end);
+
+    match (start_position, end_position) {
+        (Some(start_position), Some(end_position)) => Ok(Interval::new(
+            reference_sequence_name,
+            start_position,
+            end_position,
+            strand,
+        )),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid interval",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strandedness_matches() {
+        let forward = Some(Strand::Forward);
+        let reverse = Some(Strand::Reverse);
+
+        assert!(Strandedness::Ignore.matches(forward, reverse));
+        assert!(Strandedness::Same.matches(forward, forward));
+        assert!(Strandedness::Same.matches(None, None));
+        assert!(!Strandedness::Same.matches(forward, reverse));
+        assert!(Strandedness::Opposite.matches(forward, reverse));
+        assert!(!Strandedness::Opposite.matches(forward, None));
+    }
+
+    #[test]
+    fn test_features_with_unsorted_input() {
+        fn t(data: &[u8]) -> io::Result<()> {
+            let mut reader = crate::Reader::new(data);
+            let mut features = Features::new(reader.records::<3>(), None);
+            while features.next_entry()?.is_some() {}
+            Ok(())
+        }
+
+        assert!(t(b"sq0\t7\t13\nsq0\t8\t9\nsq1\t0\t5\n").is_ok());
+        assert!(t(b"sq0\t7\t13\nsq0\t5\t9\n").is_err());
+        assert!(t(b"sq0\t7\t13\nsq1\t0\t5\nsq0\t8\t9\n").is_err());
+    }
+}
@@ -0,0 +1,303 @@
+use std::{collections::VecDeque, io};
+
+use super::{Entry, Feature, Features, Genome, Strandedness};
+
+/// Finds the closest feature for each feature in another stream.
+///
+/// For each feature in `a`, this yields the closest feature in `b` on the same reference
+/// sequence, if any, and the distance between them. The distance is the number of bases between
+/// the two features, i.e., overlapping and book-ended features have a distance of 0. Ties are
+/// broken by the first feature found, preferring overlapping features, then upstream features.
+///
+/// Both streams must be sorted by the reference sequence order in the genome and then by start
+/// position.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bed::{self as bed, ops::{self, Genome, Strandedness}};
+///
+/// let genome: Genome = [("sq0", 55), ("sq1", 34)].into_iter().collect();
+///
+/// let a = b"sq0\t8\t13\nsq1\t0\t5\n";
+/// let mut a_reader = bed::Reader::new(&a[..]);
+///
+/// let b = b"sq0\t0\t2\nsq0\t21\t34\n";
+/// let mut b_reader = bed::Reader::new(&b[..]);
+///
+/// let pairs: Vec<_> = ops::closest(
+///     a_reader.records::<3>(),
+///     b_reader.records::<3>(),
+///     &genome,
+///     Strandedness::Ignore,
+/// )
+/// .map(|result| {
+///     result.map(|(a, b)| (a.to_string(), b.map(|(b, distance)| (b.to_string(), distance))))
+/// })
+/// .collect::<io::Result<_>>()?;
+///
+/// assert_eq!(
+///     pairs,
+///     [
+///         (String::from("sq0\t8\t13"), Some((String::from("sq0\t0\t2"), 6))),
+///         (String::from("sq1\t0\t5"), None),
+///     ]
+/// );
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn closest<'g, A, B, F, G>(
+    a: A,
+    b: B,
+    genome: &'g Genome,
+    strandedness: Strandedness,
+) -> impl Iterator<Item = io::Result<(F, Option<(G, usize)>)>> + 'g
+where
+    A: IntoIterator<Item = io::Result<F>>,
+    A::IntoIter: 'g,
+    B: IntoIterator<Item = io::Result<G>>,
+    B::IntoIter: 'g,
+    F: Feature + 'g,
+    G: Feature + Clone + 'g,
+{
+    Closest {
+        a: Features::new(a.into_iter(), Some(genome)),
+        b: Features::new(b.into_iter(), Some(genome)),
+        strandedness,
+        reference_sequence_id: None,
+        entries: VecDeque::new(),
+        upstream_entries: Vec::new(),
+    }
+}
+
+struct Closest<'g, A, B, F, G> {
+    a: Features<'g, A, F>,
+    b: Features<'g, B, G>,
+    strandedness: Strandedness,
+    reference_sequence_id: Option<usize>,
+    // Features that end after the current start.
+    entries: VecDeque<Entry<G>>,
+    // The features with the greatest end that end at or before the current start. There is at
+    // most one per strand.
+    upstream_entries: Vec<Entry<G>>,
+}
+
+impl<'g, A, B, F, G> Closest<'g, A, B, F, G>
+where
+    A: Iterator<Item = io::Result<F>>,
+    B: Iterator<Item = io::Result<G>>,
+    F: Feature,
+    G: Feature + Clone,
+{
+    fn advance(&mut self, entry: &Entry<F>) -> io::Result<()> {
+        let reference_sequence_id = entry.reference_sequence_id;
+
+        if self.reference_sequence_id != Some(reference_sequence_id) {
+            self.entries.clear();
+            self.upstream_entries.clear();
+            self.reference_sequence_id = Some(reference_sequence_id);
+        }
+
+        loop {
+            let is_needed = match self.b.peek()? {
+                Some(b) if b.reference_sequence_id < reference_sequence_id => true,
+                Some(b) if b.reference_sequence_id == reference_sequence_id => {
+                    b.start < entry.end || !self.has_downstream_entry(entry)
+                }
+                _ => false,
+            };
+
+            if !is_needed {
+                break;
+            }
+
+            if let Some(b) = self.b.next_entry()? {
+                if b.reference_sequence_id == reference_sequence_id {
+                    self.entries.push_back(b);
+                }
+            }
+        }
+
+        let mut i = 0;
+
+        while i < self.entries.len() {
+            if self.entries[i].end <= entry.start {
+                if let Some(b) = self.entries.remove(i) {
+                    self.push_upstream_entry(b);
+                }
+            } else {
+                i += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn has_downstream_entry(&self, entry: &Entry<F>) -> bool {
+        self.entries
+            .iter()
+            .any(|b| b.start >= entry.end && self.strandedness.matches(entry.strand, b.strand))
+    }
+
+    fn push_upstream_entry(&mut self, b: Entry<G>) {
+        match self
+            .upstream_entries
+            .iter_mut()
+            .find(|upstream_entry| upstream_entry.strand == b.strand)
+        {
+            Some(upstream_entry) => {
+                if b.end >= upstream_entry.end {
+                    *upstream_entry = b;
+                }
+            }
+            None => self.upstream_entries.push(b),
+        }
+    }
+
+    fn find_closest(&self, entry: &Entry<F>) -> Option<(G, usize)> {
+        let mut closest: Option<(&Entry<G>, usize)> = None;
+
+        let distances = self
+            .entries
+            .iter()
+            .map(|b| {
+                let distance = if b.overlaps(entry.start, entry.end) {
+                    0
+                } else {
+                    b.start.saturating_sub(entry.end)
+                };
+
+                (b, distance)
+            })
+            .chain(
+                self.upstream_entries
+                    .iter()
+                    .map(|b| (b, entry.start - b.end)),
+            );
+
+        for (b, distance) in distances {
+            if !self.strandedness.matches(entry.strand, b.strand) {
+                continue;
+            }
+
+            if closest.map(|(_, d)| distance < d).unwrap_or(true) {
+                closest = Some((b, distance));
+            }
+        }
+
+        closest.map(|(b, distance)| (b.feature.clone(), distance))
+    }
+}
+
+impl<'g, A, B, F, G> Iterator for Closest<'g, A, B, F, G>
+where
+    A: Iterator<Item = io::Result<F>>,
+    B: Iterator<Item = io::Result<G>>,
+    F: Feature,
+    G: Feature + Clone,
+{
+    type Item = io::Result<(F, Option<(G, usize)>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = match self.a.next_entry() {
+            Ok(Some(entry)) => entry,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e)),
+        };
+
+        if let Err(e) = self.advance(&entry) {
+            return Some(Err(e));
+        }
+
+        let closest = self.find_closest(&entry);
+
+        Some(Ok((entry.feature, closest)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Reader, Record};
+
+    #[test]
+    fn test_closest() -> io::Result<()> {
+        let genome: Genome = [("sq0", 89), ("sq1", 34), ("sq2", 21)]
+            .into_iter()
+            .collect();
+
+        let a = b"\
+sq0\t8\t13\t.\t0\t+
+sq0\t21\t34\t.\t0\t-
+sq0\t40\t41\t.\t0\t+
+sq0\t60\t61\t.\t0\t+
+sq1\t5\t8\t.\t0\t+
+sq2\t5\t8\t.\t0\t+
+";
+
+        let b = b"\
+sq0\t0\t2\t.\t0\t+
+sq0\t5\t8\t.\t0\t-
+sq0\t30\t31\t.\t0\t+
+sq0\t44\t55\t.\t0\t-
+sq0\t70\t89\t.\t0\t+
+sq2\t0\t1\t.\t0\t+
+";
+
+        let t = |strandedness| -> io::Result<Vec<Option<(usize, usize)>>> {
+            let mut a_reader = Reader::new(&a[..]);
+            let mut b_reader = Reader::new(&b[..]);
+
+            closest(
+                a_reader.records::<6>(),
+                b_reader.records::<6>(),
+                &genome,
+                strandedness,
+            )
+            .map(|result| {
+                result.map(|(_, b): (Record<6>, Option<(Record<6>, usize)>)| {
+                    b.map(|(b, distance)| (usize::from(b.start_position()) - 1, distance))
+                })
+            })
+            .collect()
+        };
+
+        assert_eq!(
+            t(Strandedness::Ignore)?,
+            [
+                Some((5, 0)),
+                Some((30, 0)),
+                Some((44, 3)),
+                Some((44, 5)),
+                None,
+                Some((0, 4)),
+            ]
+        );
+
+        assert_eq!(
+            t(Strandedness::Same)?,
+            [
+                Some((0, 6)),
+                Some((44, 10)),
+                Some((30, 9)),
+                Some((70, 9)),
+                None,
+                Some((0, 4)),
+            ]
+        );
+
+        assert_eq!(
+            t(Strandedness::Opposite)?,
+            [
+                Some((5, 0)),
+                Some((30, 0)),
+                Some((44, 3)),
+                Some((44, 5)),
+                None,
+                None,
+            ]
+        );
+
+        Ok(())
+    }
+}
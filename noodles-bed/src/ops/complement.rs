@@ -0,0 +1,150 @@
+use std::io;
+
+use super::{build_interval, Feature, Features, Genome, Interval};
+
+/// Returns the intervals of the genome that are not covered by any feature.
+///
+/// Reference sequences with no features are yielded whole. Gaps are in the reference sequence
+/// order of the genome and are clamped to the reference sequence lengths.
+///
+/// Features must be sorted by the reference sequence order in the genome and then by start
+/// position.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bed::{self as bed, ops::{self, Genome}};
+///
+/// let genome: Genome = [("sq0", 21), ("sq1", 34)].into_iter().collect();
+///
+/// let data = b"sq0\t2\t5\nsq0\t8\t13\n";
+/// let mut reader = bed::Reader::new(&data[..]);
+///
+/// let intervals: Vec<_> = ops::complement(reader.records::<3>(), &genome)
+///     .map(|result| result.map(|interval| interval.to_string()))
+///     .collect::<io::Result<_>>()?;
+///
+/// assert_eq!(
+///     intervals,
+///     ["sq0\t0\t2", "sq0\t5\t8", "sq0\t13\t21", "sq1\t0\t34"]
+/// );
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn complement<'g, I, F>(
+    features: I,
+    genome: &'g Genome,
+) -> impl Iterator<Item = io::Result<Interval>> + 'g
+where
+    I: IntoIterator<Item = io::Result<F>>,
+    I::IntoIter: 'g,
+    F: Feature + 'g,
+{
+    Complement {
+        features: Features::new(features.into_iter(), Some(genome)),
+        genome,
+        reference_sequence_id: 0,
+        position: 0,
+    }
+}
+
+struct Complement<'g, I, F> {
+    features: Features<'g, I, F>,
+    genome: &'g Genome,
+    reference_sequence_id: usize,
+    position: usize,
+}
+
+impl<'g, I, F> Complement<'g, I, F>
+where
+    I: Iterator<Item = io::Result<F>>,
+    F: Feature,
+{
+    // Returns the reference sequence ID and 0-based, half-open interval of the next gap.
+    fn next_gap(&mut self) -> io::Result<Option<(usize, usize, usize)>> {
+        let reference_sequences = self.genome.reference_sequences();
+
+        while let Some((_, length)) = reference_sequences.get(self.reference_sequence_id) {
+            let id = self.reference_sequence_id;
+            let length = *length;
+
+            let is_on_reference_sequence = matches!(
+                self.features.peek()?,
+                Some(entry) if entry.reference_sequence_id == id
+            );
+
+            if is_on_reference_sequence {
+                if let Some(entry) = self.features.next_entry()? {
+                    let start = self.position;
+                    let end = entry.start.min(length);
+                    self.position = self.position.max(entry.end);
+
+                    if start < end {
+                        return Ok(Some((id, start, end)));
+                    }
+                }
+            } else {
+                let start = self.position;
+
+                self.reference_sequence_id += 1;
+                self.position = 0;
+
+                if start < length {
+                    return Ok(Some((id, start, length)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl<'g, I, F> Iterator for Complement<'g, I, F>
+where
+    I: Iterator<Item = io::Result<F>>,
+    F: Feature,
+{
+    type Item = io::Result<Interval>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_gap() {
+            Ok(Some((id, start, end))) => {
+                let (name, _) = &self.genome.reference_sequences()[id];
+                Some(build_interval(name, start, end, None))
+            }
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Reader;
+
+    #[test]
+    fn test_complement() -> io::Result<()> {
+        let genome: Genome = [("sq0", 21), ("sq1", 34), ("sq2", 8)].into_iter().collect();
+
+        let t = |data: &[u8]| -> io::Result<Vec<String>> {
+            let mut reader = Reader::new(data);
+
+            complement(reader.records::<3>(), &genome)
+                .map(|result| result.map(|interval| interval.to_string()))
+                .collect()
+        };
+
+        assert_eq!(
+            t(b"sq0\t0\t5\nsq0\t3\t8\nsq0\t13\t21\nsq2\t5\t13\n")?,
+            ["sq0\t8\t13", "sq1\t0\t34", "sq2\t0\t5"]
+        );
+
+        assert_eq!(t(b"")?, ["sq0\t0\t21", "sq1\t0\t34", "sq2\t0\t8"]);
+
+        assert!(t(b"sq1\t0\t5\nsq0\t0\t5\n").is_err());
+        assert!(t(b"sq3\t0\t5\n").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,302 @@
+use std::io;
+
+use super::{Feature, Features, Genome, Strandedness, Window};
+
+/// The coverage of a feature by another stream of features.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coverage<F> {
+    feature: F,
+    count: usize,
+    covered_bases: usize,
+    length: usize,
+}
+
+impl<F> Coverage<F> {
+    /// Returns the feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::{self as bed, ops::{self, Genome, Strandedness}};
+    ///
+    /// let genome: Genome = [("sq0", 55)].into_iter().collect();
+    /// let mut a_reader = bed::Reader::new(&b"sq0\t2\t13\n"[..]);
+    /// let mut b_reader = bed::Reader::new(&b"sq0\t0\t5\n"[..]);
+    ///
+    /// let mut coverages = ops::coverage(
+    ///     a_reader.records::<3>(),
+    ///     b_reader.records::<3>(),
+    ///     &genome,
+    ///     Strandedness::Ignore,
+    /// );
+    ///
+    /// let coverage = coverages.next().transpose()?.expect("missing coverage");
+    /// assert_eq!(coverage.feature().to_string(), "sq0\t2\t13");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn feature(&self) -> &F {
+        &self.feature
+    }
+
+    /// Returns the number of features that overlap the feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::{self as bed, ops::{self, Genome, Strandedness}};
+    ///
+    /// let genome: Genome = [("sq0", 55)].into_iter().collect();
+    /// let mut a_reader = bed::Reader::new(&b"sq0\t2\t13\n"[..]);
+    /// let mut b_reader = bed::Reader::new(&b"sq0\t0\t5\nsq0\t3\t8\n"[..]);
+    ///
+    /// let mut coverages = ops::coverage(
+    ///     a_reader.records::<3>(),
+    ///     b_reader.records::<3>(),
+    ///     &genome,
+    ///     Strandedness::Ignore,
+    /// );
+    ///
+    /// let coverage = coverages.next().transpose()?.expect("missing coverage");
+    /// assert_eq!(coverage.count(), 2);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the number of bases in the feature that are covered by at least one feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::{self as bed, ops::{self, Genome, Strandedness}};
+    ///
+    /// let genome: Genome = [("sq0", 55)].into_iter().collect();
+    /// let mut a_reader = bed::Reader::new(&b"sq0\t2\t13\n"[..]);
+    /// let mut b_reader = bed::Reader::new(&b"sq0\t0\t5\nsq0\t3\t8\n"[..]);
+    ///
+    /// let mut coverages = ops::coverage(
+    ///     a_reader.records::<3>(),
+    ///     b_reader.records::<3>(),
+    ///     &genome,
+    ///     Strandedness::Ignore,
+    /// );
+    ///
+    /// let coverage = coverages.next().transpose()?.expect("missing coverage");
+    /// assert_eq!(coverage.covered_bases(), 6);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn covered_bases(&self) -> usize {
+        self.covered_bases
+    }
+
+    /// Returns the length of the feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::{self as bed, ops::{self, Genome, Strandedness}};
+    ///
+    /// let genome: Genome = [("sq0", 55)].into_iter().collect();
+    /// let mut a_reader = bed::Reader::new(&b"sq0\t2\t13\n"[..]);
+    /// let mut b_reader = bed::Reader::new(&b""[..]);
+    ///
+    /// let mut coverages = ops::coverage(
+    ///     a_reader.records::<3>(),
+    ///     b_reader.records::<3>(),
+    ///     &genome,
+    ///     Strandedness::Ignore,
+    /// );
+    ///
+    /// let coverage = coverages.next().transpose()?.expect("missing coverage");
+    /// assert_eq!(coverage.length(), 11);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the fraction of the feature that is covered.
+    ///
+    /// This is 0 for a feature with a length of 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::{self as bed, ops::{self, Genome, Strandedness}};
+    ///
+    /// let genome: Genome = [("sq0", 55)].into_iter().collect();
+    /// let mut a_reader = bed::Reader::new(&b"sq0\t0\t8\n"[..]);
+    /// let mut b_reader = bed::Reader::new(&b"sq0\t2\t4\n"[..]);
+    ///
+    /// let mut coverages = ops::coverage(
+    ///     a_reader.records::<3>(),
+    ///     b_reader.records::<3>(),
+    ///     &genome,
+    ///     Strandedness::Ignore,
+    /// );
+    ///
+    /// let coverage = coverages.next().transpose()?.expect("missing coverage");
+    /// assert_eq!(coverage.fraction(), 0.25);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn fraction(&self) -> f64 {
+        if self.length == 0 {
+            0.0
+        } else {
+            self.covered_bases as f64 / self.length as f64
+        }
+    }
+}
+
+/// Computes the coverage of features by another stream of features.
+///
+/// For each feature in `a`, this yields the number of features in `b` that overlap it and the
+/// number of its bases that are covered.
+///
+/// Both streams must be sorted by the reference sequence order in the genome and then by start
+/// position.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bed::{self as bed, ops::{self, Genome, Strandedness}};
+///
+/// let genome: Genome = [("sq0", 55)].into_iter().collect();
+///
+/// let a = b"sq0\t0\t8\nsq0\t13\t21\n";
+/// let mut a_reader = bed::Reader::new(&a[..]);
+///
+/// let b = b"sq0\t2\t4\nsq0\t3\t6\n";
+/// let mut b_reader = bed::Reader::new(&b[..]);
+///
+/// let coverages: Vec<_> = ops::coverage(
+///     a_reader.records::<3>(),
+///     b_reader.records::<3>(),
+///     &genome,
+///     Strandedness::Ignore,
+/// )
+/// .map(|result| result.map(|coverage| (coverage.count(), coverage.covered_bases())))
+/// .collect::<io::Result<_>>()?;
+///
+/// assert_eq!(coverages, [(2, 4), (0, 0)]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn coverage<'g, A, B, F, G>(
+    a: A,
+    b: B,
+    genome: &'g Genome,
+    strandedness: Strandedness,
+) -> impl Iterator<Item = io::Result<Coverage<F>>> + 'g
+where
+    A: IntoIterator<Item = io::Result<F>>,
+    A::IntoIter: 'g,
+    B: IntoIterator<Item = io::Result<G>>,
+    B::IntoIter: 'g,
+    F: Feature + 'g,
+    G: Feature + 'g,
+{
+    let mut a = Features::new(a.into_iter(), Some(genome));
+    let mut b = Window::new(b.into_iter(), genome);
+
+    std::iter::from_fn(move || {
+        let entry = match a.next_entry() {
+            Ok(Some(entry)) => entry,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e)),
+        };
+
+        if let Err(e) = b.advance(entry.reference_sequence_id, entry.start, entry.end) {
+            return Some(Err(e));
+        }
+
+        let mut count = 0;
+        let mut covered_bases = 0;
+
+        // Overlapping features are in start order.
+        let mut position = entry.start;
+
+        for b in b.overlapping(entry.start, entry.end, entry.strand, strandedness) {
+            count += 1;
+
+            let start = position.max(b.start);
+            let end = entry.end.min(b.end);
+
+            if start < end {
+                covered_bases += end - start;
+                position = end;
+            }
+        }
+
+        Some(Ok(Coverage {
+            feature: entry.feature,
+            count,
+            covered_bases,
+            length: entry.end.saturating_sub(entry.start),
+        }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Reader;
+
+    #[test]
+    fn test_coverage() -> io::Result<()> {
+        let genome: Genome = [("sq0", 55), ("sq1", 34)].into_iter().collect();
+
+        let a = b"\
+sq0\t0\t21\t.\t0\t+
+sq0\t13\t34\t.\t0\t-
+sq1\t5\t8\t.\t0\t+
+";
+
+        let b = b"\
+sq0\t2\t5\t.\t0\t+
+sq0\t3\t8\t.\t0\t-
+sq0\t13\t21\t.\t0\t+
+sq0\t30\t55\t.\t0\t-
+";
+
+        let t = |strandedness| -> io::Result<Vec<(usize, usize, usize)>> {
+            let mut a_reader = Reader::new(&a[..]);
+            let mut b_reader = Reader::new(&b[..]);
+
+            coverage(
+                a_reader.records::<6>(),
+                b_reader.records::<6>(),
+                &genome,
+                strandedness,
+            )
+            .map(|result| {
+                result.map(|coverage| {
+                    (
+                        coverage.count(),
+                        coverage.covered_bases(),
+                        coverage.length(),
+                    )
+                })
+            })
+            .collect()
+        };
+
+        assert_eq!(
+            t(Strandedness::Ignore)?,
+            [(3, 14, 21), (2, 12, 21), (0, 0, 3),]
+        );
+
+        assert_eq!(
+            t(Strandedness::Opposite)?,
+            [(1, 5, 21), (1, 8, 21), (0, 0, 3),]
+        );
+
+        Ok(())
+    }
+}
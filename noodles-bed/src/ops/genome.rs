@@ -0,0 +1,161 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead},
+};
+
+use crate::reader::read_line;
+
+/// A list of reference sequence names and lengths.
+///
+/// This defines the order of reference sequences in sorted input and the lengths used to
+/// complement features.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Genome {
+    reference_sequences: Vec<(String, usize)>,
+    indices: HashMap<String, usize>,
+}
+
+impl Genome {
+    /// Reads a genome file.
+    ///
+    /// A genome file (e.g., `chrom.sizes` or a FASTA index) has a reference sequence name and
+    /// length per line, separated by a tab. Any other fields are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::ops::Genome;
+    ///
+    /// let data = b"sq0\t8\nsq1\t13\n";
+    /// let genome = Genome::read(&data[..])?;
+    ///
+    /// assert_eq!(
+    ///     genome.reference_sequences(),
+    ///     [(String::from("sq0"), 8), (String::from("sq1"), 13)]
+    /// );
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read<R>(mut reader: R) -> io::Result<Self>
+    where
+        R: BufRead,
+    {
+        const DELIMITER: char = '\t';
+
+        let mut genome = Self::default();
+        let mut buf = String::new();
+
+        loop {
+            buf.clear();
+
+            if read_line(&mut reader, &mut buf)? == 0 {
+                break;
+            }
+
+            if buf.is_empty() {
+                continue;
+            }
+
+            let mut fields = buf.split(DELIMITER);
+
+            let (name, length) =
+                match (fields.next(), fields.next()) {
+                    (Some(name), Some(raw_length)) => raw_length
+                        .parse()
+                        .map(|length| (name, length))
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+                    _ => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "missing reference sequence length",
+                        ))
+                    }
+                };
+
+            if genome.get_index_of(name).is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("duplicate reference sequence name: {}", name),
+                ));
+            }
+
+            genome.push(name, length);
+        }
+
+        Ok(genome)
+    }
+
+    /// Returns the reference sequence names and lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::ops::Genome;
+    /// let genome: Genome = [("sq0", 8)].into_iter().collect();
+    /// assert_eq!(genome.reference_sequences(), [(String::from("sq0"), 8)]);
+    /// ```
+    pub fn reference_sequences(&self) -> &[(String, usize)] {
+        &self.reference_sequences
+    }
+
+    /// Returns the index of the reference sequence with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::ops::Genome;
+    /// let genome: Genome = [("sq0", 8), ("sq1", 13)].into_iter().collect();
+    /// assert_eq!(genome.get_index_of("sq1"), Some(1));
+    /// assert!(genome.get_index_of("sq2").is_none());
+    /// ```
+    pub fn get_index_of(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).copied()
+    }
+
+    fn push<N>(&mut self, name: N, length: usize)
+    where
+        N: Into<String>,
+    {
+        let name = name.into();
+        let i = self.reference_sequences.len();
+        self.indices.entry(name.clone()).or_insert(i);
+        self.reference_sequences.push((name, length));
+    }
+}
+
+impl<N> FromIterator<(N, usize)> for Genome
+where
+    N: Into<String>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (N, usize)>,
+    {
+        let mut genome = Self::default();
+
+        for (name, length) in iter {
+            genome.push(name, length);
+        }
+
+        genome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() -> io::Result<()> {
+        let data = b"sq0\t8\t0\nsq1\t13\n";
+        let genome = Genome::read(&data[..])?;
+        assert_eq!(genome.get_index_of("sq0"), Some(0));
+        assert_eq!(genome.get_index_of("sq1"), Some(1));
+
+        assert!(Genome::read(&b"sq0\n"[..]).is_err());
+        assert!(Genome::read(&b"sq0\tndls\n"[..]).is_err());
+        assert!(Genome::read(&b"sq0\t8\nsq0\t13\n"[..]).is_err());
+
+        Ok(())
+    }
+}
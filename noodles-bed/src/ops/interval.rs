@@ -0,0 +1,182 @@
+use std::fmt;
+
+use noodles_core::Position;
+
+use super::Feature;
+use crate::record::Strand;
+
+/// An interval on a reference sequence.
+///
+/// This is the output of operations that build new intervals, e.g., [`super::merge`]. It is
+/// formatted as a BED3 record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Interval {
+    reference_sequence_name: String,
+    start_position: Position,
+    end_position: Position,
+    strand: Option<Strand>,
+}
+
+impl Interval {
+    /// Creates an interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::ops::Interval;
+    /// use noodles_core::Position;
+    ///
+    /// let interval = Interval::new(
+    ///     "sq0",
+    ///     Position::try_from(8)?,
+    ///     Position::try_from(13)?,
+    ///     None,
+    /// );
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn new<N>(
+        reference_sequence_name: N,
+        start_position: Position,
+        end_position: Position,
+        strand: Option<Strand>,
+    ) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            reference_sequence_name: reference_sequence_name.into(),
+            start_position,
+            end_position,
+            strand,
+        }
+    }
+
+    /// Returns the reference sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::ops::Interval;
+    /// use noodles_core::Position;
+    ///
+    /// let interval = Interval::new(
+    ///     "sq0",
+    ///     Position::try_from(8)?,
+    ///     Position::try_from(13)?,
+    ///     None,
+    /// );
+    ///
+    /// assert_eq!(interval.reference_sequence_name(), "sq0");
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn reference_sequence_name(&self) -> &str {
+        &self.reference_sequence_name
+    }
+
+    /// Returns the start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::ops::Interval;
+    /// use noodles_core::Position;
+    ///
+    /// let start_position = Position::try_from(8)?;
+    /// let interval = Interval::new("sq0", start_position, Position::try_from(13)?, None);
+    ///
+    /// assert_eq!(interval.start_position(), start_position);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn start_position(&self) -> Position {
+        self.start_position
+    }
+
+    /// Returns the end position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::ops::Interval;
+    /// use noodles_core::Position;
+    ///
+    /// let end_position = Position::try_from(13)?;
+    /// let interval = Interval::new("sq0", Position::try_from(8)?, end_position, None);
+    ///
+    /// assert_eq!(interval.end_position(), end_position);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn end_position(&self) -> Position {
+        self.end_position
+    }
+
+    /// Returns the strand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{ops::Interval, record::Strand};
+    /// use noodles_core::Position;
+    ///
+    /// let interval = Interval::new(
+    ///     "sq0",
+    ///     Position::try_from(8)?,
+    ///     Position::try_from(13)?,
+    ///     Some(Strand::Forward),
+    /// );
+    ///
+    /// assert_eq!(interval.strand(), Some(Strand::Forward));
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn strand(&self) -> Option<Strand> {
+        self.strand
+    }
+}
+
+impl Feature for Interval {
+    fn reference_sequence_name(&self) -> &str {
+        self.reference_sequence_name()
+    }
+
+    fn start_position(&self) -> Position {
+        self.start_position()
+    }
+
+    fn end_position(&self) -> Position {
+        self.end_position()
+    }
+
+    fn strand(&self) -> Option<Strand> {
+        self.strand()
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}",
+            self.reference_sequence_name,
+            usize::from(self.start_position) - 1,
+            self.end_position
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), noodles_core::position::TryFromIntError> {
+        let interval = Interval::new(
+            "sq0",
+            Position::try_from(8)?,
+            Position::try_from(13)?,
+            Some(Strand::Reverse),
+        );
+
+        assert_eq!(interval.to_string(), "sq0\t7\t13");
+
+        Ok(())
+    }
+}
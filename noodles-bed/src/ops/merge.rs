@@ -0,0 +1,252 @@
+use std::io;
+
+use super::{build_interval, Entry, Feature, Features, Interval};
+use crate::record::Strand;
+
+/// Merges overlapping and book-ended features.
+///
+/// Features must be sorted by reference sequence and then by start position. Reference sequences
+/// can be in any order, but all features on a reference sequence must be contiguous.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bed::{self as bed, ops};
+///
+/// let data = b"sq0\t2\t5\nsq0\t4\t8\nsq0\t8\t13\nsq0\t21\t34\nsq1\t0\t5\n";
+/// let mut reader = bed::Reader::new(&data[..]);
+///
+/// let intervals: Vec<_> = ops::merge(reader.records::<3>())
+///     .map(|result| result.map(|interval| interval.to_string()))
+///     .collect::<io::Result<_>>()?;
+///
+/// assert_eq!(intervals, ["sq0\t2\t13", "sq0\t21\t34", "sq1\t0\t5"]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn merge<I, F>(features: I) -> impl Iterator<Item = io::Result<Interval>>
+where
+    I: IntoIterator<Item = io::Result<F>>,
+    F: Feature,
+{
+    Merge::new(features.into_iter(), false)
+}
+
+/// Merges overlapping and book-ended features on the same strand.
+///
+/// This is the same as [`merge`], but only features on the same strand are merged. Merged
+/// intervals have the strand of their features.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bed::{self as bed, ops, record::Strand};
+///
+/// let data = b"sq0\t2\t5\t.\t0\t+\nsq0\t4\t8\t.\t0\t-\nsq0\t8\t13\t.\t0\t+\n";
+/// let mut reader = bed::Reader::new(&data[..]);
+///
+/// let intervals: Vec<_> = ops::merge_stranded(reader.records::<6>())
+///     .map(|result| result.map(|interval| (interval.to_string(), interval.strand())))
+///     .collect::<io::Result<_>>()?;
+///
+/// assert_eq!(
+///     intervals,
+///     [
+///         (String::from("sq0\t2\t5"), Some(Strand::Forward)),
+///         (String::from("sq0\t4\t8"), Some(Strand::Reverse)),
+///         (String::from("sq0\t8\t13"), Some(Strand::Forward)),
+///     ]
+/// );
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn merge_stranded<I, F>(features: I) -> impl Iterator<Item = io::Result<Interval>>
+where
+    I: IntoIterator<Item = io::Result<F>>,
+    F: Feature,
+{
+    Merge::new(features.into_iter(), true)
+}
+
+// A merged interval.
+struct Run {
+    reference_sequence_id: usize,
+    reference_sequence_name: String,
+    start: usize,
+    end: usize,
+    strand: Option<Strand>,
+}
+
+struct Merge<I, F> {
+    features: Features<'static, I, F>,
+    is_stranded: bool,
+    // Runs that can still be extended. There is at most one per strand.
+    open_runs: Vec<Run>,
+    // Runs that can no longer be extended but may start after an open run.
+    closed_runs: Vec<Run>,
+    is_eof: bool,
+}
+
+impl<I, F> Merge<I, F>
+where
+    I: Iterator<Item = io::Result<F>>,
+    F: Feature,
+{
+    fn new(features: I, is_stranded: bool) -> Self {
+        Self {
+            features: Features::new(features, None),
+            is_stranded,
+            open_runs: Vec::new(),
+            closed_runs: Vec::new(),
+            is_eof: false,
+        }
+    }
+
+    fn add(&mut self, entry: Entry<F>) {
+        let strand = if self.is_stranded { entry.strand } else { None };
+
+        let mut i = 0;
+
+        while i < self.open_runs.len() {
+            let run = &self.open_runs[i];
+
+            if run.reference_sequence_id != entry.reference_sequence_id || run.end < entry.start {
+                let run = self.open_runs.swap_remove(i);
+                self.closed_runs.push(run);
+            } else {
+                i += 1;
+            }
+        }
+
+        if let Some(run) = self.open_runs.iter_mut().find(|run| run.strand == strand) {
+            run.end = run.end.max(entry.end);
+        } else {
+            self.open_runs.push(Run {
+                reference_sequence_id: entry.reference_sequence_id,
+                reference_sequence_name: entry.feature.reference_sequence_name().into(),
+                start: entry.start,
+                end: entry.end,
+                strand,
+            });
+        }
+    }
+
+    // Returns the next closed run that starts before all open runs.
+    fn next_closed_run(&mut self) -> Option<Run> {
+        let min_open_start = self
+            .open_runs
+            .iter()
+            .map(|run| (run.reference_sequence_id, run.start))
+            .min();
+
+        let (i, run) = self
+            .closed_runs
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, run)| (run.reference_sequence_id, run.start))?;
+
+        match min_open_start {
+            Some(start) if start < (run.reference_sequence_id, run.start) => None,
+            _ => Some(self.closed_runs.remove(i)),
+        }
+    }
+}
+
+impl<I, F> Iterator for Merge<I, F>
+where
+    I: Iterator<Item = io::Result<F>>,
+    F: Feature,
+{
+    type Item = io::Result<Interval>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(run) = self.next_closed_run() {
+                return Some(build_interval(
+                    &run.reference_sequence_name,
+                    run.start,
+                    run.end,
+                    run.strand,
+                ));
+            }
+
+            if self.is_eof {
+                return None;
+            }
+
+            match self.features.next_entry() {
+                Ok(Some(entry)) => self.add(entry),
+                Ok(None) => {
+                    self.closed_runs.append(&mut self.open_runs);
+                    self.is_eof = true;
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Reader;
+
+    fn t<I>(intervals: I) -> io::Result<Vec<String>>
+    where
+        I: Iterator<Item = io::Result<Interval>>,
+    {
+        intervals
+            .map(|result| result.map(|interval| interval.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_merge() -> io::Result<()> {
+        let data = b"sq0\t0\t5\nsq0\t1\t3\nsq0\t4\t8\nsq0\t9\t13\nsq2\t5\t8\nsq1\t0\t1\n";
+        let mut reader = Reader::new(&data[..]);
+        let actual = t(merge(reader.records::<3>()))?;
+        assert_eq!(
+            actual,
+            ["sq0\t0\t8", "sq0\t9\t13", "sq2\t5\t8", "sq1\t0\t1"]
+        );
+
+        let mut reader = Reader::new(&b""[..]);
+        assert!(t(merge(reader.records::<3>()))?.is_empty());
+
+        let data = b"sq0\t5\t8\nsq0\t0\t1\n";
+        let mut reader = Reader::new(&data[..]);
+        assert!(t(merge(reader.records::<3>())).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_stranded() -> io::Result<()> {
+        let data = b"\
+sq0\t0\t21\t.\t0\t+
+sq0\t2\t5\t.\t0\t-
+sq0\t5\t8\t.\t0\t-
+sq0\t13\t34\t.\t0\t-
+sq0\t40\t55\t.\t0\t.
+sq1\t0\t5\t.\t0\t+
+";
+
+        let mut reader = Reader::new(&data[..]);
+        let actual: Vec<_> = merge_stranded(reader.records::<6>())
+            .map(|result| result.map(|interval| (interval.to_string(), interval.strand())))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(
+            actual,
+            [
+                (String::from("sq0\t0\t21"), Some(Strand::Forward)),
+                (String::from("sq0\t2\t8"), Some(Strand::Reverse)),
+                (String::from("sq0\t13\t34"), Some(Strand::Reverse)),
+                (String::from("sq0\t40\t55"), None),
+                (String::from("sq1\t0\t5"), Some(Strand::Forward)),
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use std::{collections::VecDeque, io};
+
+use super::{build_interval, Feature, Features, Genome, Interval, Strandedness, Window};
+
+/// Subtracts features from another stream of features.
+///
+/// For each feature in `a`, this yields the parts of the feature that do not overlap any feature
+/// in `b`. A feature with no overlaps is yielded whole.
+///
+/// Both streams must be sorted by the reference sequence order in the genome and then by start
+/// position.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bed::{self as bed, ops::{self, Genome, Strandedness}};
+///
+/// let genome: Genome = [("sq0", 55)].into_iter().collect();
+///
+/// let a = b"sq0\t2\t21\nsq0\t34\t55\n";
+/// let mut a_reader = bed::Reader::new(&a[..]);
+///
+/// let b = b"sq0\t5\t8\nsq0\t13\t21\n";
+/// let mut b_reader = bed::Reader::new(&b[..]);
+///
+/// let intervals: Vec<_> = ops::subtract(
+///     a_reader.records::<3>(),
+///     b_reader.records::<3>(),
+///     &genome,
+///     Strandedness::Ignore,
+/// )
+/// .map(|result| result.map(|interval| interval.to_string()))
+/// .collect::<io::Result<_>>()?;
+///
+/// assert_eq!(intervals, ["sq0\t2\t5", "sq0\t8\t13", "sq0\t34\t55"]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn subtract<'g, A, B, F, G>(
+    a: A,
+    b: B,
+    genome: &'g Genome,
+    strandedness: Strandedness,
+) -> impl Iterator<Item = io::Result<Interval>> + 'g
+where
+    A: IntoIterator<Item = io::Result<F>>,
+    A::IntoIter: 'g,
+    B: IntoIterator<Item = io::Result<G>>,
+    B::IntoIter: 'g,
+    F: Feature + 'g,
+    G: Feature + 'g,
+{
+    Subtract {
+        a: Features::new(a.into_iter(), Some(genome)),
+        b: Window::new(b.into_iter(), genome),
+        strandedness,
+        intervals: VecDeque::new(),
+    }
+}
+
+struct Subtract<'g, A, B, F, G> {
+    a: Features<'g, A, F>,
+    b: Window<'g, B, G>,
+    strandedness: Strandedness,
+    intervals: VecDeque<io::Result<Interval>>,
+}
+
+impl<'g, A, B, F, G> Iterator for Subtract<'g, A, B, F, G>
+where
+    A: Iterator<Item = io::Result<F>>,
+    B: Iterator<Item = io::Result<G>>,
+    F: Feature,
+    G: Feature,
+{
+    type Item = io::Result<Interval>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(result) = self.intervals.pop_front() {
+                return Some(result);
+            }
+
+            let entry = match self.a.next_entry() {
+                Ok(Some(entry)) => entry,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if let Err(e) = self
+                .b
+                .advance(entry.reference_sequence_id, entry.start, entry.end)
+            {
+                return Some(Err(e));
+            }
+
+            let name = entry.feature.reference_sequence_name();
+
+            // Overlapping features are in start order.
+            let mut position = entry.start;
+
+            for b in self
+                .b
+                .overlapping(entry.start, entry.end, entry.strand, self.strandedness)
+            {
+                if b.start > position {
+                    self.intervals
+                        .push_back(build_interval(name, position, b.start, entry.strand));
+                }
+
+                position = position.max(b.end);
+            }
+
+            if position < entry.end {
+                self.intervals
+                    .push_back(build_interval(name, position, entry.end, entry.strand));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Reader;
+
+    #[test]
+    fn test_subtract() -> io::Result<()> {
+        let genome: Genome = [("sq0", 55), ("sq1", 34)].into_iter().collect();
+
+        let a = b"\
+sq0\t0\t21\t.\t0\t+
+sq0\t13\t34\t.\t0\t-
+sq1\t5\t8\t.\t0\t+
+";
+
+        let b = b"\
+sq0\t2\t5\t.\t0\t+
+sq0\t3\t8\t.\t0\t-
+sq0\t13\t21\t.\t0\t+
+sq0\t30\t55\t.\t0\t-
+";
+
+        let t = |strandedness| -> io::Result<Vec<String>> {
+            let mut a_reader = Reader::new(&a[..]);
+            let mut b_reader = Reader::new(&b[..]);
+
+            subtract(
+                a_reader.records::<6>(),
+                b_reader.records::<6>(),
+                &genome,
+                strandedness,
+            )
+            .map(|result| result.map(|interval| interval.to_string()))
+            .collect()
+        };
+
+        assert_eq!(
+            t(Strandedness::Ignore)?,
+            ["sq0\t0\t2", "sq0\t8\t13", "sq0\t21\t30", "sq1\t5\t8"]
+        );
+
+        assert_eq!(
+            t(Strandedness::Same)?,
+            ["sq0\t0\t2", "sq0\t5\t13", "sq0\t13\t30", "sq1\t5\t8"]
+        );
+
+        Ok(())
+    }
+}
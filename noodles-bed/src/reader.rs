@@ -1,10 +1,25 @@
+//! BED reader.
+
+mod query;
+
+pub use self::query::{Query, QueryIndex};
+
 use std::{
-    io::{self, BufRead},
+    error,
+    io::{self, BufRead, Read, Seek},
     iter,
-    str::FromStr,
+    str::{self, FromStr},
 };
 
-use super::Record;
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+
+use super::{
+    auto_sql::AutoSql,
+    line::is_header_line,
+    record::{BedGraph, BedN, BroadPeak, NarrowPeak, TypedRecord},
+    Line, Record,
+};
 
 /// A BED reader.
 pub struct Reader<R> {
@@ -91,8 +106,54 @@ where
         read_line(&mut self.inner, buf)
     }
 
+    /// Returns an iterator over lines starting from the current stream position.
+    ///
+    /// Unlike [`Self::records`], this includes track, browser, and comment lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"browser position sq0:8-13\ntrack name=peaks\nsq0\t7\t13\n";
+    /// let mut reader = bed::Reader::new(&data[..]);
+    ///
+    /// let mut lines = reader.lines::<3>();
+    ///
+    /// assert!(matches!(lines.next().transpose()?, Some(bed::Line::Browser(_))));
+    ///
+    /// let line = lines.next().transpose()?;
+    /// assert!(matches!(line, Some(bed::Line::Track(track)) if track.name() == Some("peaks")));
+    ///
+    /// assert!(matches!(lines.next().transpose()?, Some(bed::Line::Record(_))));
+    /// assert!(lines.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn lines<const N: u8>(&mut self) -> impl Iterator<Item = io::Result<Line<N>>> + '_
+    where
+        Record<N>: FromStr<Err = super::record::ParseError>,
+    {
+        let mut buf = String::new();
+
+        iter::from_fn(move || {
+            buf.clear();
+
+            match self.read_line(&mut buf) {
+                Ok(0) => None,
+                Ok(_) => Some(
+                    buf.parse()
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+                ),
+                Err(e) => Some(Err(e)),
+            }
+        })
+    }
+
     /// Returns an iterator over records starting from the current stream position.
     ///
+    /// Track, browser, and comment lines are skipped.
+    ///
     /// # Examples
     ///
     /// ```
@@ -100,7 +161,7 @@ where
     /// use noodles_bed as bed;
     /// use noodles_core::Position;
     ///
-    /// let data = b"sq0\t7\t13\n# sq0\t20\t34\n";
+    /// let data = b"track name=peaks\nsq0\t7\t13\n# sq0\t20\t34\n";
     /// let mut reader = bed::Reader::new(&data[..]);
     ///
     /// let mut records = reader.records::<3>();
@@ -116,8 +177,139 @@ where
     where
         Record<N>: FromStr<Err = super::record::ParseError>,
     {
-        const COMMENT_PREFIX: &str = "#";
+        self.parsed_records(str::parse)
+    }
+
+    /// Returns an iterator over bedGraph records starting from the current stream position.
+    ///
+    /// Track, browser, and comment lines are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"track type=bedGraph\nsq0\t7\t13\t0.5\n";
+    /// let mut reader = bed::Reader::new(&data[..]);
+    ///
+    /// let mut records = reader.bed_graph_records();
+    ///
+    /// let record = records.next().transpose()?;
+    /// assert_eq!(record.map(|r| r.value()), Some(0.5));
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn bed_graph_records(&mut self) -> impl Iterator<Item = io::Result<BedGraph>> + '_ {
+        self.parsed_records(str::parse)
+    }
+
+    /// Returns an iterator over ENCODE narrowPeak records starting from the current stream
+    /// position.
+    ///
+    /// Track, browser, and comment lines are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"sq0\t7\t13\tpeak0\t500\t.\t5.5\t3.2\t-1\t2\n";
+    /// let mut reader = bed::Reader::new(&data[..]);
+    ///
+    /// let mut records = reader.narrow_peak_records();
+    ///
+    /// let record = records.next().transpose()?;
+    /// assert_eq!(record.and_then(|r| r.peak()), Some(2));
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn narrow_peak_records(&mut self) -> impl Iterator<Item = io::Result<NarrowPeak>> + '_ {
+        self.parsed_records(str::parse)
+    }
+
+    /// Returns an iterator over ENCODE broadPeak records starting from the current stream
+    /// position.
+    ///
+    /// Track, browser, and comment lines are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let data = b"sq0\t7\t13\tpeak0\t500\t.\t5.5\t3.2\t-1\n";
+    /// let mut reader = bed::Reader::new(&data[..]);
+    ///
+    /// let mut records = reader.broad_peak_records();
+    ///
+    /// let record = records.next().transpose()?;
+    /// assert_eq!(record.and_then(|r| r.p_value()), Some(3.2));
+    ///
+    /// assert!(records.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn broad_peak_records(&mut self) -> impl Iterator<Item = io::Result<BroadPeak>> + '_ {
+        self.parsed_records(str::parse)
+    }
+
+    /// Returns an iterator over records with typed custom fields starting from the current stream
+    /// position.
+    ///
+    /// Custom fields are parsed and validated using the given autoSql definition. Track, browser,
+    /// and comment lines are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::{self as bed, auto_sql::AutoSql, record::custom_fields::Value};
+    ///
+    /// let auto_sql: AutoSql = r#"table bed3plus1
+    /// "BED3+1"
+    /// (
+    ///     string chrom; "Reference sequence name"
+    ///     uint chromStart; "Start position"
+    ///     uint chromEnd; "End position"
+    ///     ushort dp; "Read depth"
+    /// )"#.parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    ///
+    /// let data = b"sq0\t7\t13\t21\nsq0\t13\t21\t-1\n";
+    /// let mut reader = bed::Reader::new(&data[..]);
+    ///
+    /// let mut records = reader.typed_records::<3>(&auto_sql);
+    ///
+    /// let record = records.next().transpose()?;
+    /// assert_eq!(
+    ///     record.map(|r| r.custom_fields()[0].clone()),
+    ///     Some(Value::Integer(21))
+    /// );
+    ///
+    /// assert!(records.next().transpose().is_err());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn typed_records<'r, const N: u8>(
+        &'r mut self,
+        auto_sql: &'r AutoSql,
+    ) -> impl Iterator<Item = io::Result<TypedRecord<N>>> + 'r
+    where
+        Record<N>: BedN<3> + FromStr<Err = super::record::ParseError>,
+    {
+        self.parsed_records(move |s| TypedRecord::from_str_auto_sql(s, auto_sql))
+    }
 
+    fn parsed_records<'r, F, T, E>(
+        &'r mut self,
+        mut parse: F,
+    ) -> impl Iterator<Item = io::Result<T>> + 'r
+    where
+        F: FnMut(&str) -> Result<T, E> + 'r,
+        E: error::Error + Send + Sync + 'static,
+    {
         let mut buf = String::new();
 
         iter::from_fn(move || loop {
@@ -126,12 +318,11 @@ where
             match self.read_line(&mut buf) {
                 Ok(0) => return None,
                 Ok(_) => {
-                    if buf.starts_with(COMMENT_PREFIX) {
+                    if is_header_line(&buf) {
                         continue;
                     } else {
                         return Some(
-                            buf.parse()
-                                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+                            parse(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
                         );
                     }
                 }
@@ -141,7 +332,116 @@ where
     }
 }
 
-fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read,
+{
+    /// Returns the current virtual position of the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let data = Vec::new();
+    /// let reader = bed::Reader::new(bgzf::Reader::new(&data[..]));
+    /// let virtual_position = reader.virtual_position();
+    ///
+    /// assert_eq!(virtual_position.compressed(), 0);
+    /// assert_eq!(virtual_position.uncompressed(), 0);
+    /// ```
+    pub fn virtual_position(&self) -> bgzf::VirtualPosition {
+        self.inner.virtual_position()
+    }
+}
+
+impl<R> Reader<bgzf::Reader<R>>
+where
+    R: Read + Seek,
+{
+    /// Seeks the underlying BGZF stream to the given virtual position.
+    ///
+    /// Virtual positions typically come from an associated index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_bed as bed;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let data = Cursor::new(Vec::new());
+    /// let mut reader = bed::Reader::new(bgzf::Reader::new(data));
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::default();
+    /// reader.seek(virtual_position)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek(&mut self, pos: bgzf::VirtualPosition) -> io::Result<bgzf::VirtualPosition> {
+        self.inner.seek(pos)
+    }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// The index can be a tabix index ([`noodles_tabix::Index`]) or a CSI index
+    /// ([`noodles_csi::Index`]) with a tabix-style auxiliary header. See [`crate::IndexedWriter`]
+    /// to build a tabix index while writing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bed as bed;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    ///
+    /// let mut reader = File::open("sample.bed.gz")
+    ///     .map(bgzf::Reader::new)
+    ///     .map(bed::Reader::new)?;
+    ///
+    /// let index = tabix::read("sample.bed.gz.tbi")?;
+    /// let region = "sq0:8-13".parse()?;
+    /// let query = reader.query::<_, 3>(&index, &region)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<I, const N: u8>(
+        &mut self,
+        index: &I,
+        region: &Region,
+    ) -> io::Result<Query<'_, R, N>>
+    where
+        I: QueryIndex,
+        Record<N>: BedN<3> + FromStr<Err = super::record::ParseError>,
+    {
+        let reference_sequence_id =
+            index.reference_sequence_id(region.name())?.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "region reference sequence does not exist in reference sequences: {:?}",
+                        region
+                    ),
+                )
+            })?;
+
+        let chunks = index.query(reference_sequence_id, region.interval())?;
+
+        Ok(Query::new(
+            self,
+            chunks,
+            region.name().into(),
+            region.interval(),
+        ))
+    }
+}
+
+pub(crate) fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
 where
     R: BufRead,
 {
@@ -186,4 +486,26 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_records_with_header_lines() -> io::Result<()> {
+        let data = b"\
+browser position sq0:8-13
+browser hide all
+track name=peaks description=\"ChIP-seq peaks\"
+# noodles
+sq0\t7\t13
+";
+
+        let mut reader = Reader::new(&data[..]);
+        let lines = reader.lines::<3>().collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[1], Line::Browser(String::from("hide all")));
+
+        let mut reader = Reader::new(&data[..]);
+        let records = reader.records::<3>().collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(records.len(), 1);
+
+        Ok(())
+    }
 }
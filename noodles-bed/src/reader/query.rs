@@ -0,0 +1,230 @@
+use std::{
+    io::{self, Read, Seek},
+    str::FromStr,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::region::Interval;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk, BinningIndex};
+use noodles_tabix as tabix;
+
+use super::Reader;
+use crate::{line::is_header_line, record, record::BedN, Record};
+
+/// A binning index that resolves reference sequence names.
+///
+/// This is implemented for [`tabix::Index`] and [`csi::Index`]. A CSI index must have a
+/// tabix-style auxiliary header, which holds the reference sequence names.
+pub trait QueryIndex: BinningIndex {
+    /// Returns the position of the reference sequence with the given name in the index.
+    fn reference_sequence_id(&self, reference_sequence_name: &str) -> io::Result<Option<usize>>;
+}
+
+impl QueryIndex for tabix::Index {
+    fn reference_sequence_id(&self, reference_sequence_name: &str) -> io::Result<Option<usize>> {
+        Ok(self
+            .header()
+            .reference_sequence_names()
+            .get_index_of(reference_sequence_name))
+    }
+}
+
+impl QueryIndex for csi::Index {
+    fn reference_sequence_id(&self, reference_sequence_name: &str) -> io::Result<Option<usize>> {
+        let names = parse_aux_reference_sequence_names(self.aux())?;
+        Ok(names
+            .iter()
+            .position(|name| *name == reference_sequence_name))
+    }
+}
+
+// A tabix-style auxiliary header is 7 `int32_t` fields (format, col_seq, col_beg, col_end, meta,
+// skip, and l_nm) followed by `l_nm` bytes of NUL-terminated reference sequence names.
+fn parse_aux_reference_sequence_names(aux: &[u8]) -> io::Result<Vec<&str>> {
+    const HEADER_LEN: usize = 7 * 4;
+    const NUL: u8 = 0x00;
+
+    if aux.len() < HEADER_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "missing CSI auxiliary reference sequence names",
+        ));
+    }
+
+    let mut l_nm = [0; 4];
+    l_nm.copy_from_slice(&aux[HEADER_LEN - 4..HEADER_LEN]);
+
+    let l_nm = usize::try_from(i32::from_le_bytes(l_nm))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let buf = aux.get(HEADER_LEN..HEADER_LEN + l_nm).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid CSI auxiliary reference sequence names",
+        )
+    })?;
+
+    let buf = buf.strip_suffix(&[NUL]).unwrap_or(buf);
+
+    if buf.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    buf.split(|&b| b == NUL)
+        .map(|name| {
+            std::str::from_utf8(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+        .collect()
+}
+
+enum State {
+    Seek,
+    Read(bgzf::VirtualPosition),
+    Done,
+}
+
+/// An iterator over records of a BED reader that intersect a given region.
+///
+/// This is created by calling [`Reader::query`].
+pub struct Query<'r, R, const N: u8>
+where
+    R: Read + Seek,
+{
+    reader: &'r mut Reader<bgzf::Reader<R>>,
+
+    chunks: Vec<Chunk>,
+    i: usize,
+
+    reference_sequence_name: String,
+    interval: Interval,
+
+    state: State,
+    line_buf: String,
+}
+
+impl<'r, R, const N: u8> Query<'r, R, N>
+where
+    R: Read + Seek,
+    Record<N>: BedN<3> + FromStr<Err = record::ParseError>,
+{
+    pub(super) fn new(
+        reader: &'r mut Reader<bgzf::Reader<R>>,
+        chunks: Vec<Chunk>,
+        reference_sequence_name: String,
+        interval: Interval,
+    ) -> Self {
+        Self {
+            reader,
+
+            chunks,
+            i: 0,
+
+            reference_sequence_name,
+            interval,
+
+            state: State::Seek,
+            line_buf: String::new(),
+        }
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<Record<N>>> {
+        loop {
+            self.line_buf.clear();
+
+            if self.reader.read_line(&mut self.line_buf)? == 0 {
+                return Ok(None);
+            }
+
+            if is_header_line(&self.line_buf) {
+                continue;
+            }
+
+            return self
+                .line_buf
+                .parse()
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+        }
+    }
+}
+
+impl<'r, R, const N: u8> Iterator for Query<'r, R, N>
+where
+    R: Read + Seek,
+    Record<N>: BedN<3> + FromStr<Err = record::ParseError>,
+{
+    type Item = io::Result<Record<N>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.state {
+                State::Seek => {
+                    self.state = match self.chunks.get(self.i).copied() {
+                        Some(chunk) => {
+                            self.i += 1;
+
+                            if let Err(e) = self.reader.seek(chunk.start()) {
+                                return Some(Err(e));
+                            }
+
+                            State::Read(chunk.end())
+                        }
+                        None => State::Done,
+                    }
+                }
+                State::Read(chunk_end) => match self.read_record() {
+                    Ok(Some(record)) => {
+                        if self.reader.virtual_position() >= chunk_end {
+                            self.state = State::Seek;
+                        }
+
+                        if intersects(&record, &self.reference_sequence_name, self.interval) {
+                            return Some(Ok(record));
+                        }
+                    }
+                    Ok(None) => self.state = State::Seek,
+                    Err(e) => return Some(Err(e)),
+                },
+                State::Done => return None,
+            }
+        }
+    }
+}
+
+fn intersects<const N: u8>(
+    record: &Record<N>,
+    reference_sequence_name: &str,
+    region_interval: Interval,
+) -> bool
+where
+    Record<N>: BedN<3>,
+{
+    let record_interval = Interval::from(record.start_position()..=record.end_position());
+
+    record.reference_sequence_name() == reference_sequence_name
+        && record_interval.intersects(region_interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_aux_reference_sequence_names() -> io::Result<()> {
+        let mut aux = Vec::new();
+
+        for n in [0, 1, 2, 3, 0, 0] {
+            aux.extend(i32::to_le_bytes(n));
+        }
+
+        let names = b"sq0\x00sq1\x00";
+        aux.extend(i32::to_le_bytes(names.len() as i32));
+        aux.extend(names);
+
+        assert_eq!(parse_aux_reference_sequence_names(&aux)?, ["sq0", "sq1"]);
+
+        assert!(parse_aux_reference_sequence_names(&[]).is_err());
+
+        Ok(())
+    }
+}
@@ -1,12 +1,21 @@
 //! BED record and fields.
 
+pub mod bed_graph;
+pub mod broad_peak;
 pub mod builder;
 pub mod color;
+pub mod custom_fields;
 pub mod name;
+pub mod narrow_peak;
 pub mod score;
 pub mod strand;
+pub mod typed_record;
 
-pub use self::{builder::Builder, color::Color, name::Name, score::Score, strand::Strand};
+pub use self::{
+    bed_graph::BedGraph, broad_peak::BroadPeak, builder::Builder, color::Color,
+    custom_fields::CustomFields, name::Name, narrow_peak::NarrowPeak, score::Score, strand::Strand,
+    typed_record::TypedRecord,
+};
 
 use std::{
     error,
@@ -16,7 +25,7 @@ use std::{
     str::FromStr,
 };
 
-use noodles_core::Position;
+use noodles_core::{Position, Region};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 
@@ -498,6 +507,18 @@ where
     }
 }
 
+impl<const N: u8> From<&Record<N>> for Region
+where
+    Record<N>: BedN<3>,
+{
+    fn from(record: &Record<N>) -> Self {
+        Self::new(
+            record.reference_sequence_name(),
+            record.start_position()..=record.end_position(),
+        )
+    }
+}
+
 impl fmt::Display for Record<3> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         format_bed_3_fields(f, self)?;
@@ -1103,6 +1124,17 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_record_for_region() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record<3> = "sq0\t7\t13".parse()?;
+        let region = Region::from(&record);
+        assert_eq!(
+            region,
+            Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?)
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_fmt_for_record_3() -> Result<(), noodles_core::position::TryFromIntError> {
         let start = Position::try_from(8)?;
@@ -0,0 +1,175 @@
+//! bedGraph record.
+
+use std::{error, fmt, num, str::FromStr};
+
+use super::{
+    parse_bed_3_fields, BedN, OptionalFields, ParseError as RecordParseError, Record, DELIMITER,
+};
+
+/// A bedGraph record.
+///
+/// A bedGraph record is a BED3 record with a data value (`dataValue`) that applies to the entire
+/// interval.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BedGraph {
+    record: Record<3>,
+    value: f32,
+}
+
+impl BedN<3> for BedGraph {}
+
+impl BedGraph {
+    /// Creates a bedGraph record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::BedGraph};
+    /// use noodles_core::Position;
+    ///
+    /// let record = bed::Record::<3>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// let bed_graph = BedGraph::new(record, 0.5);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(record: Record<3>, value: f32) -> Self {
+        Self { record, value }
+    }
+
+    /// Returns the BED3 fields of the record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::BedGraph;
+    /// use noodles_core::Position;
+    ///
+    /// let record: BedGraph = "sq0\t7\t13\t0.5".parse()?;
+    ///
+    /// assert_eq!(record.record().reference_sequence_name(), "sq0");
+    /// assert_eq!(record.record().start_position(), Position::try_from(8)?);
+    /// assert_eq!(record.record().end_position(), Position::try_from(13)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn record(&self) -> &Record<3> {
+        &self.record
+    }
+
+    /// Returns the data value (`dataValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::BedGraph;
+    /// let record: BedGraph = "sq0\t7\t13\t0.5".parse()?;
+    /// assert_eq!(record.value(), 0.5);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+}
+
+impl fmt::Display for BedGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.record, DELIMITER, self.value)
+    }
+}
+
+/// An error returned when a raw bedGraph record fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The BED3 fields are invalid.
+    InvalidRecord(RecordParseError),
+    /// The data value is missing.
+    MissingValue,
+    /// The data value is invalid.
+    InvalidValue(num::ParseFloatError),
+    /// The record has more fields than expected.
+    UnexpectedField,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord(e) => write!(f, "invalid record: {}", e),
+            Self::MissingValue => f.write_str("missing value"),
+            Self::InvalidValue(e) => write!(f, "invalid value: {}", e),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+        }
+    }
+}
+
+impl FromStr for BedGraph {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split(DELIMITER);
+
+        let standard_fields = parse_bed_3_fields(&mut fields).map_err(ParseError::InvalidRecord)?;
+        let record = Record::new(standard_fields, OptionalFields::default());
+
+        let value = fields
+            .next()
+            .ok_or(ParseError::MissingValue)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidValue))?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        Ok(Self::new(record, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let record = Record::<3>::builder()
+            .set_reference_sequence_name("sq0")
+            .set_start_position(noodles_core::Position::try_from(8)?)
+            .set_end_position(noodles_core::Position::try_from(13)?)
+            .build()?;
+
+        let bed_graph = BedGraph::new(record.clone(), 0.5);
+        assert_eq!(bed_graph.to_string(), "sq0\t7\t13\t0.5");
+
+        let bed_graph = BedGraph::new(record, 8.0);
+        assert_eq!(bed_graph.to_string(), "sq0\t7\t13\t8");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() {
+        let record: BedGraph = "sq0\t7\t13\t-1.25".parse().unwrap();
+        assert_eq!(record.record().reference_sequence_name(), "sq0");
+        assert_eq!(record.value(), -1.25);
+
+        assert!(matches!(
+            "sq0\t7".parse::<BedGraph>(),
+            Err(ParseError::InvalidRecord(_))
+        ));
+        assert_eq!(
+            "sq0\t7\t13".parse::<BedGraph>(),
+            Err(ParseError::MissingValue)
+        );
+        assert!(matches!(
+            "sq0\t7\t13\tndls".parse::<BedGraph>(),
+            Err(ParseError::InvalidValue(_))
+        ));
+        assert_eq!(
+            "sq0\t7\t13\t0.5\t1".parse::<BedGraph>(),
+            Err(ParseError::UnexpectedField)
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! ENCODE broadPeak record.
+
+use std::{error, fmt, num, str::FromStr};
+
+use super::{
+    narrow_peak::{format_optional_value, parse_optional_value},
+    parse_bed_6_fields, BedN, OptionalFields, ParseError as RecordParseError, Record, DELIMITER,
+};
+
+/// An ENCODE broadPeak record.
+///
+/// A broadPeak record is a BED6+3 record of a broad region of enrichment. Unlike a narrowPeak
+/// record, it has no point source. The optional values are written as `-1` when missing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BroadPeak {
+    record: Record<6>,
+    signal_value: f32,
+    p_value: Option<f32>,
+    q_value: Option<f32>,
+}
+
+impl BedN<3> for BroadPeak {}
+impl BedN<4> for BroadPeak {}
+impl BedN<5> for BroadPeak {}
+impl BedN<6> for BroadPeak {}
+
+impl BroadPeak {
+    /// Creates a broadPeak record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::BroadPeak};
+    /// use noodles_core::Position;
+    ///
+    /// let record = bed::Record::<6>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// let broad_peak = BroadPeak::new(record, 5.5, Some(3.2), None);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(
+        record: Record<6>,
+        signal_value: f32,
+        p_value: Option<f32>,
+        q_value: Option<f32>,
+    ) -> Self {
+        Self {
+            record,
+            signal_value,
+            p_value,
+            q_value,
+        }
+    }
+
+    /// Returns the BED6 fields of the record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::{BroadPeak, Strand};
+    ///
+    /// let record: BroadPeak = "sq0\t7\t13\tpeak0\t500\t+\t5.5\t3.2\t-1".parse()?;
+    ///
+    /// assert_eq!(record.record().reference_sequence_name(), "sq0");
+    /// assert_eq!(record.record().strand(), Some(Strand::Forward));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn record(&self) -> &Record<6> {
+        &self.record
+    }
+
+    /// Returns the overall enrichment of the region (`signalValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::BroadPeak;
+    /// let record: BroadPeak = "sq0\t7\t13\tpeak0\t500\t+\t5.5\t3.2\t-1".parse()?;
+    /// assert_eq!(record.signal_value(), 5.5);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn signal_value(&self) -> f32 {
+        self.signal_value
+    }
+
+    /// Returns the statistical significance of the region as -log10(p) (`pValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::BroadPeak;
+    /// let record: BroadPeak = "sq0\t7\t13\tpeak0\t500\t+\t5.5\t3.2\t-1".parse()?;
+    /// assert_eq!(record.p_value(), Some(3.2));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn p_value(&self) -> Option<f32> {
+        self.p_value
+    }
+
+    /// Returns the statistical significance of the region as -log10(q) (`qValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::BroadPeak;
+    /// let record: BroadPeak = "sq0\t7\t13\tpeak0\t500\t+\t5.5\t3.2\t-1".parse()?;
+    /// assert!(record.q_value().is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn q_value(&self) -> Option<f32> {
+        self.q_value
+    }
+}
+
+impl fmt::Display for BroadPeak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.record, DELIMITER, self.signal_value)?;
+        format_optional_value(f, self.p_value)?;
+        format_optional_value(f, self.q_value)?;
+        Ok(())
+    }
+}
+
+/// An error returned when a raw broadPeak record fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The BED6 fields are invalid.
+    InvalidRecord(RecordParseError),
+    /// The signal value is missing.
+    MissingSignalValue,
+    /// The signal value is invalid.
+    InvalidSignalValue(num::ParseFloatError),
+    /// The p-value is missing.
+    MissingPValue,
+    /// The p-value is invalid.
+    InvalidPValue(num::ParseFloatError),
+    /// The q-value is missing.
+    MissingQValue,
+    /// The q-value is invalid.
+    InvalidQValue(num::ParseFloatError),
+    /// The record has more fields than expected.
+    UnexpectedField,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord(e) => write!(f, "invalid record: {}", e),
+            Self::MissingSignalValue => f.write_str("missing signal value"),
+            Self::InvalidSignalValue(e) => write!(f, "invalid signal value: {}", e),
+            Self::MissingPValue => f.write_str("missing p-value"),
+            Self::InvalidPValue(e) => write!(f, "invalid p-value: {}", e),
+            Self::MissingQValue => f.write_str("missing q-value"),
+            Self::InvalidQValue(e) => write!(f, "invalid q-value: {}", e),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+        }
+    }
+}
+
+impl FromStr for BroadPeak {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split(DELIMITER);
+
+        let standard_fields = parse_bed_6_fields(&mut fields).map_err(ParseError::InvalidRecord)?;
+        let record = Record::new(standard_fields, OptionalFields::default());
+
+        let signal_value = fields
+            .next()
+            .ok_or(ParseError::MissingSignalValue)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidSignalValue))?;
+
+        let p_value = fields
+            .next()
+            .ok_or(ParseError::MissingPValue)
+            .and_then(|s| parse_optional_value(s).map_err(ParseError::InvalidPValue))?;
+
+        let q_value = fields
+            .next()
+            .ok_or(ParseError::MissingQValue)
+            .and_then(|s| parse_optional_value(s).map_err(ParseError::InvalidQValue))?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        Ok(Self::new(record, signal_value, p_value, q_value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let record = Record::<6>::builder()
+            .set_reference_sequence_name("sq0")
+            .set_start_position(noodles_core::Position::try_from(8)?)
+            .set_end_position(noodles_core::Position::try_from(13)?)
+            .build()?;
+
+        let broad_peak = BroadPeak::new(record.clone(), 5.5, Some(3.2), Some(1.0));
+        assert_eq!(broad_peak.to_string(), "sq0\t7\t13\t.\t0\t.\t5.5\t3.2\t1");
+
+        let broad_peak = BroadPeak::new(record, 5.5, None, None);
+        assert_eq!(broad_peak.to_string(), "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t-1");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() {
+        let record: BroadPeak = "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t0.8".parse().unwrap();
+        assert_eq!(record.signal_value(), 5.5);
+        assert!(record.p_value().is_none());
+        assert_eq!(record.q_value(), Some(0.8));
+
+        let record: BroadPeak = "sq0\t7\t13\t.\t0\t.\t5.5\t-1.0\t-1.00000".parse().unwrap();
+        assert!(record.p_value().is_none());
+        assert!(record.q_value().is_none());
+
+        assert!(matches!(
+            "sq0\t7\t13\t.\t0".parse::<BroadPeak>(),
+            Err(ParseError::InvalidRecord(_))
+        ));
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.".parse::<BroadPeak>(),
+            Err(ParseError::MissingSignalValue)
+        );
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5".parse::<BroadPeak>(),
+            Err(ParseError::MissingPValue)
+        );
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1".parse::<BroadPeak>(),
+            Err(ParseError::MissingQValue)
+        );
+        assert!(matches!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1\tndls".parse::<BroadPeak>(),
+            Err(ParseError::InvalidQValue(_))
+        ));
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t-1\t-1".parse::<BroadPeak>(),
+            Err(ParseError::UnexpectedField)
+        );
+    }
+}
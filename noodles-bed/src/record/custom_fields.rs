@@ -0,0 +1,61 @@
+//! BED record custom fields.
+
+pub mod value;
+
+pub use self::value::Value;
+
+use std::{
+    fmt::{self, Write},
+    ops::Deref,
+};
+
+use super::DELIMITER;
+
+/// BED record custom fields.
+///
+/// These are the typed values of the fields that follow the standard fields, as described by an
+/// autoSql definition ([`crate::auto_sql::AutoSql`]).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CustomFields(Vec<Value>);
+
+impl Deref for CustomFields {
+    type Target = [Value];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for CustomFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, value) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_char(DELIMITER)?;
+            }
+
+            write!(f, "{}", value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Vec<Value>> for CustomFields {
+    fn from(values: Vec<Value>) -> Self {
+        Self(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let fields = CustomFields::default();
+        assert_eq!(fields.to_string(), "");
+
+        let fields = CustomFields::from(vec![Value::Integer(8), Value::Float(0.5)]);
+        assert_eq!(fields.to_string(), "8\t0.5");
+    }
+}
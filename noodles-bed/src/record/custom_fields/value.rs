@@ -0,0 +1,305 @@
+//! BED record custom field value.
+
+use std::{error, fmt, num};
+
+use crate::auto_sql::{
+    field::{Size, Type},
+    Field,
+};
+
+const DELIMITER: char = ',';
+
+/// A BED record custom field value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// An integer (`byte`, `ubyte`, `short`, `ushort`, `int`, `uint`, or `bigint`).
+    Integer(i64),
+    /// A floating-point (`float` or `double`).
+    Float(f64),
+    /// A string (`char`, `string`, `lstring`, or `enum`).
+    String(String),
+    /// An array of integers.
+    IntegerArray(Vec<i64>),
+    /// An array of floating-points.
+    FloatArray(Vec<f64>),
+    /// An array of strings (`string[n]`, `lstring[n]`, or `set`).
+    StringArray(Vec<String>),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{}", n),
+            Self::Float(n) => write!(f, "{}", n),
+            Self::String(s) => f.write_str(s),
+            Self::IntegerArray(values) => format_array(f, values),
+            Self::FloatArray(values) => format_array(f, values),
+            Self::StringArray(values) => format_array(f, values),
+        }
+    }
+}
+
+fn format_array<T>(f: &mut fmt::Formatter<'_>, values: &[T]) -> fmt::Result
+where
+    T: fmt::Display,
+{
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{}", DELIMITER)?;
+        }
+
+        write!(f, "{}", value)?;
+    }
+
+    Ok(())
+}
+
+/// An error returned when a raw BED record custom field value fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The integer is invalid.
+    InvalidInteger(num::ParseIntError),
+    /// The floating-point is invalid.
+    InvalidFloat(num::ParseFloatError),
+    /// The string is longer than the field allows.
+    InvalidLength(usize),
+    /// The value is not one of the enum or set values.
+    InvalidValue(String),
+    /// The number of array elements does not match the field size.
+    InvalidArrayLength {
+        /// The expected number of elements.
+        expected: usize,
+        /// The actual number of elements.
+        actual: usize,
+    },
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInteger(e) => write!(f, "invalid integer: {}", e),
+            Self::InvalidFloat(e) => write!(f, "invalid float: {}", e),
+            Self::InvalidLength(n) => write!(f, "invalid length: {}", n),
+            Self::InvalidValue(s) => write!(f, "invalid value: {}", s),
+            Self::InvalidArrayLength { expected, actual } => write!(
+                f,
+                "invalid array length: expected {}, got {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl Value {
+    /// Parses a raw custom field value with the given autoSql field definition.
+    ///
+    /// Array elements are comma-separated, and a trailing comma is allowed. The lengths of arrays
+    /// sized by another field (e.g., `int[blockCount]`) are not checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{
+    ///     auto_sql::{field::{Size, Type}, Field},
+    ///     record::custom_fields::Value,
+    /// };
+    ///
+    /// let field = Field::new(Type::Ushort, None, "dp", "Read depth");
+    /// assert_eq!(Value::from_str_field("8", &field), Ok(Value::Integer(8)));
+    /// assert!(Value::from_str_field("-8", &field).is_err());
+    ///
+    /// let field = Field::new(Type::Int, Some(Size::Fixed(2)), "ad", "Allelic depths");
+    /// assert_eq!(
+    ///     Value::from_str_field("5,3,", &field),
+    ///     Ok(Value::IntegerArray(vec![5, 3]))
+    /// );
+    /// ```
+    pub fn from_str_field(s: &str, field: &Field) -> Result<Self, ParseError> {
+        let value = match (field.ty(), field.size()) {
+            (Type::Char, None) => parse_string(s, Some(1))?,
+            (Type::Char, Some(Size::Fixed(n))) => parse_string(s, Some(*n))?,
+            (Type::Char, Some(Size::Field(_))) => parse_string(s, None)?,
+            (Type::String | Type::Lstring, None) => parse_string(s, None)?,
+            (Type::Enum(values), _) => parse_enum(s, values)?,
+            (Type::Set(values), _) => parse_set(s, values)?,
+            (Type::Float | Type::Double, None) => s
+                .parse()
+                .map(Value::Float)
+                .map_err(ParseError::InvalidFloat)?,
+            (Type::Float | Type::Double, Some(_)) => split_array(s)
+                .map(|t| t.parse().map_err(ParseError::InvalidFloat))
+                .collect::<Result<_, _>>()
+                .map(Value::FloatArray)?,
+            (Type::String | Type::Lstring, Some(_)) => {
+                Value::StringArray(split_array(s).map(String::from).collect())
+            }
+            (ty, None) => parse_integer(s, ty).map(Value::Integer)?,
+            (ty, Some(_)) => split_array(s)
+                .map(|t| parse_integer(t, ty))
+                .collect::<Result<_, _>>()
+                .map(Value::IntegerArray)?,
+        };
+
+        if let (Some(Size::Fixed(expected)), Some(actual)) = (field.size(), value.array_len()) {
+            if actual != *expected {
+                return Err(ParseError::InvalidArrayLength {
+                    expected: *expected,
+                    actual,
+                });
+            }
+        }
+
+        Ok(value)
+    }
+
+    pub(crate) fn array_len(&self) -> Option<usize> {
+        match self {
+            Self::IntegerArray(values) => Some(values.len()),
+            Self::FloatArray(values) => Some(values.len()),
+            Self::StringArray(values) => Some(values.len()),
+            _ => None,
+        }
+    }
+}
+
+fn split_array(s: &str) -> impl Iterator<Item = &str> {
+    let s = s.strip_suffix(DELIMITER).unwrap_or(s);
+    s.split(DELIMITER).filter(move |_| !s.is_empty())
+}
+
+fn parse_integer(s: &str, ty: &Type) -> Result<i64, ParseError> {
+    let result = match ty {
+        Type::Byte => s.parse::<i8>().map(i64::from),
+        Type::Ubyte => s.parse::<u8>().map(i64::from),
+        Type::Short => s.parse::<i16>().map(i64::from),
+        Type::Ushort => s.parse::<u16>().map(i64::from),
+        Type::Int => s.parse::<i32>().map(i64::from),
+        Type::Uint => s.parse::<u32>().map(i64::from),
+        _ => s.parse::<i64>(),
+    };
+
+    result.map_err(ParseError::InvalidInteger)
+}
+
+fn parse_string(s: &str, max_len: Option<usize>) -> Result<Value, ParseError> {
+    match max_len {
+        Some(n) if s.chars().count() > n => Err(ParseError::InvalidLength(s.chars().count())),
+        _ => Ok(Value::String(s.into())),
+    }
+}
+
+fn parse_enum_value(s: &str, values: &[String]) -> Result<String, ParseError> {
+    if values.iter().any(|v| v == s) {
+        Ok(s.into())
+    } else {
+        Err(ParseError::InvalidValue(s.into()))
+    }
+}
+
+fn parse_enum(s: &str, values: &[String]) -> Result<Value, ParseError> {
+    parse_enum_value(s, values).map(Value::String)
+}
+
+fn parse_set(s: &str, values: &[String]) -> Result<Value, ParseError> {
+    split_array(s)
+        .map(|t| parse_enum_value(t, values))
+        .collect::<Result<_, _>>()
+        .map(Value::StringArray)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Value::Integer(-8).to_string(), "-8");
+        assert_eq!(Value::Float(0.25).to_string(), "0.25");
+        assert_eq!(Value::String(String::from("ndls")).to_string(), "ndls");
+        assert_eq!(Value::IntegerArray(vec![5, 3]).to_string(), "5,3");
+        assert_eq!(Value::FloatArray(vec![0.5, 1.0]).to_string(), "0.5,1");
+        assert_eq!(
+            Value::StringArray(vec![String::from("a"), String::from("b")]).to_string(),
+            "a,b"
+        );
+        assert_eq!(Value::IntegerArray(Vec::new()).to_string(), "");
+    }
+
+    #[test]
+    fn test_from_str_field() {
+        fn t(ty: Type, size: Option<Size>, s: &str) -> Result<Value, ParseError> {
+            Value::from_str_field(s, &Field::new(ty, size, "f", ""))
+        }
+
+        assert_eq!(t(Type::Byte, None, "-128"), Ok(Value::Integer(-128)));
+        assert!(matches!(
+            t(Type::Ubyte, None, "256"),
+            Err(ParseError::InvalidInteger(_))
+        ));
+        assert_eq!(
+            t(Type::Uint, None, "4294967295"),
+            Ok(Value::Integer(4294967295))
+        );
+        assert_eq!(t(Type::Double, None, "0.25"), Ok(Value::Float(0.25)));
+        assert!(matches!(
+            t(Type::Float, None, "ndls"),
+            Err(ParseError::InvalidFloat(_))
+        ));
+
+        assert_eq!(
+            t(Type::Char, None, "+"),
+            Ok(Value::String(String::from("+")))
+        );
+        assert_eq!(
+            t(Type::Char, Some(Size::Fixed(2)), "ACG"),
+            Err(ParseError::InvalidLength(3))
+        );
+        assert_eq!(
+            t(Type::Lstring, None, "ndls"),
+            Ok(Value::String(String::from("ndls")))
+        );
+
+        let values = vec![String::from("snv"), String::from("indel")];
+        assert_eq!(
+            t(Type::Enum(values.clone()), None, "snv"),
+            Ok(Value::String(String::from("snv")))
+        );
+        assert_eq!(
+            t(Type::Enum(values.clone()), None, "mnv"),
+            Err(ParseError::InvalidValue(String::from("mnv")))
+        );
+        assert_eq!(
+            t(Type::Set(values.clone()), None, "snv,indel"),
+            Ok(Value::StringArray(values.clone()))
+        );
+        assert_eq!(
+            t(Type::Set(values), None, ""),
+            Ok(Value::StringArray(Vec::new()))
+        );
+
+        assert_eq!(
+            t(Type::Int, Some(Size::Fixed(2)), "5,3,"),
+            Ok(Value::IntegerArray(vec![5, 3]))
+        );
+        assert_eq!(
+            t(Type::Int, Some(Size::Fixed(2)), "5"),
+            Err(ParseError::InvalidArrayLength {
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            t(Type::Float, Some(Size::Field(String::from("n"))), "0.5,1"),
+            Ok(Value::FloatArray(vec![0.5, 1.0]))
+        );
+        assert_eq!(
+            t(Type::String, Some(Size::Fixed(2)), "a,b"),
+            Ok(Value::StringArray(vec![
+                String::from("a"),
+                String::from("b")
+            ]))
+        );
+    }
+}
@@ -0,0 +1,332 @@
+//! ENCODE narrowPeak record.
+
+use std::{error, fmt, num, str::FromStr};
+
+use super::{
+    parse_bed_6_fields, BedN, OptionalFields, ParseError as RecordParseError, Record, DELIMITER,
+};
+
+const MISSING_VALUE: &str = "-1";
+
+/// An ENCODE narrowPeak record.
+///
+/// A narrowPeak record is a BED6+4 record of a peak called at a point source. The optional
+/// values are written as `-1` when missing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NarrowPeak {
+    record: Record<6>,
+    signal_value: f32,
+    p_value: Option<f32>,
+    q_value: Option<f32>,
+    peak: Option<usize>,
+}
+
+impl BedN<3> for NarrowPeak {}
+impl BedN<4> for NarrowPeak {}
+impl BedN<5> for NarrowPeak {}
+impl BedN<6> for NarrowPeak {}
+
+impl NarrowPeak {
+    /// Creates a narrowPeak record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::NarrowPeak};
+    /// use noodles_core::Position;
+    ///
+    /// let record = bed::Record::<6>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// let narrow_peak = NarrowPeak::new(record, 5.5, Some(3.2), None, Some(2));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(
+        record: Record<6>,
+        signal_value: f32,
+        p_value: Option<f32>,
+        q_value: Option<f32>,
+        peak: Option<usize>,
+    ) -> Self {
+        Self {
+            record,
+            signal_value,
+            p_value,
+            q_value,
+            peak,
+        }
+    }
+
+    /// Returns the BED6 fields of the record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::{NarrowPeak, Strand};
+    ///
+    /// let record: NarrowPeak = "sq0\t7\t13\tpeak0\t500\t+\t5.5\t3.2\t-1\t2".parse()?;
+    ///
+    /// assert_eq!(record.record().reference_sequence_name(), "sq0");
+    /// assert_eq!(record.record().strand(), Some(Strand::Forward));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn record(&self) -> &Record<6> {
+        &self.record
+    }
+
+    /// Returns the overall enrichment of the region (`signalValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::NarrowPeak;
+    /// let record: NarrowPeak = "sq0\t7\t13\tpeak0\t500\t+\t5.5\t3.2\t-1\t2".parse()?;
+    /// assert_eq!(record.signal_value(), 5.5);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn signal_value(&self) -> f32 {
+        self.signal_value
+    }
+
+    /// Returns the statistical significance of the region as -log10(p) (`pValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::NarrowPeak;
+    /// let record: NarrowPeak = "sq0\t7\t13\tpeak0\t500\t+\t5.5\t3.2\t-1\t2".parse()?;
+    /// assert_eq!(record.p_value(), Some(3.2));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn p_value(&self) -> Option<f32> {
+        self.p_value
+    }
+
+    /// Returns the statistical significance of the region as -log10(q) (`qValue`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::NarrowPeak;
+    /// let record: NarrowPeak = "sq0\t7\t13\tpeak0\t500\t+\t5.5\t3.2\t-1\t2".parse()?;
+    /// assert!(record.q_value().is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn q_value(&self) -> Option<f32> {
+        self.q_value
+    }
+
+    /// Returns the 0-based offset of the point source from the start of the region (`peak`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::record::NarrowPeak;
+    /// let record: NarrowPeak = "sq0\t7\t13\tpeak0\t500\t+\t5.5\t3.2\t-1\t2".parse()?;
+    /// assert_eq!(record.peak(), Some(2));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn peak(&self) -> Option<usize> {
+        self.peak
+    }
+}
+
+impl fmt::Display for NarrowPeak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.record, DELIMITER, self.signal_value)?;
+        format_optional_value(f, self.p_value)?;
+        format_optional_value(f, self.q_value)?;
+        format_optional_value(f, self.peak)?;
+        Ok(())
+    }
+}
+
+pub(super) fn format_optional_value<T>(f: &mut fmt::Formatter<'_>, value: Option<T>) -> fmt::Result
+where
+    T: fmt::Display,
+{
+    match value {
+        Some(n) => write!(f, "{}{}", DELIMITER, n),
+        None => write!(f, "{}{}", DELIMITER, MISSING_VALUE),
+    }
+}
+
+/// An error returned when a raw narrowPeak record fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The BED6 fields are invalid.
+    InvalidRecord(RecordParseError),
+    /// The signal value is missing.
+    MissingSignalValue,
+    /// The signal value is invalid.
+    InvalidSignalValue(num::ParseFloatError),
+    /// The p-value is missing.
+    MissingPValue,
+    /// The p-value is invalid.
+    InvalidPValue(num::ParseFloatError),
+    /// The q-value is missing.
+    MissingQValue,
+    /// The q-value is invalid.
+    InvalidQValue(num::ParseFloatError),
+    /// The peak is missing.
+    MissingPeak,
+    /// The peak is invalid.
+    InvalidPeak(num::ParseIntError),
+    /// The record has more fields than expected.
+    UnexpectedField,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord(e) => write!(f, "invalid record: {}", e),
+            Self::MissingSignalValue => f.write_str("missing signal value"),
+            Self::InvalidSignalValue(e) => write!(f, "invalid signal value: {}", e),
+            Self::MissingPValue => f.write_str("missing p-value"),
+            Self::InvalidPValue(e) => write!(f, "invalid p-value: {}", e),
+            Self::MissingQValue => f.write_str("missing q-value"),
+            Self::InvalidQValue(e) => write!(f, "invalid q-value: {}", e),
+            Self::MissingPeak => f.write_str("missing peak"),
+            Self::InvalidPeak(e) => write!(f, "invalid peak: {}", e),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+        }
+    }
+}
+
+impl FromStr for NarrowPeak {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split(DELIMITER);
+
+        let standard_fields = parse_bed_6_fields(&mut fields).map_err(ParseError::InvalidRecord)?;
+        let record = Record::new(standard_fields, OptionalFields::default());
+
+        let signal_value = fields
+            .next()
+            .ok_or(ParseError::MissingSignalValue)
+            .and_then(|s| s.parse().map_err(ParseError::InvalidSignalValue))?;
+
+        let p_value = fields
+            .next()
+            .ok_or(ParseError::MissingPValue)
+            .and_then(|s| parse_optional_value(s).map_err(ParseError::InvalidPValue))?;
+
+        let q_value = fields
+            .next()
+            .ok_or(ParseError::MissingQValue)
+            .and_then(|s| parse_optional_value(s).map_err(ParseError::InvalidQValue))?;
+
+        let peak = fields
+            .next()
+            .ok_or(ParseError::MissingPeak)
+            .and_then(|s| parse_peak(s).map_err(ParseError::InvalidPeak))?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        Ok(Self::new(record, signal_value, p_value, q_value, peak))
+    }
+}
+
+// A missing value is any representation of -1, e.g., `-1`, `-1.0`, etc.
+pub(super) fn parse_optional_value(s: &str) -> Result<Option<f32>, num::ParseFloatError> {
+    s.parse()
+        .map(|n: f32| if n == -1.0 { None } else { Some(n) })
+}
+
+fn parse_peak(s: &str) -> Result<Option<usize>, num::ParseIntError> {
+    match s.parse::<i64>() {
+        Ok(-1) => Ok(None),
+        _ => s.parse().map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        let record = Record::<6>::builder()
+            .set_reference_sequence_name("sq0")
+            .set_start_position(noodles_core::Position::try_from(8)?)
+            .set_end_position(noodles_core::Position::try_from(13)?)
+            .build()?;
+
+        let narrow_peak = NarrowPeak::new(record.clone(), 5.5, Some(3.2), Some(1.0), Some(2));
+        assert_eq!(
+            narrow_peak.to_string(),
+            "sq0\t7\t13\t.\t0\t.\t5.5\t3.2\t1\t2"
+        );
+
+        let narrow_peak = NarrowPeak::new(record, 5.5, None, None, None);
+        assert_eq!(
+            narrow_peak.to_string(),
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t-1\t-1"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() {
+        let record: NarrowPeak = "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t0.8\t-1".parse().unwrap();
+        assert_eq!(record.signal_value(), 5.5);
+        assert!(record.p_value().is_none());
+        assert_eq!(record.q_value(), Some(0.8));
+        assert!(record.peak().is_none());
+
+        assert!(matches!(
+            "sq0\t7\t13\t.\t0".parse::<NarrowPeak>(),
+            Err(ParseError::InvalidRecord(_))
+        ));
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.".parse::<NarrowPeak>(),
+            Err(ParseError::MissingSignalValue)
+        );
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5".parse::<NarrowPeak>(),
+            Err(ParseError::MissingPValue)
+        );
+        assert!(matches!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\tndls\t-1\t-1".parse::<NarrowPeak>(),
+            Err(ParseError::InvalidPValue(_))
+        ));
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1".parse::<NarrowPeak>(),
+            Err(ParseError::MissingQValue)
+        );
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t-1".parse::<NarrowPeak>(),
+            Err(ParseError::MissingPeak)
+        );
+        assert!(matches!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t-1\t-2".parse::<NarrowPeak>(),
+            Err(ParseError::InvalidPeak(_))
+        ));
+
+        let record: NarrowPeak = "sq0\t7\t13\t.\t0\t.\t5.5\t-1.0\t-1.00000\t-1"
+            .parse()
+            .unwrap();
+        assert!(record.p_value().is_none());
+        assert!(record.q_value().is_none());
+        assert!(record.peak().is_none());
+
+        let record: NarrowPeak = "sq0\t7\t13\t.\t0\t.\t5.5\t-1.5\t0\t0".parse().unwrap();
+        assert_eq!(record.p_value(), Some(-1.5));
+        assert_eq!(record.q_value(), Some(0.0));
+        assert_eq!(record.peak(), Some(0));
+
+        assert_eq!(
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t-1\t-1\t.".parse::<NarrowPeak>(),
+            Err(ParseError::UnexpectedField)
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! BED record with typed custom fields.
+
+use std::{error, fmt, mem, str::FromStr};
+
+use super::{
+    custom_fields::{self, Value},
+    BedN, CustomFields, ParseError as RecordParseError, Record, DELIMITER,
+};
+use crate::auto_sql::{field::Size, AutoSql};
+
+/// A BED record with typed custom fields (BEDn+m).
+///
+/// The fields that follow the `N` standard fields are parsed and validated using an autoSql
+/// definition ([`AutoSql`]), where the first `N` field definitions describe the standard fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypedRecord<const N: u8> {
+    record: Record<N>,
+    custom_fields: CustomFields,
+}
+
+impl<const N: u8> TypedRecord<N>
+where
+    Record<N>: BedN<3>,
+{
+    /// Creates a BED record with typed custom fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{
+    ///     self as bed,
+    ///     record::{custom_fields::Value, CustomFields, TypedRecord},
+    /// };
+    /// use noodles_core::Position;
+    ///
+    /// let record = bed::Record::<3>::builder()
+    ///     .set_reference_sequence_name("sq0")
+    ///     .set_start_position(Position::try_from(8)?)
+    ///     .set_end_position(Position::try_from(13)?)
+    ///     .build()?;
+    ///
+    /// let custom_fields = CustomFields::from(vec![Value::Float(0.5)]);
+    /// let typed_record = TypedRecord::new(record, custom_fields);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(record: Record<N>, custom_fields: CustomFields) -> Self {
+        Self {
+            record,
+            custom_fields,
+        }
+    }
+
+    /// Converts a BED record to a record with typed custom fields.
+    ///
+    /// The raw optional fields of the given record are parsed as custom fields using the autoSql
+    /// definition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{
+    ///     self as bed,
+    ///     auto_sql::AutoSql,
+    ///     record::{custom_fields::Value, TypedRecord},
+    /// };
+    ///
+    /// let auto_sql: AutoSql = r#"table bed3plus1
+    /// "BED3+1"
+    /// (
+    ///     string chrom; "Reference sequence name"
+    ///     uint chromStart; "Start position"
+    ///     uint chromEnd; "End position"
+    ///     float af; "Allele frequency"
+    /// )"#.parse()?;
+    ///
+    /// let record: bed::Record<3> = "sq0\t7\t13\t0.5".parse()?;
+    /// let typed_record = TypedRecord::try_from_record(record, &auto_sql)?;
+    ///
+    /// assert!(typed_record.record().optional_fields().is_empty());
+    /// assert_eq!(&typed_record.custom_fields()[..], [Value::Float(0.5)]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_record(mut record: Record<N>, auto_sql: &AutoSql) -> Result<Self, ParseError> {
+        let definitions = auto_sql
+            .fields()
+            .get(usize::from(N)..)
+            .ok_or(ParseError::InvalidDefinition)?;
+
+        let optional_fields = mem::take(&mut record.optional_fields);
+        let mut raw_values = optional_fields.iter();
+        let mut values = Vec::with_capacity(definitions.len());
+
+        for field in definitions {
+            let value = raw_values
+                .next()
+                .ok_or_else(|| ParseError::MissingField(field.name().into()))
+                .and_then(|s| {
+                    Value::from_str_field(s, field)
+                        .map_err(|e| ParseError::InvalidField(field.name().into(), e))
+                })?;
+
+            values.push(value);
+        }
+
+        if raw_values.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        // Arrays sized by a standard field (e.g., `blockSizes`) are not checked here.
+        for (field, value) in definitions.iter().zip(&values) {
+            let size_field_name = match field.size() {
+                Some(Size::Field(name)) => name,
+                _ => continue,
+            };
+
+            let size = definitions
+                .iter()
+                .position(|f| f.name() == size_field_name)
+                .map(|i| &values[i]);
+
+            if let (Some(Value::Integer(n)), Some(actual)) = (size, value.array_len()) {
+                if i64::try_from(actual).ok() != Some(*n) {
+                    return Err(ParseError::InvalidField(
+                        field.name().into(),
+                        custom_fields::value::ParseError::InvalidArrayLength {
+                            expected: usize::try_from(*n).unwrap_or_default(),
+                            actual,
+                        },
+                    ));
+                }
+            }
+        }
+
+        Ok(Self::new(record, CustomFields::from(values)))
+    }
+
+    /// Parses a raw BED record with the given autoSql definition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{
+    ///     auto_sql::AutoSql,
+    ///     record::{custom_fields::Value, TypedRecord},
+    /// };
+    ///
+    /// let auto_sql: AutoSql = r#"table bed3plus2
+    /// "BED3+2"
+    /// (
+    ///     string chrom; "Reference sequence name"
+    ///     uint chromStart; "Start position"
+    ///     uint chromEnd; "End position"
+    ///     enum(snv, indel) kind; "Variant kind"
+    ///     int[2] ad; "Allelic depths"
+    /// )"#.parse()?;
+    ///
+    /// let record = TypedRecord::<3>::from_str_auto_sql("sq0\t7\t8\tsnv\t5,3", &auto_sql)?;
+    ///
+    /// assert_eq!(
+    ///     &record.custom_fields()[..],
+    ///     [
+    ///         Value::String(String::from("snv")),
+    ///         Value::IntegerArray(vec![5, 3]),
+    ///     ]
+    /// );
+    ///
+    /// assert!(TypedRecord::<3>::from_str_auto_sql("sq0\t7\t8\tmnv\t5,3", &auto_sql).is_err());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_str_auto_sql(s: &str, auto_sql: &AutoSql) -> Result<Self, ParseError>
+    where
+        Record<N>: FromStr<Err = RecordParseError>,
+    {
+        let record = s.parse().map_err(ParseError::InvalidRecord)?;
+        Self::try_from_record(record, auto_sql)
+    }
+
+    /// Returns the standard fields of the record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::{CustomFields, TypedRecord}};
+    ///
+    /// let record: bed::Record<3> = "sq0\t7\t13".parse()?;
+    /// let typed_record = TypedRecord::new(record.clone(), CustomFields::default());
+    ///
+    /// assert_eq!(typed_record.record(), &record);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn record(&self) -> &Record<N> {
+        &self.record
+    }
+
+    /// Returns the custom fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::{CustomFields, TypedRecord}};
+    ///
+    /// let record: bed::Record<3> = "sq0\t7\t13".parse()?;
+    /// let typed_record = TypedRecord::new(record, CustomFields::default());
+    ///
+    /// assert!(typed_record.custom_fields().is_empty());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn custom_fields(&self) -> &CustomFields {
+        &self.custom_fields
+    }
+}
+
+impl<const N: u8> fmt::Display for TypedRecord<N>
+where
+    Record<N>: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.record)?;
+
+        if !self.custom_fields.is_empty() {
+            write!(f, "{}{}", DELIMITER, self.custom_fields)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned when a raw BED record with typed custom fields fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The record is invalid.
+    InvalidRecord(RecordParseError),
+    /// The autoSql definition has fewer fields than the number of standard fields.
+    InvalidDefinition,
+    /// A custom field is missing.
+    MissingField(String),
+    /// A custom field is invalid.
+    InvalidField(String, custom_fields::value::ParseError),
+    /// The record has more fields than the autoSql definition.
+    UnexpectedField,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord(e) => write!(f, "invalid record: {}", e),
+            Self::InvalidDefinition => f.write_str("invalid definition"),
+            Self::MissingField(name) => write!(f, "missing field: {}", name),
+            Self::InvalidField(name, e) => write!(f, "invalid field: {}: {}", name, e),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTO_SQL: &str = r#"table bed6plus6
+"Variant annotations"
+(
+    string chrom; "Reference sequence name"
+    uint chromStart; "Start position"
+    uint chromEnd; "End position"
+    string name; "Variant ID"
+    uint score; "Score"
+    char[1] strand; "Strand"
+    enum(snv, mnv, indel) kind; "Variant kind"
+    float af; "Allele frequency"
+    ushort dp; "Read depth"
+    ubyte alleleCount; "Number of alleles"
+    int[alleleCount] ad; "Allelic depths"
+    set(coding, splice, utr) effects; "Predicted effects"
+)"#;
+
+    #[test]
+    fn test_from_str_auto_sql() -> Result<(), Box<dyn std::error::Error>> {
+        let auto_sql: AutoSql = AUTO_SQL.parse()?;
+
+        let s = "sq0\t7\t8\trs1\t0\t+\tsnv\t0.25\t21\t2\t16,5\tcoding,splice";
+        let record = TypedRecord::<6>::from_str_auto_sql(s, &auto_sql)?;
+
+        assert_eq!(
+            &record.custom_fields()[..],
+            [
+                Value::String(String::from("snv")),
+                Value::Float(0.25),
+                Value::Integer(21),
+                Value::Integer(2),
+                Value::IntegerArray(vec![16, 5]),
+                Value::StringArray(vec![String::from("coding"), String::from("splice")]),
+            ]
+        );
+
+        assert_eq!(record.to_string(), s);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str_auto_sql_with_invalid_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let auto_sql: AutoSql = AUTO_SQL.parse()?;
+
+        assert!(matches!(
+            TypedRecord::<6>::from_str_auto_sql("sq0\t7", &auto_sql),
+            Err(ParseError::InvalidRecord(_))
+        ));
+
+        let bed3_auto_sql: AutoSql = r#"table bed3 "BED3" (
+            string chrom; "Reference sequence name"
+            uint chromStart; "Start position"
+            uint chromEnd; "End position"
+        )"#
+        .parse()?;
+        assert_eq!(
+            TypedRecord::<6>::from_str_auto_sql("sq0\t7\t8\t.\t0\t.", &bed3_auto_sql),
+            Err(ParseError::InvalidDefinition)
+        );
+
+        assert_eq!(
+            TypedRecord::<6>::from_str_auto_sql("sq0\t7\t8\trs1\t0\t+\tsnv\t0.25", &auto_sql),
+            Err(ParseError::MissingField(String::from("dp")))
+        );
+
+        assert!(matches!(
+            TypedRecord::<6>::from_str_auto_sql(
+                "sq0\t7\t8\trs1\t0\t+\tsnv\t0.25\t-21\t2\t16,5\t",
+                &auto_sql
+            ),
+            Err(ParseError::InvalidField(name, _)) if name == "dp"
+        ));
+
+        assert_eq!(
+            TypedRecord::<6>::from_str_auto_sql(
+                "sq0\t7\t8\trs1\t0\t+\tsnv\t0.25\t21\t3\t16,5\t",
+                &auto_sql
+            ),
+            Err(ParseError::InvalidField(
+                String::from("ad"),
+                custom_fields::value::ParseError::InvalidArrayLength {
+                    expected: 3,
+                    actual: 2
+                }
+            ))
+        );
+
+        assert_eq!(
+            TypedRecord::<6>::from_str_auto_sql(
+                "sq0\t7\t8\trs1\t0\t+\tsnv\t0.25\t21\t2\t16,5\t\t.",
+                &auto_sql
+            ),
+            Err(ParseError::UnexpectedField)
+        );
+
+        Ok(())
+    }
+}
@@ -335,8 +335,10 @@ impl<'a> ser::SerializeStructVariant for &'a mut RecordSerializer {
 
 #[cfg(test)]
 mod serde_tests {
+    use noodles_core::Position;
+
     use crate::{
-        record::{Color, Name, Score, Strand},
+        record::{BedGraph, BroadPeak, Color, Name, NarrowPeak, Score, Strand},
         Record,
     };
 
@@ -507,4 +509,41 @@ mod serde_tests {
 
         assert_eq!(&result, expected);
     }
+
+    #[test]
+    fn test_to_string_peak_record_wrappers() -> std::result::Result<(), Box<dyn std::error::Error>>
+    {
+        let start = Position::try_from(8)?;
+        let end = Position::try_from(13)?;
+
+        let record = Record::<6>::builder()
+            .set_reference_sequence_name("sq0")
+            .set_start_position(start)
+            .set_end_position(end)
+            .build()?;
+
+        let bed_graph = BedGraph::new(
+            Record::<3>::builder()
+                .set_reference_sequence_name("sq0")
+                .set_start_position(start)
+                .set_end_position(end)
+                .build()?,
+            0.5,
+        );
+        assert_eq!(record_to_string(bed_graph)?, "sq0\t7\t13\t0.5");
+
+        let narrow_peak = NarrowPeak::new(record.clone(), 5.5, Some(3.2), None, Some(2));
+        assert_eq!(
+            record_to_string(narrow_peak)?,
+            "sq0\t7\t13\t.\t0\t.\t5.5\t3.2\t-1\t2"
+        );
+
+        let broad_peak = BroadPeak::new(record, 5.5, None, Some(0.8));
+        assert_eq!(
+            record_to_string(broad_peak)?,
+            "sq0\t7\t13\t.\t0\t.\t5.5\t-1\t0.8"
+        );
+
+        Ok(())
+    }
 }
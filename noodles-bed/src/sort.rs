@@ -0,0 +1,245 @@
+//! BED sort order validation and sorting.
+//!
+//! Indexing (e.g., [`crate::IndexedWriter`]) and interval set operations ([`crate::ops`]) require
+//! features to be sorted by reference sequence and then by start position.
+//!
+//! The reference sequence order is given by an [`ops::Genome`]. This can be read from a FASTA
+//! index or `chrom.sizes` file ([`ops::Genome::read`]) or collected from the reference sequences
+//! of a SAM header or the contigs of a VCF header.
+
+mod sorter;
+
+pub use self::sorter::{Builder, Sorter};
+
+use std::{error, fmt, io};
+
+use noodles_core::Position;
+
+use crate::ops::{self, Feature};
+
+/// A sort order violation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Violation {
+    /// A feature is on a reference sequence that is not in the genome.
+    MissingReferenceSequence {
+        /// The 1-based record number.
+        record_number: usize,
+        /// The reference sequence name.
+        reference_sequence_name: String,
+    },
+    /// A feature is on a reference sequence that precedes the reference sequence of the previous
+    /// feature.
+    UnsortedReferenceSequence {
+        /// The 1-based record number.
+        record_number: usize,
+        /// The reference sequence name of the previous feature.
+        previous: String,
+        /// The reference sequence name.
+        reference_sequence_name: String,
+    },
+    /// A feature starts before the previous feature on the same reference sequence.
+    UnsortedPosition {
+        /// The 1-based record number.
+        record_number: usize,
+        /// The reference sequence name.
+        reference_sequence_name: String,
+        /// The start position of the previous feature.
+        previous: Position,
+        /// The start position.
+        start_position: Position,
+    },
+}
+
+impl Violation {
+    /// Returns the 1-based record number of the feature that violates the sort order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::{self as bed, ops::Genome, sort};
+    ///
+    /// let genome: Genome = [("sq0", 55)].into_iter().collect();
+    ///
+    /// let data = b"sq0\t8\t13\nsq0\t5\t21\n";
+    /// let mut reader = bed::Reader::new(&data[..]);
+    ///
+    /// let violation = sort::check_sorted(reader.records::<3>(), &genome)?;
+    /// assert_eq!(violation.map(|v| v.record_number()), Some(2));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn record_number(&self) -> usize {
+        match self {
+            Self::MissingReferenceSequence { record_number, .. } => *record_number,
+            Self::UnsortedReferenceSequence { record_number, .. } => *record_number,
+            Self::UnsortedPosition { record_number, .. } => *record_number,
+        }
+    }
+}
+
+impl error::Error for Violation {}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingReferenceSequence {
+                record_number,
+                reference_sequence_name,
+            } => write!(
+                f,
+                "record {}: reference sequence not in genome: {}",
+                record_number, reference_sequence_name
+            ),
+            Self::UnsortedReferenceSequence {
+                record_number,
+                previous,
+                reference_sequence_name,
+            } => write!(
+                f,
+                "record {}: reference sequence {} is before {}",
+                record_number, reference_sequence_name, previous
+            ),
+            Self::UnsortedPosition {
+                record_number,
+                reference_sequence_name,
+                previous,
+                start_position,
+            } => write!(
+                f,
+                "record {}: {}:{} is before {}:{}",
+                record_number,
+                reference_sequence_name,
+                start_position,
+                reference_sequence_name,
+                previous
+            ),
+        }
+    }
+}
+
+/// Checks that features are sorted by the reference sequence order of the genome and then by
+/// start position.
+///
+/// This returns the first violation of the sort order, if any.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bed::{self as bed, ops::Genome, sort};
+///
+/// let genome: Genome = [("sq0", 55), ("sq1", 34)].into_iter().collect();
+///
+/// let data = b"sq0\t8\t13\nsq1\t5\t21\n";
+/// let mut reader = bed::Reader::new(&data[..]);
+/// assert!(sort::check_sorted(reader.records::<3>(), &genome)?.is_none());
+///
+/// let data = b"sq1\t5\t21\nsq0\t8\t13\n";
+/// let mut reader = bed::Reader::new(&data[..]);
+/// assert!(sort::check_sorted(reader.records::<3>(), &genome)?.is_some());
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn check_sorted<I, F>(features: I, genome: &ops::Genome) -> io::Result<Option<Violation>>
+where
+    I: IntoIterator<Item = io::Result<F>>,
+    F: Feature,
+{
+    // The reference sequence ID, name, and start position of the previous feature.
+    let mut previous: Option<(usize, String, Position)> = None;
+
+    for (i, result) in features.into_iter().enumerate() {
+        let feature = result?;
+
+        let record_number = i + 1;
+        let reference_sequence_name = feature.reference_sequence_name();
+        let start_position = feature.start_position();
+
+        let reference_sequence_id = match genome.get_index_of(reference_sequence_name) {
+            Some(id) => id,
+            None => {
+                return Ok(Some(Violation::MissingReferenceSequence {
+                    record_number,
+                    reference_sequence_name: reference_sequence_name.into(),
+                }))
+            }
+        };
+
+        if let Some((previous_id, previous_name, previous_start_position)) = &mut previous {
+            if reference_sequence_id < *previous_id {
+                return Ok(Some(Violation::UnsortedReferenceSequence {
+                    record_number,
+                    previous: previous_name.clone(),
+                    reference_sequence_name: reference_sequence_name.into(),
+                }));
+            } else if reference_sequence_id == *previous_id {
+                if start_position < *previous_start_position {
+                    return Ok(Some(Violation::UnsortedPosition {
+                        record_number,
+                        reference_sequence_name: reference_sequence_name.into(),
+                        previous: *previous_start_position,
+                        start_position,
+                    }));
+                }
+
+                *previous_start_position = start_position;
+                continue;
+            }
+        }
+
+        previous = Some((
+            reference_sequence_id,
+            reference_sequence_name.into(),
+            start_position,
+        ));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Reader;
+
+    #[test]
+    fn test_check_sorted() -> Result<(), Box<dyn std::error::Error>> {
+        let genome: ops::Genome = [("sq0", 55), ("sq1", 34)].into_iter().collect();
+
+        let t = |data: &[u8]| {
+            let mut reader = Reader::new(data);
+            check_sorted(reader.records::<3>(), &genome)
+        };
+
+        assert!(t(b"")?.is_none());
+        assert!(t(b"sq0\t8\t13\nsq0\t8\t9\nsq0\t21\t34\nsq1\t0\t5\n")?.is_none());
+
+        assert_eq!(
+            t(b"sq0\t8\t13\nsq2\t0\t5\n")?,
+            Some(Violation::MissingReferenceSequence {
+                record_number: 2,
+                reference_sequence_name: String::from("sq2"),
+            })
+        );
+
+        assert_eq!(
+            t(b"sq0\t8\t13\nsq1\t0\t5\nsq0\t21\t34\n")?,
+            Some(Violation::UnsortedReferenceSequence {
+                record_number: 3,
+                previous: String::from("sq1"),
+                reference_sequence_name: String::from("sq0"),
+            })
+        );
+
+        assert_eq!(
+            t(b"sq0\t8\t13\nsq0\t21\t34\nsq0\t13\t21\n")?,
+            Some(Violation::UnsortedPosition {
+                record_number: 3,
+                reference_sequence_name: String::from("sq0"),
+                previous: Position::try_from(22)?,
+                start_position: Position::try_from(14)?,
+            })
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,494 @@
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write},
+    mem,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{self, AtomicUsize},
+};
+
+use crate::{line::is_header_line, ops::Genome, reader::read_line};
+
+const LINE_FEED: u8 = b'\n';
+
+// The maximum number of temporary files that are merged at once.
+const MAX_MERGE_WIDTH: usize = 64;
+
+/// A BED sorter.
+///
+/// This sorts BED lines by reference sequence, start position, and end position using an
+/// external merge sort. Lines are buffered in memory up to a maximum size, after which they are
+/// sorted and written to a temporary file. The sorted temporary files are then merged, at most
+/// 64 at a time.
+///
+/// Reference sequences are sorted by the order in the genome, if set, or lexicographically by
+/// name. Header lines (comment, track, and browser lines) are written first, in their original
+/// order. Only the first three fields of each record are parsed, so any number of fields is kept.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bed::sort::Sorter;
+///
+/// let data = b"track name=peaks\nsq1\t0\t5\nsq0\t21\t34\nsq0\t8\t13\tp0\n";
+///
+/// let sorter = Sorter::default();
+/// let mut buf = Vec::new();
+/// sorter.sort(&data[..], &mut buf)?;
+///
+/// assert_eq!(buf, b"track name=peaks\nsq0\t8\t13\tp0\nsq0\t21\t34\nsq1\t0\t5\n");
+/// # Ok::<_, io::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Sorter {
+    genome: Option<Genome>,
+    max_buffer_size: usize,
+    temp_dir: PathBuf,
+}
+
+impl Sorter {
+    /// Creates a BED sorter builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::sort::Sorter;
+    /// let builder = Sorter::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Sorts BED lines from the reader and writes them to the writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed::{ops::Genome, sort::Sorter};
+    ///
+    /// let genome: Genome = [("sq1", 34), ("sq0", 55)].into_iter().collect();
+    /// let sorter = Sorter::builder().set_genome(genome).build();
+    ///
+    /// let data = b"sq0\t8\t13\nsq1\t0\t5\n";
+    /// let mut buf = Vec::new();
+    /// sorter.sort(&data[..], &mut buf)?;
+    ///
+    /// assert_eq!(buf, b"sq1\t0\t5\nsq0\t8\t13\n");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn sort<R, W>(&self, mut reader: R, writer: W) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        let mut writer = BufWriter::new(writer);
+
+        let mut entries = Vec::new();
+        let mut buffer_size = 0;
+        let mut chunks = Vec::new();
+
+        let mut buf = String::new();
+
+        loop {
+            buf.clear();
+
+            if read_line(&mut reader, &mut buf)? == 0 {
+                break;
+            }
+
+            if buf.is_empty() {
+                continue;
+            } else if is_header_line(&buf) {
+                // Records are only written after all input is read, so header lines are written
+                // as they are read.
+                write_line(&mut writer, &buf)?;
+                continue;
+            }
+
+            buffer_size += buf.len();
+            entries.push(Entry::parse(mem::take(&mut buf), self.genome.as_ref())?);
+
+            if buffer_size >= self.max_buffer_size {
+                chunks.push(self.write_chunk(&mut entries)?);
+                buffer_size = 0;
+            }
+        }
+
+        if chunks.is_empty() {
+            sort_entries(&mut entries);
+
+            for entry in &entries {
+                write_line(&mut writer, &entry.line)?;
+            }
+        } else {
+            if !entries.is_empty() {
+                chunks.push(self.write_chunk(&mut entries)?);
+            }
+
+            // Chunks are merged in consecutive groups, which keeps the sort stable.
+            while chunks.len() > MAX_MERGE_WIDTH {
+                chunks = chunks
+                    .chunks(MAX_MERGE_WIDTH)
+                    .map(|group| self.merge_chunks_into_chunk(group))
+                    .collect::<io::Result<_>>()?;
+            }
+
+            self.merge_chunks(&chunks, &mut writer)?;
+        }
+
+        writer.flush()
+    }
+
+    fn write_chunk(&self, entries: &mut Vec<Entry>) -> io::Result<TempFile> {
+        sort_entries(entries);
+
+        let mut temp_file = TempFile::new(&self.temp_dir)?;
+
+        {
+            let mut writer = BufWriter::new(&temp_file.file);
+
+            for entry in entries.drain(..) {
+                write_line(&mut writer, &entry.line)?;
+            }
+
+            writer.flush()?;
+        }
+
+        temp_file.file.seek(SeekFrom::Start(0))?;
+
+        Ok(temp_file)
+    }
+
+    fn merge_chunks_into_chunk(&self, chunks: &[TempFile]) -> io::Result<TempFile> {
+        let mut temp_file = TempFile::new(&self.temp_dir)?;
+
+        {
+            let mut writer = BufWriter::new(&temp_file.file);
+            self.merge_chunks(chunks, &mut writer)?;
+            writer.flush()?;
+        }
+
+        temp_file.file.seek(SeekFrom::Start(0))?;
+
+        Ok(temp_file)
+    }
+
+    fn merge_chunks<W>(&self, chunks: &[TempFile], writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let genome = self.genome.as_ref();
+
+        let mut readers: Vec<_> = chunks
+            .iter()
+            .map(|chunk| BufReader::new(&chunk.file))
+            .collect();
+
+        let mut heap = BinaryHeap::with_capacity(readers.len());
+
+        for (i, reader) in readers.iter_mut().enumerate() {
+            if let Some(entry) = read_entry(reader, genome)? {
+                heap.push(HeapEntry { entry, chunk: i });
+            }
+        }
+
+        while let Some(HeapEntry { entry, chunk }) = heap.pop() {
+            write_line(writer, &entry.line)?;
+
+            if let Some(entry) = read_entry(&mut readers[chunk], genome)? {
+                heap.push(HeapEntry { entry, chunk });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Sorter {
+    fn default() -> Self {
+        Builder::default().build()
+    }
+}
+
+// A BED line with its sort key.
+struct Entry {
+    reference_sequence_id: usize,
+    reference_sequence_name_len: usize,
+    start: usize,
+    end: usize,
+    line: String,
+}
+
+impl Entry {
+    fn parse(line: String, genome: Option<&Genome>) -> io::Result<Self> {
+        const DELIMITER: char = '\t';
+
+        let mut fields = line.split(DELIMITER);
+
+        let (reference_sequence_name, raw_start, raw_end) =
+            match (fields.next(), fields.next(), fields.next()) {
+                (Some(name), Some(raw_start), Some(raw_end)) => (name, raw_start, raw_end),
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid record: {}", line),
+                    ))
+                }
+            };
+
+        let reference_sequence_id = match genome {
+            Some(genome) => genome
+                .get_index_of(reference_sequence_name)
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "reference sequence not in genome: {}",
+                            reference_sequence_name
+                        ),
+                    )
+                })?,
+            None => 0,
+        };
+
+        let start = raw_start
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let end = raw_end
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(Self {
+            reference_sequence_id,
+            reference_sequence_name_len: reference_sequence_name.len(),
+            start,
+            end,
+            line,
+        })
+    }
+
+    // Without a genome, all reference sequence IDs are 0, and the names are compared instead.
+    fn key(&self) -> (usize, &str, usize, usize) {
+        (
+            self.reference_sequence_id,
+            &self.line[..self.reference_sequence_name_len],
+            self.start,
+            self.end,
+        )
+    }
+}
+
+// An entry in the merge heap. Entries are ordered in reverse, so the heap is a min-heap, and ties
+// are broken by chunk to keep the sort stable.
+struct HeapEntry {
+    entry: Entry,
+    chunk: usize,
+}
+
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HeapEntry {}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.entry.key(), other.chunk).cmp(&(self.entry.key(), self.chunk))
+    }
+}
+
+// A temporary file that is removed when dropped.
+struct TempFile {
+    path: PathBuf,
+    file: File,
+}
+
+impl TempFile {
+    fn new(dir: &Path) -> io::Result<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        loop {
+            let n = COUNTER.fetch_add(1, atomic::Ordering::Relaxed);
+            let path = dir.join(format!(".noodles-bed-sort-{}-{}.tmp", process::id(), n));
+
+            match OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(file) => return Ok(Self { path, file }),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn sort_entries(entries: &mut [Entry]) {
+    entries.sort_by(|a, b| a.key().cmp(&b.key()));
+}
+
+fn read_entry<R>(reader: &mut R, genome: Option<&Genome>) -> io::Result<Option<Entry>>
+where
+    R: BufRead,
+{
+    let mut buf = String::new();
+
+    match read_line(reader, &mut buf)? {
+        0 => Ok(None),
+        _ => Entry::parse(buf, genome).map(Some),
+    }
+}
+
+fn write_line<W>(writer: &mut W, line: &str) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(line.as_bytes())?;
+    writer.write_all(&[LINE_FEED])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &[u8] = b"\
+# comment
+sq1\t0\t5
+sq0\t21\t34\tp0
+track name=peaks
+sq0\t8\t13
+sq2\t3\t8
+
+sq0\t8\t10
+sq0\t21\t34\tp1
+";
+
+    #[test]
+    fn test_sort() -> io::Result<()> {
+        let mut buf = Vec::new();
+        Sorter::default().sort(DATA, &mut buf)?;
+
+        let expected = b"\
+# comment
+track name=peaks
+sq0\t8\t10
+sq0\t8\t13
+sq0\t21\t34\tp0
+sq0\t21\t34\tp1
+sq1\t0\t5
+sq2\t3\t8
+";
+
+        assert_eq!(buf, &expected[..]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_with_chunks() -> io::Result<()> {
+        let mut expected = Vec::new();
+        Sorter::default().sort(DATA, &mut expected)?;
+
+        for max_buffer_size in [1, 16, 32] {
+            let sorter = Sorter::builder()
+                .set_max_buffer_size(max_buffer_size)
+                .build();
+
+            let mut buf = Vec::new();
+            sorter.sort(DATA, &mut buf)?;
+
+            assert_eq!(buf, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_with_multiple_merge_passes() -> io::Result<()> {
+        let mut data = Vec::new();
+
+        for i in 0..(MAX_MERGE_WIDTH * 3 + 1) {
+            if i % 50 == 0 {
+                writeln!(data, "# {}", i)?;
+            }
+
+            writeln!(data, "sq{}\t{}\t{}\tr{}", i % 3, (i * 7) % 100, 100, i)?;
+        }
+
+        let mut expected = Vec::new();
+        Sorter::default().sort(&data[..], &mut expected)?;
+        assert!(expected.starts_with(b"# 0\n# 50\n# 100\n# 150\nsq0\t0\t100\tr0\n"));
+
+        let sorter = Sorter::builder().set_max_buffer_size(1).build();
+        let mut buf = Vec::new();
+        sorter.sort(&data[..], &mut buf)?;
+
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_with_genome() -> io::Result<()> {
+        let genome: Genome = [("sq2", 8), ("sq0", 55), ("sq1", 34)].into_iter().collect();
+
+        let sorter = Sorter::builder()
+            .set_genome(genome)
+            .set_max_buffer_size(16)
+            .build();
+
+        let mut buf = Vec::new();
+        sorter.sort(DATA, &mut buf)?;
+
+        let expected = b"\
+# comment
+track name=peaks
+sq2\t3\t8
+sq0\t8\t10
+sq0\t8\t13
+sq0\t21\t34\tp0
+sq0\t21\t34\tp1
+sq1\t0\t5
+";
+
+        assert_eq!(buf, &expected[..]);
+
+        let genome: Genome = [("sq0", 55)].into_iter().collect();
+        let sorter = Sorter::builder().set_genome(genome).build();
+        assert!(sorter.sort(DATA, io::sink()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_with_invalid_record() {
+        let sorter = Sorter::default();
+        assert!(sorter.sort(&b"sq0\t8\n"[..], io::sink()).is_err());
+        assert!(sorter.sort(&b"sq0\t8\tndls\n"[..], io::sink()).is_err());
+    }
+}
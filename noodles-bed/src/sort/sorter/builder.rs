@@ -0,0 +1,84 @@
+use std::{env, path::PathBuf};
+
+use super::Sorter;
+use crate::ops::Genome;
+
+// 64 MiB
+const DEFAULT_MAX_BUFFER_SIZE: usize = 1 << 26;
+
+/// A BED sorter builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    genome: Option<Genome>,
+    max_buffer_size: Option<usize>,
+    temp_dir: Option<PathBuf>,
+}
+
+impl Builder {
+    /// Sets the genome used for the reference sequence order.
+    ///
+    /// By default, reference sequences are sorted lexicographically by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{ops::Genome, sort::Sorter};
+    ///
+    /// let genome: Genome = [("sq0", 8)].into_iter().collect();
+    /// let sorter = Sorter::builder().set_genome(genome).build();
+    /// ```
+    pub fn set_genome(mut self, genome: Genome) -> Self {
+        self.genome = Some(genome);
+        self
+    }
+
+    /// Sets the maximum number of bytes of lines to buffer in memory before writing a sorted
+    /// chunk to a temporary file.
+    ///
+    /// The default is 64 MiB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::sort::Sorter;
+    /// let sorter = Sorter::builder().set_max_buffer_size(1 << 20).build();
+    /// ```
+    pub fn set_max_buffer_size(mut self, max_buffer_size: usize) -> Self {
+        self.max_buffer_size = Some(max_buffer_size);
+        self
+    }
+
+    /// Sets the directory where temporary files are written.
+    ///
+    /// The default is the system temporary directory ([`std::env::temp_dir`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::sort::Sorter;
+    /// let sorter = Sorter::builder().set_temp_dir("tmp").build();
+    /// ```
+    pub fn set_temp_dir<P>(mut self, temp_dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.temp_dir = Some(temp_dir.into());
+        self
+    }
+
+    /// Builds a BED sorter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::sort::Sorter;
+    /// let sorter = Sorter::builder().build();
+    /// ```
+    pub fn build(self) -> Sorter {
+        Sorter {
+            genome: self.genome,
+            max_buffer_size: self.max_buffer_size.unwrap_or(DEFAULT_MAX_BUFFER_SIZE),
+            temp_dir: self.temp_dir.unwrap_or_else(env::temp_dir),
+        }
+    }
+}
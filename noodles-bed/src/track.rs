@@ -0,0 +1,306 @@
+//! BED track definition line.
+
+use std::{error, fmt, str::FromStr};
+
+pub(crate) const PREFIX: &str = "track";
+
+const NAME: &str = "name";
+const DESCRIPTION: &str = "description";
+const VISIBILITY: &str = "visibility";
+const ITEM_RGB: &str = "itemRgb";
+const USE_SCORE: &str = "useScore";
+
+/// A BED track definition line.
+///
+/// A track line (e.g., `track name=peaks description="ChIP-seq peaks" useScore=1`) sets display
+/// attributes of the records that follow it. Attributes are kept in the order they are given.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Track {
+    attributes: Vec<(String, String)>,
+}
+
+impl Track {
+    /// Returns the list of attributes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let track: bed::Track = "track name=peaks useScore=1".parse()?;
+    /// assert_eq!(
+    ///     track.attributes(),
+    ///     [
+    ///         (String::from("name"), String::from("peaks")),
+    ///         (String::from("useScore"), String::from("1")),
+    ///     ]
+    /// );
+    /// # Ok::<_, bed::track::ParseError>(())
+    /// ```
+    pub fn attributes(&self) -> &[(String, String)] {
+        &self.attributes
+    }
+
+    /// Returns the value of the attribute with the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let track: bed::Track = "track name=peaks color=255,0,0".parse()?;
+    /// assert_eq!(track.get("color"), Some("255,0,0"));
+    /// assert!(track.get("priority").is_none());
+    /// # Ok::<_, bed::track::ParseError>(())
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Inserts an attribute.
+    ///
+    /// If the key already exists, its value is replaced in place and the old value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    ///
+    /// let mut track = bed::Track::default();
+    /// assert!(track.insert("name", "peaks").is_none());
+    /// assert_eq!(track.insert("name", "reads"), Some(String::from("peaks")));
+    ///
+    /// assert_eq!(track.name(), Some("reads"));
+    /// ```
+    pub fn insert<K, V>(&mut self, key: K, value: V) -> Option<String>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let key = key.into();
+        let value = value.into();
+
+        match self.attributes.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => Some(std::mem::replace(v, value)),
+            None => {
+                self.attributes.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Returns the track name (`name`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let track: bed::Track = "track name=peaks".parse()?;
+    /// assert_eq!(track.name(), Some("peaks"));
+    /// # Ok::<_, bed::track::ParseError>(())
+    /// ```
+    pub fn name(&self) -> Option<&str> {
+        self.get(NAME)
+    }
+
+    /// Returns the track description (`description`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let track: bed::Track = r#"track description="ChIP-seq peaks""#.parse()?;
+    /// assert_eq!(track.description(), Some("ChIP-seq peaks"));
+    /// # Ok::<_, bed::track::ParseError>(())
+    /// ```
+    pub fn description(&self) -> Option<&str> {
+        self.get(DESCRIPTION)
+    }
+
+    /// Returns the display mode (`visibility`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let track: bed::Track = "track visibility=dense".parse()?;
+    /// assert_eq!(track.visibility(), Some("dense"));
+    /// # Ok::<_, bed::track::ParseError>(())
+    /// ```
+    pub fn visibility(&self) -> Option<&str> {
+        self.get(VISIBILITY)
+    }
+
+    /// Returns whether records are colored by their item RGB field (`itemRgb=On`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let track: bed::Track = r#"track itemRgb="On""#.parse()?;
+    /// assert!(track.item_rgb());
+    /// # Ok::<_, bed::track::ParseError>(())
+    /// ```
+    pub fn item_rgb(&self) -> bool {
+        self.get(ITEM_RGB)
+            .map(|value| value.eq_ignore_ascii_case("on"))
+            .unwrap_or(false)
+    }
+
+    /// Returns whether records are shaded by their score (`useScore=1`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed as bed;
+    /// let track: bed::Track = "track useScore=1".parse()?;
+    /// assert!(track.use_score());
+    /// # Ok::<_, bed::track::ParseError>(())
+    /// ```
+    pub fn use_score(&self) -> bool {
+        self.get(USE_SCORE)
+            .map(|value| value == "1")
+            .unwrap_or(false)
+    }
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(PREFIX)?;
+
+        for (key, value) in &self.attributes {
+            if value.is_empty() || value.contains(char::is_whitespace) {
+                write!(f, " {}=\"{}\"", key, value)?;
+            } else {
+                write!(f, " {}={}", key, value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned when a raw BED track line fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The `track` prefix is missing.
+    MissingPrefix,
+    /// An attribute is missing a value.
+    MissingValue(String),
+    /// A quoted value is missing its closing quote.
+    UnterminatedValue(String),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPrefix => f.write_str("missing prefix"),
+            Self::MissingValue(key) => write!(f, "missing value: {}", key),
+            Self::UnterminatedValue(key) => write!(f, "unterminated value: {}", key),
+        }
+    }
+}
+
+impl FromStr for Track {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut s = match s.strip_prefix(PREFIX) {
+            Some(t) if t.is_empty() || t.starts_with(char::is_whitespace) => t,
+            _ => return Err(ParseError::MissingPrefix),
+        };
+
+        let mut track = Self::default();
+
+        loop {
+            s = s.trim_start();
+
+            if s.is_empty() {
+                break;
+            }
+
+            let key_end = s
+                .find(|c: char| c == '=' || c.is_whitespace())
+                .unwrap_or(s.len());
+
+            let key = &s[..key_end];
+
+            s = match s[key_end..].strip_prefix('=') {
+                Some(t) => t,
+                None => return Err(ParseError::MissingValue(key.into())),
+            };
+
+            let value = match s.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let t = &s[1..];
+                    let i = t
+                        .find(quote)
+                        .ok_or_else(|| ParseError::UnterminatedValue(key.into()))?;
+                    s = &t[i + 1..];
+                    &t[..i]
+                }
+                _ => {
+                    let i = s.find(char::is_whitespace).unwrap_or(s.len());
+                    let value = &s[..i];
+                    s = &s[i..];
+                    value
+                }
+            };
+
+            track.insert(key, value);
+        }
+
+        Ok(track)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        let mut track = Track::default();
+        assert_eq!(track.to_string(), "track");
+
+        track.insert("name", "peaks");
+        track.insert("description", "ChIP-seq peaks");
+        track.insert("useScore", "1");
+
+        assert_eq!(
+            track.to_string(),
+            r#"track name=peaks description="ChIP-seq peaks" useScore=1"#
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let mut expected = Track::default();
+        expected.insert("name", "pairedReads");
+        expected.insert("description", "Clone Paired Reads");
+        expected.insert("useScore", "1");
+        expected.insert("itemRgb", "On");
+
+        assert_eq!(
+            r#"track name=pairedReads description="Clone Paired Reads" useScore=1 itemRgb='On'"#
+                .parse(),
+            Ok(expected)
+        );
+
+        assert_eq!("track".parse(), Ok(Track::default()));
+
+        assert_eq!("".parse::<Track>(), Err(ParseError::MissingPrefix));
+        assert_eq!("tracks".parse::<Track>(), Err(ParseError::MissingPrefix));
+        assert_eq!(
+            "track name".parse::<Track>(),
+            Err(ParseError::MissingValue(String::from("name")))
+        );
+        assert_eq!(
+            r#"track name="peaks"#.parse::<Track>(),
+            Err(ParseError::UnterminatedValue(String::from("name")))
+        );
+    }
+}
@@ -3,7 +3,10 @@ use std::{
     io::{self, Write},
 };
 
-use super::Record;
+use super::{
+    record::{BedGraph, BroadPeak, NarrowPeak, TypedRecord},
+    Record, Track,
+};
 
 /// A BED writer.
 pub struct Writer<W> {
@@ -92,6 +95,122 @@ where
     {
         write_record(&mut self.inner, record)
     }
+
+    /// Writes a bedGraph record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::BedGraph};
+    ///
+    /// let mut writer = bed::Writer::new(Vec::new());
+    ///
+    /// let record: BedGraph = "sq0\t7\t13\t0.5".parse()?;
+    /// writer.write_bed_graph_record(&record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"sq0\t7\t13\t0.5\n");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_bed_graph_record(&mut self, record: &BedGraph) -> io::Result<()> {
+        writeln!(self.inner, "{}", record)
+    }
+
+    /// Writes an ENCODE narrowPeak record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::NarrowPeak};
+    ///
+    /// let mut writer = bed::Writer::new(Vec::new());
+    ///
+    /// let record: NarrowPeak = "sq0\t7\t13\tpeak0\t500\t.\t5.5\t3.2\t-1\t2".parse()?;
+    /// writer.write_narrow_peak_record(&record)?;
+    ///
+    /// assert_eq!(
+    ///     writer.get_ref(),
+    ///     b"sq0\t7\t13\tpeak0\t500\t.\t5.5\t3.2\t-1\t2\n"
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_narrow_peak_record(&mut self, record: &NarrowPeak) -> io::Result<()> {
+        writeln!(self.inner, "{}", record)
+    }
+
+    /// Writes an ENCODE broadPeak record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{self as bed, record::BroadPeak};
+    ///
+    /// let mut writer = bed::Writer::new(Vec::new());
+    ///
+    /// let record: BroadPeak = "sq0\t7\t13\tpeak0\t500\t.\t5.5\t3.2\t-1".parse()?;
+    /// writer.write_broad_peak_record(&record)?;
+    ///
+    /// assert_eq!(
+    ///     writer.get_ref(),
+    ///     b"sq0\t7\t13\tpeak0\t500\t.\t5.5\t3.2\t-1\n"
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_broad_peak_record(&mut self, record: &BroadPeak) -> io::Result<()> {
+        writeln!(self.inner, "{}", record)
+    }
+
+    /// Writes a BED record with typed custom fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bed::{
+    ///     self as bed,
+    ///     record::{custom_fields::Value, CustomFields, TypedRecord},
+    /// };
+    ///
+    /// let mut writer = bed::Writer::new(Vec::new());
+    ///
+    /// let record: bed::Record<3> = "sq0\t7\t13".parse()?;
+    /// let custom_fields = CustomFields::from(vec![Value::IntegerArray(vec![5, 3])]);
+    /// let typed_record = TypedRecord::new(record, custom_fields);
+    /// writer.write_typed_record(&typed_record)?;
+    ///
+    /// assert_eq!(writer.get_ref(), b"sq0\t7\t13\t5,3\n");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_typed_record<const N: u8>(&mut self, record: &TypedRecord<N>) -> io::Result<()>
+    where
+        Record<N>: fmt::Display,
+    {
+        writeln!(self.inner, "{}", record)
+    }
+
+    /// Writes a BED track definition line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bed as bed;
+    ///
+    /// let mut writer = bed::Writer::new(Vec::new());
+    ///
+    /// let mut track = bed::Track::default();
+    /// track.insert("name", "peaks");
+    /// track.insert("description", "ChIP-seq peaks");
+    ///
+    /// writer.write_track(&track)?;
+    ///
+    /// assert_eq!(
+    ///     writer.get_ref(),
+    ///     b"track name=peaks description=\"ChIP-seq peaks\"\n"
+    /// );
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn write_track(&mut self, track: &Track) -> io::Result<()> {
+        writeln!(self.inner, "{}", track)
+    }
 }
 
 fn write_record<W, const N: u8>(writer: &mut W, record: &Record<N>) -> io::Result<()>
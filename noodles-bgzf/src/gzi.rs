@@ -0,0 +1,96 @@
+//! gzip index (GZI).
+//!
+//! A GZI is a list of the compressed and uncompressed positions of every BGZF block in a stream,
+//! excluding the first block, which always starts at (0, 0). It allows seeking to an uncompressed
+//! position in a BGZF stream that has no other index.
+
+mod indexer;
+mod reader;
+mod writer;
+
+pub use self::{indexer::Indexer, reader::Reader, writer::Writer};
+
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+};
+
+/// A gzip index.
+///
+/// Each entry is the (compressed position, uncompressed position) pair at the start of a block.
+pub type Index = Vec<(u64, u64)>;
+
+/// Reads the entire contents of a gzip index.
+///
+/// This is a convenience function and is equivalent to opening the file at the given path and
+/// reading the index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = gzi::read("reference.fa.gz.gzi")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn read<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(BufReader::new).map(Reader::new)?;
+    reader.read_index()
+}
+
+/// Builds a gzip index from a BGZF file.
+///
+/// This is a convenience function and is equivalent to opening the file at the given path and
+/// scanning each block with an [`Indexer`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = gzi::index("reference.fa.gz")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut indexer = File::open(src).map(BufReader::new).map(Indexer::new)?;
+    indexer.index()
+}
+
+/// Returns the position of the block that contains the given uncompressed position.
+///
+/// The result is the (compressed position, uncompressed position) pair at the start of the block.
+pub(crate) fn query(index: &[(u64, u64)], pos: u64) -> (u64, u64) {
+    let i = index.partition_point(|&(_, uncompressed_position)| uncompressed_position <= pos);
+
+    if i == 0 {
+        (0, 0)
+    } else {
+        index[i - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        let index = vec![(21, 8), (43, 16)];
+
+        assert_eq!(query(&index, 0), (0, 0));
+        assert_eq!(query(&index, 7), (0, 0));
+        assert_eq!(query(&index, 8), (21, 8));
+        assert_eq!(query(&index, 15), (21, 8));
+        assert_eq!(query(&index, 16), (43, 16));
+        assert_eq!(query(&index, 144), (43, 16));
+
+        assert_eq!(query(&[], 8), (0, 0));
+    }
+}
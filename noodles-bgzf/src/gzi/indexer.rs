@@ -0,0 +1,85 @@
+use std::io::{self, Read};
+
+use super::Index;
+use crate::reader::read_compressed_block;
+
+/// A gzip index (GZI) indexer.
+///
+/// This builds a gzip index by scanning the headers and trailers of each block in a BGZF stream.
+/// Block data is not inflated.
+pub struct Indexer<R> {
+    inner: R,
+    buf: Vec<u8>,
+}
+
+impl<R> Indexer<R>
+where
+    R: Read,
+{
+    /// Creates a gzip index indexer.
+    ///
+    /// The given reader must be a raw BGZF stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let data = [];
+    /// let indexer = gzi::Indexer::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Builds a gzip index from the remainder of the stream.
+    ///
+    /// The position of the stream is expected to be at the start.
+    ///
+    /// Blocks with no uncompressed data, e.g., the EOF block, are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush()?;
+    /// let compressed_position = writer.get_ref().len() as u64;
+    /// writer.write_all(b"bgzf")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let mut indexer = gzi::Indexer::new(&data[..]);
+    /// let index = indexer.index()?;
+    ///
+    /// assert_eq!(index, vec![(compressed_position, 7)]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn index(&mut self) -> io::Result<Index> {
+        let mut index = Vec::new();
+
+        let mut compressed_position = 0;
+        let mut uncompressed_position = 0;
+
+        loop {
+            let (clen, (_, ulen)) = read_compressed_block(&mut self.inner, &mut self.buf)?;
+
+            if clen == 0 {
+                break;
+            }
+
+            if ulen > 0 && compressed_position > 0 {
+                index.push((compressed_position, uncompressed_position));
+            }
+
+            compressed_position += clen as u64;
+            uncompressed_position += ulen as u64;
+        }
+
+        Ok(index)
+    }
+}
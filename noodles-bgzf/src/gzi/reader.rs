@@ -0,0 +1,95 @@
+use std::io::{self, Read};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use super::Index;
+
+/// A gzip index (GZI) reader.
+pub struct Reader<R> {
+    inner: R,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    /// Creates a gzip index reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let data = [0, 0, 0, 0, 0, 0, 0, 0];
+    /// let reader = gzi::Reader::new(&data[..]);
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Reads a gzip index.
+    ///
+    /// The position of the stream is expected to be at the start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::gzi;
+    ///
+    /// let data = [
+    ///     0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // number_entries = 1
+    ///     0x15, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 21
+    ///     0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 8
+    /// ];
+    ///
+    /// let mut reader = gzi::Reader::new(&data[..]);
+    /// let index = reader.read_index()?;
+    ///
+    /// assert_eq!(index, vec![(21, 8)]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_index(&mut self) -> io::Result<Index> {
+        let n = self.inner.read_u64::<LittleEndian>().and_then(|n| {
+            usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })?;
+
+        // The number of entries is untrusted, so the index is not preallocated.
+        let mut index = Vec::new();
+
+        for _ in 0..n {
+            let compressed_position = self.inner.read_u64::<LittleEndian>()?;
+            let uncompressed_position = self.inner.read_u64::<LittleEndian>()?;
+            index.push((compressed_position, uncompressed_position));
+        }
+
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_index() -> io::Result<()> {
+        let data = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let mut reader = Reader::new(&data[..]);
+        assert!(reader.read_index()?.is_empty());
+
+        let data = [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let mut reader = Reader::new(&data[..]);
+        assert!(matches!(
+            reader.read_index(),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        let data = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f];
+        let mut reader = Reader::new(&data[..]);
+        assert!(matches!(
+            reader.read_index(),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+}
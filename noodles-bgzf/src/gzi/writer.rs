@@ -0,0 +1,72 @@
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+/// A gzip index (GZI) writer.
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Creates a gzip index writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::Writer::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let writer = gzi::Writer::new(Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Writes a gzip index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::gzi;
+    ///
+    /// let mut writer = gzi::Writer::new(Vec::new());
+    /// writer.write_index(&[(21, 8)])?;
+    ///
+    /// let expected = [
+    ///     0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // number_entries = 1
+    ///     0x15, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // compressed_offset = 21
+    ///     0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // uncompressed_offset = 8
+    /// ];
+    ///
+    /// assert_eq!(writer.get_ref(), &expected);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn write_index(&mut self, index: &[(u64, u64)]) -> io::Result<()> {
+        let n = u64::try_from(index.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        self.inner.write_u64::<LittleEndian>(n)?;
+
+        for &(compressed_position, uncompressed_position) in index {
+            self.inner.write_u64::<LittleEndian>(compressed_position)?;
+            self.inner
+                .write_u64::<LittleEndian>(uncompressed_position)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+
+use super::{gzi, Reader};
+
+/// An indexed BGZF reader.
+///
+/// This wraps a BGZF reader with its associated gzip index (GZI), which allows the reader to
+/// implement [`std::io::Seek`] using uncompressed positions. This is useful when a format stores
+/// offsets into the uncompressed stream, e.g., a FASTA index (FAI).
+///
+/// Seeking from the end of the stream ([`SeekFrom::End`]) is not supported.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io::{self, Read, Seek, SeekFrom}};
+/// use noodles_bgzf::{self as bgzf, gzi};
+///
+/// let index = gzi::read("reference.fa.gz.gzi")?;
+/// let mut reader = File::open("reference.fa.gz")
+///     .map(|f| bgzf::IndexedReader::new(f, index))?;
+///
+/// reader.seek(SeekFrom::Start(8))?;
+///
+/// let mut buf = [0; 4];
+/// reader.read_exact(&mut buf)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+    index: gzi::Index,
+    position: u64,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read,
+{
+    /// Creates an indexed BGZF reader.
+    ///
+    /// The given reader must be a raw BGZF stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// ```
+    pub fn new(inner: R, index: gzi::Index) -> Self {
+        Self::from_reader(Reader::new(inner), index)
+    }
+
+    /// Creates an indexed BGZF reader from a BGZF reader.
+    ///
+    /// The BGZF reader is expected to be at the start of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::Reader::builder(&data[..]).set_worker_count(2).build();
+    /// let reader = bgzf::IndexedReader::from_reader(reader, gzi::Index::default());
+    /// ```
+    pub fn from_reader(inner: Reader<R>, index: gzi::Index) -> Self {
+        Self {
+            inner,
+            index,
+            position: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// assert_eq!(reader.get_ref().position(), 0);
+    /// ```
+    pub fn get_ref(&self) -> &Reader<R> {
+        &self.inner
+    }
+
+    /// Returns the associated gzip index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// assert!(reader.index().is_empty());
+    /// ```
+    pub fn index(&self) -> &gzi::Index {
+        &self.index
+    }
+
+    /// Returns the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// let reader = reader.into_inner();
+    /// ```
+    pub fn into_inner(self) -> Reader<R> {
+        self.inner
+    }
+}
+
+impl<R> Read for IndexedReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R> BufRead for IndexedReader<R>
+where
+    R: Read,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.inner.buffer().len());
+        self.inner.consume(amt);
+        self.position += amt as u64;
+    }
+}
+
+impl<R> Seek for IndexedReader<R>
+where
+    R: Read + Seek,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => pos,
+            SeekFrom::Current(delta) => {
+                let pos = i64::try_from(self.position)
+                    .ok()
+                    .and_then(|position| position.checked_add(delta))
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidInput, "invalid seek position")
+                    })?;
+
+                u64::try_from(pos).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+            }
+            SeekFrom::End(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "seeking from the end of a BGZF stream is not supported",
+                ))
+            }
+        };
+
+        if pos == self.position {
+            return Ok(pos);
+        }
+
+        // Positions later in the current block are reached without reinflating it.
+        if let Some(delta) = pos.checked_sub(self.position) {
+            if delta <= self.inner.buffer().len() as u64 {
+                self.consume(delta as usize);
+                return Ok(pos);
+            }
+        }
+
+        self.inner.seek_by_uncompressed_position(&self.index, pos)?;
+        self.position = pos;
+
+        Ok(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::Writer;
+
+    #[test]
+    fn test_seek() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"-bgzf")?;
+        let data = writer.finish()?;
+
+        let index = gzi::Indexer::new(&data[..]).index()?;
+        let mut reader = IndexedReader::new(io::Cursor::new(data), index);
+
+        assert_eq!(reader.seek(SeekFrom::Start(5))?, 5);
+
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"es-b");
+
+        assert_eq!(reader.stream_position()?, 9);
+        assert_eq!(reader.seek(SeekFrom::Current(-9))?, 0);
+
+        let mut buf = String::new();
+        reader.read_line(&mut buf)?;
+        assert_eq!(buf, "noodles-bgzf");
+        assert_eq!(reader.stream_position()?, 12);
+
+        assert!(reader.seek(SeekFrom::End(0)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek_within_block() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"-bgzf")?;
+        let data = writer.finish()?;
+
+        let index = gzi::Indexer::new(&data[..]).index()?;
+        let mut reader = IndexedReader::new(io::Cursor::new(data), index);
+
+        let mut buf = [0; 1];
+        reader.read_exact(&mut buf)?;
+
+        let compressed_position = reader.get_ref().get_ref().position();
+
+        assert_eq!(reader.stream_position()?, 1);
+        assert_eq!(reader.seek(SeekFrom::Start(5))?, 5);
+        assert_eq!(reader.get_ref().get_ref().position(), compressed_position);
+
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"e");
+
+        Ok(())
+    }
+}
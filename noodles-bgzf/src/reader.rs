@@ -1,3 +1,10 @@
+//! BGZF reader.
+
+mod builder;
+mod inflater;
+
+pub use self::builder::Builder;
+
 use std::{
     cmp,
     io::{self, BufRead, Read, Seek, SeekFrom},
@@ -6,7 +13,8 @@ use std::{
 use byteorder::{ByteOrder, LittleEndian, ReadBytesExt};
 use flate2::Crc;
 
-use super::{gz, Block, VirtualPosition, BGZF_HEADER_SIZE};
+use self::inflater::Inflater;
+use super::{gz, gzi, Block, VirtualPosition, BGZF_HEADER_SIZE};
 
 /// A BGZF reader.
 ///
@@ -18,6 +26,9 @@ use super::{gz, Block, VirtualPosition, BGZF_HEADER_SIZE};
 /// correctly track (virtual) positions, the reader _cannot_ be double buffered (e.g., using
 /// [`std::io::BufReader`]).
 ///
+/// Blocks can optionally be inflated on a pool of worker threads. See
+/// [`Builder::set_worker_count`].
+///
 /// # Examples
 ///
 /// ```no_run
@@ -33,12 +44,27 @@ pub struct Reader<R> {
     position: u64,
     cdata: Vec<u8>,
     block: Block,
+    inflater: Option<Inflater>,
 }
 
 impl<R> Reader<R>
 where
     R: Read,
 {
+    /// Creates a BGZF reader builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let builder = bgzf::Reader::builder(&data[..]);
+    /// let reader = builder.build();
+    /// ```
+    pub fn builder(inner: R) -> Builder<R> {
+        Builder::new(inner)
+    }
+
     /// Creates a BGZF reader.
     ///
     /// # Examples
@@ -49,12 +75,7 @@ where
     /// let reader = bgzf::Reader::new(&data[..]);
     /// ```
     pub fn new(inner: R) -> Self {
-        Self {
-            inner,
-            position: 0,
-            cdata: Vec::new(),
-            block: Block::default(),
-        }
+        Self::builder(inner).build()
     }
 
     /// Returns a reference to the underlying reader.
@@ -73,6 +94,9 @@ where
 
     /// Returns a mutable reference to the underlying reader.
     ///
+    /// When using multiple workers, the underlying reader may be positioned ahead of the current
+    /// block.
+    ///
     /// # Examples
     ///
     /// ```
@@ -126,6 +150,30 @@ where
     pub fn virtual_position(&self) -> VirtualPosition {
         self.block.virtual_position()
     }
+
+    // Returns the unconsumed part of the current block.
+    pub(crate) fn buffer(&self) -> &[u8] {
+        self.block.buffer()
+    }
+
+    // Reads and inflates the next block, returning its compressed size. A size of 0 means the
+    // stream reached EOF.
+    fn read_block(&mut self) -> io::Result<usize> {
+        let inflater = match self.inflater.as_mut() {
+            Some(inflater) => inflater,
+            None => return read_block(&mut self.inner, &mut self.cdata, &mut self.block),
+        };
+
+        match inflater.next_block(&mut self.inner)? {
+            Some(block) => {
+                let clen = block.clen() as usize;
+                let prev_block = std::mem::replace(&mut self.block, block);
+                inflater.recycle(prev_block);
+                Ok(clen)
+            }
+            None => Ok(0),
+        }
+    }
 }
 
 impl<R> Reader<R>
@@ -150,9 +198,13 @@ where
     pub fn seek(&mut self, pos: VirtualPosition) -> io::Result<VirtualPosition> {
         let (cpos, upos) = pos.into();
 
+        if let Some(inflater) = self.inflater.as_mut() {
+            inflater.clear();
+        }
+
         self.inner.seek(SeekFrom::Start(cpos))?;
 
-        let block_size = read_block(&mut self.inner, &mut self.cdata, &mut self.block)?;
+        let block_size = self.read_block()?;
         self.position = cpos + (block_size as u64);
 
         self.block.set_cpos(cpos);
@@ -160,6 +212,62 @@ where
 
         Ok(pos)
     }
+
+    /// Seeks the stream to the given uncompressed position.
+    ///
+    /// The gzip index is used to find the block that contains the uncompressed position. The
+    /// reader then seeks to the start of that block and consumes the remaining bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Read, Write};
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush()?;
+    /// writer.write_all(b"bgzf")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let index = gzi::Indexer::new(&data[..]).index()?;
+    ///
+    /// let mut reader = bgzf::Reader::new(Cursor::new(data));
+    /// reader.seek_by_uncompressed_position(&index, 8)?;
+    ///
+    /// let mut buf = Vec::new();
+    /// reader.read_to_end(&mut buf)?;
+    /// assert_eq!(buf, b"gzf");
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn seek_by_uncompressed_position(
+        &mut self,
+        index: &[(u64, u64)],
+        pos: u64,
+    ) -> io::Result<u64> {
+        let (compressed_position, uncompressed_position) = gzi::query(index, pos);
+
+        let virtual_position = VirtualPosition::try_from((compressed_position, 0))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        self.seek(virtual_position)?;
+
+        let mut remaining = pos - uncompressed_position;
+
+        while remaining > 0 {
+            let buf = self.fill_buf()?;
+
+            if buf.is_empty() {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+
+            let n = cmp::min(buf.len() as u64, remaining);
+            self.consume(n as usize);
+            remaining -= n;
+        }
+
+        Ok(pos)
+    }
 }
 
 impl<R> Read for Reader<R>
@@ -172,7 +280,10 @@ where
         // If a new block is about to be read and the given buffer is guaranteed to be larger than
         // next block, reading to the block buffer can be skipped. The uncompressed data is read
         // directly to the given buffer to avoid double copying.
-        if self.block.is_eof() && buf.len() >= block::MAX_UNCOMPRESSED_DATA_LENGTH {
+        if self.inflater.is_none()
+            && self.block.is_eof()
+            && buf.len() >= block::MAX_UNCOMPRESSED_DATA_LENGTH
+        {
             let block_size =
                 read_block_into(&mut self.inner, &mut self.cdata, &mut self.block, buf)?;
             self.block.set_cpos(self.position);
@@ -215,7 +326,7 @@ where
 
     fn fill_buf(&mut self) -> io::Result<&[u8]> {
         if self.block.is_eof() {
-            let block_size = read_block(&mut self.inner, &mut self.cdata, &mut self.block)?;
+            let block_size = self.read_block()?;
             self.block.set_cpos(self.position);
             self.position += block_size as u64;
         }
@@ -317,7 +428,10 @@ pub(crate) fn inflate_data(reader: &[u8], writer: &mut [u8]) -> io::Result<()> {
     decoder.read_exact(writer)
 }
 
-fn read_compressed_block<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<(usize, (u32, usize))>
+pub(crate) fn read_compressed_block<R>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+) -> io::Result<(usize, (u32, usize))>
 where
     R: Read,
 {
@@ -436,7 +550,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::io::Cursor;
+    use std::io::{Cursor, Write};
 
     use crate::writer::BGZF_EOF;
 
@@ -475,6 +589,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_with_multiple_workers() -> io::Result<()> {
+        use crate::Writer;
+
+        let mut writer = Writer::new(Vec::new());
+        let mut virtual_positions = Vec::new();
+
+        for i in 0..8u8 {
+            virtual_positions.push(writer.virtual_position());
+            writer.write_all(&[i; 5])?;
+            writer.flush()?;
+        }
+
+        let data = writer.finish()?;
+
+        let mut reader = Reader::builder(Cursor::new(&data))
+            .set_worker_count(3)
+            .build();
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        let expected: Vec<_> = (0..8u8).flat_map(|i| [i; 5]).collect();
+        assert_eq!(buf, expected);
+        assert_eq!(
+            reader.virtual_position(),
+            VirtualPosition::try_from((data.len() as u64, 0)).unwrap()
+        );
+
+        let pos = VirtualPosition::try_from((virtual_positions[5].compressed(), 2)).unwrap();
+        reader.seek(pos)?;
+        assert_eq!(reader.virtual_position(), pos);
+
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(buf, [5, 5, 5, 6]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_header() -> io::Result<()> {
         let mut reader = BGZF_EOF;
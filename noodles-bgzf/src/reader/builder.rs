@@ -0,0 +1,79 @@
+use std::io::Read;
+
+use super::{inflater::Inflater, Reader};
+use crate::Block;
+
+/// A BGZF reader builder.
+#[derive(Debug)]
+pub struct Builder<R> {
+    inner: R,
+    worker_count: usize,
+}
+
+impl<R> Builder<R>
+where
+    R: Read,
+{
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            worker_count: 1,
+        }
+    }
+
+    /// Sets a worker count.
+    ///
+    /// When the worker count is greater than 1, the reader reads ahead up to `worker_count`
+    /// compressed blocks and inflates them on a pool of worker threads. Blocks are still emitted
+    /// in stream order.
+    ///
+    /// By default, the worker count is set to 1, i.e., blocks are inflated on the calling thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let builder = bgzf::Reader::builder(&data[..]).set_worker_count(8);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: usize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
+    /// Builds a BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::Reader::builder(&data[..]).build();
+    /// ```
+    pub fn build(self) -> Reader<R> {
+        let inflater = if self.worker_count > 1 {
+            Some(Inflater::new(self.worker_count))
+        } else {
+            None
+        };
+
+        Reader {
+            inner: self.inner,
+            position: 0,
+            cdata: Vec::new(),
+            block: Block::default(),
+            inflater,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let builder = Builder::new(&[][..]);
+        assert_eq!(builder.worker_count, 1);
+    }
+}
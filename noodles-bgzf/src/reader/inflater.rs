@@ -0,0 +1,218 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Read},
+    thread::{self, JoinHandle},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use flate2::Crc;
+
+use super::{inflate_data, read_compressed_block};
+use crate::{block, Block};
+
+type BlockResult = io::Result<Block>;
+
+struct Request {
+    cdata: Vec<u8>,
+    clen: usize,
+    crc32: u32,
+    ulen: usize,
+    block: Block,
+    tx: Sender<BlockResult>,
+}
+
+/// A pool of worker threads that inflates read-ahead BGZF blocks.
+///
+/// Compressed blocks are read on the calling thread and queued in stream order. Each queued block
+/// has its own result channel, so blocks are returned in the order they were read regardless of
+/// which worker finishes first.
+pub(super) struct Inflater {
+    worker_count: usize,
+    request_tx: Option<Sender<Request>>,
+    handles: Vec<JoinHandle<()>>,
+    queue: VecDeque<Receiver<BlockResult>>,
+    blocks: Vec<Block>,
+    is_eof: bool,
+}
+
+impl Inflater {
+    pub(super) fn new(worker_count: usize) -> Self {
+        let (request_tx, request_rx) = crossbeam_channel::unbounded::<Request>();
+
+        let handles = (0..worker_count)
+            .map(|_| {
+                let request_rx = request_rx.clone();
+
+                thread::spawn(move || {
+                    while let Ok(request) = request_rx.recv() {
+                        let tx = request.tx.clone();
+                        let result = inflate(request);
+                        // The receiver is dropped when the read-ahead queue is cleared.
+                        let _ = tx.send(result);
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            worker_count,
+            request_tx: Some(request_tx),
+            handles,
+            queue: VecDeque::with_capacity(worker_count),
+            blocks: Vec::with_capacity(worker_count),
+            is_eof: false,
+        }
+    }
+
+    /// Returns the next inflated block in stream order.
+    ///
+    /// If the stream has no more blocks, this returns `None`.
+    pub(super) fn next_block<R>(&mut self, reader: &mut R) -> io::Result<Option<Block>>
+    where
+        R: Read,
+    {
+        self.fill_queue(reader);
+
+        match self.queue.pop_front() {
+            Some(rx) => rx
+                .recv()
+                .map_err(|_| io::Error::new(io::ErrorKind::Other, "inflater worker disconnected"))?
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns a spent block to be reused for a future inflation.
+    pub(super) fn recycle(&mut self, block: Block) {
+        if self.blocks.len() < self.worker_count {
+            self.blocks.push(block);
+        }
+    }
+
+    /// Discards all read-ahead blocks.
+    ///
+    /// This must be called when the underlying reader is repositioned.
+    pub(super) fn clear(&mut self) {
+        self.queue.clear();
+        self.is_eof = false;
+    }
+
+    fn fill_queue<R>(&mut self, reader: &mut R)
+    where
+        R: Read,
+    {
+        while !self.is_eof && self.queue.len() < self.worker_count {
+            let (tx, rx) = crossbeam_channel::bounded(1);
+
+            let mut cdata = Vec::new();
+
+            match read_compressed_block(reader, &mut cdata) {
+                Ok((0, _)) => {
+                    self.is_eof = true;
+                    break;
+                }
+                Ok((clen, (crc32, ulen))) => {
+                    let block = self.blocks.pop().unwrap_or_default();
+
+                    let request = Request {
+                        cdata,
+                        clen,
+                        crc32,
+                        ulen,
+                        block,
+                        tx,
+                    };
+
+                    if let Some(request_tx) = self.request_tx.as_ref() {
+                        // The workers only exit after `request_tx` is dropped.
+                        request_tx.send(request).ok();
+                    }
+                }
+                Err(e) => {
+                    // Errors are queued in order so that blocks read before the failure can
+                    // still be consumed.
+                    tx.send(Err(e)).ok();
+                    self.is_eof = true;
+                }
+            }
+
+            self.queue.push_back(rx);
+        }
+    }
+}
+
+impl Drop for Inflater {
+    fn drop(&mut self) {
+        self.queue.clear();
+        self.request_tx.take();
+
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn inflate(request: Request) -> io::Result<Block> {
+    let Request {
+        cdata,
+        clen,
+        crc32,
+        ulen,
+        mut block,
+        ..
+    } = request;
+
+    if ulen > block::MAX_UNCOMPRESSED_DATA_LENGTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "expected isize <= {}, got {}",
+                block::MAX_UNCOMPRESSED_DATA_LENGTH,
+                ulen
+            ),
+        ));
+    }
+
+    block.set_clen(clen as u64);
+    block.set_upos(0);
+    block.set_ulen(ulen);
+
+    inflate_data(&cdata, block.buffer_mut())?;
+
+    let mut crc = Crc::new();
+    crc.update(block.buffer());
+
+    if crc.sum() == crc32 {
+        Ok(block)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "block data checksum mismatch",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::BGZF_EOF;
+
+    #[test]
+    fn test_next_block() -> io::Result<()> {
+        let mut data = Vec::new();
+        data.extend_from_slice(BGZF_EOF);
+        data.extend_from_slice(BGZF_EOF);
+
+        let mut reader = &data[..];
+        let mut inflater = Inflater::new(2);
+
+        let block = inflater.next_block(&mut reader)?.expect("missing block");
+        assert_eq!(block.clen(), BGZF_EOF.len() as u64);
+        assert_eq!(block.ulen(), 0);
+
+        assert!(inflater.next_block(&mut reader)?.is_some());
+        assert!(inflater.next_block(&mut reader)?.is_none());
+
+        Ok(())
+    }
+}
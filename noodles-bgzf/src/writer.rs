@@ -2,17 +2,28 @@
 
 mod builder;
 mod compression_level;
+mod deflater;
+mod pending_virtual_position;
 
-pub use self::{builder::Builder, compression_level::CompressionLevel};
+pub use self::{
+    builder::Builder, compression_level::CompressionLevel,
+    pending_virtual_position::PendingVirtualPosition,
+};
 
 use std::{
     cmp,
+    collections::VecDeque,
     io::{self, Write},
+    mem,
 };
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use flate2::Crc;
 
+use self::{
+    deflater::{Deflater, GzData},
+    pending_virtual_position::Inner as PendingVirtualPositionInner,
+};
 use super::{block, gz, VirtualPosition, BGZF_HEADER_SIZE};
 
 const BGZF_FLG: u8 = 0x04; // FEXTRA
@@ -49,6 +60,9 @@ type CompressionLevelImpl = flate2::Compression;
 ///
 /// This implements [`std::io::Write`], consuming uncompressed data and emitting compressed data.
 ///
+/// Blocks can optionally be deflated on a pool of worker threads. See
+/// [`Builder::set_worker_count`].
+///
 /// # Examples
 ///
 /// ```
@@ -70,6 +84,13 @@ where
     position: u64,
     buf: Vec<u8>,
     compression_level: CompressionLevelImpl,
+    deflater: Option<Deflater>,
+    queued_block_count: u64,
+    written_block_count: u64,
+    // The block index of the last issued pending virtual position.
+    last_pending_block_index: Option<u64>,
+    // (block index, compressed position) of written blocks that can still be resolved.
+    block_positions: VecDeque<(u64, u64)>,
 }
 
 impl<W> Writer<W>
@@ -142,9 +163,13 @@ where
 
     /// Returns the current virtual position of the stream.
     ///
+    /// When using multiple workers, use [`Self::pending_virtual_position`] instead. The virtual
+    /// position is unknown while blocks are being compressed.
+    ///
     /// # Panics
     ///
-    /// This panics if the stream flushed >= 256 TiB of compressed data.
+    /// This panics if the stream flushed >= 256 TiB of compressed data or if blocks are still
+    /// being compressed by workers.
     ///
     /// # Examples
     ///
@@ -154,21 +179,187 @@ where
     /// assert_eq!(writer.virtual_position(), bgzf::VirtualPosition::from(0));
     /// ```
     pub fn virtual_position(&self) -> VirtualPosition {
+        assert!(
+            !self.has_pending_blocks(),
+            "virtual position of a writer with pending blocks"
+        );
+
+        self.current_virtual_position().unwrap()
+    }
+
+    // Returns the virtual position of the uncompressed buffer. All queued blocks must be written.
+    fn current_virtual_position(&self) -> io::Result<VirtualPosition> {
         // SAFETY: The uncompressed buffer is guaranteed to be <= `MAX_UNCOMPRESSED_POSITION`.
         let uncompressed_position = self.buf.len() as u16;
-        VirtualPosition::try_from((self.position, uncompressed_position)).unwrap()
+
+        VirtualPosition::try_from((self.position, uncompressed_position))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+
+    fn has_pending_blocks(&self) -> bool {
+        self.deflater
+            .as_ref()
+            .map(|deflater| deflater.len() > 0)
+            .unwrap_or(false)
+    }
+
+    /// Returns the current virtual position of the stream without waiting for pending blocks.
+    ///
+    /// Unlike [`Self::virtual_position`], this can be called while blocks are being compressed
+    /// when using multiple workers. The result is converted to a virtual position using
+    /// [`Self::resolve_virtual_position`], which returns an error if it cannot be resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::builder(Vec::new())
+    ///     .set_worker_count(2)
+    ///     .build();
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// let pending_virtual_position = writer.pending_virtual_position();
+    ///
+    /// let virtual_position = writer.resolve_virtual_position(pending_virtual_position)?;
+    /// assert_eq!(virtual_position, bgzf::VirtualPosition::try_from((0, 7)).unwrap());
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn pending_virtual_position(&mut self) -> PendingVirtualPosition {
+        if !self.has_pending_blocks() {
+            if let Ok(virtual_position) = self.current_virtual_position() {
+                return PendingVirtualPosition::from(virtual_position);
+            }
+        }
+
+        // SAFETY: The uncompressed buffer is guaranteed to be <= `MAX_UNCOMPRESSED_POSITION`.
+        let uncompressed_position = self.buf.len() as u16;
+        let block_index = self.queued_block_count;
+
+        self.last_pending_block_index = Some(block_index);
+
+        PendingVirtualPosition(PendingVirtualPositionInner::Pending {
+            block_index,
+            uncompressed_position,
+        })
+    }
+
+    /// Converts a pending virtual position to a virtual position.
+    ///
+    /// If the block of the given position is still being compressed, this waits for it and all
+    /// preceding blocks to be written. Resolving positions a few blocks behind the current one
+    /// does not stall the workers.
+    ///
+    /// Pending positions are expected to be resolved in the order they were taken. Resolving a
+    /// position discards the compressed positions of the blocks before it, after which older
+    /// pending positions can no longer be resolved. Only the blocks that pending positions can
+    /// refer to are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::builder(Vec::new())
+    ///     .set_worker_count(2)
+    ///     .build();
+    ///
+    /// let start = writer.pending_virtual_position();
+    /// writer.write_all(b"noodles")?;
+    /// let end = writer.pending_virtual_position();
+    ///
+    /// writer.flush()?;
+    ///
+    /// assert_eq!(
+    ///     writer.resolve_virtual_position(start)?,
+    ///     bgzf::VirtualPosition::from(0)
+    /// );
+    /// assert_eq!(
+    ///     writer.resolve_virtual_position(end)?,
+    ///     bgzf::VirtualPosition::try_from((0, 7)).unwrap()
+    /// );
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn resolve_virtual_position(
+        &mut self,
+        pos: PendingVirtualPosition,
+    ) -> io::Result<VirtualPosition> {
+        let (block_index, uncompressed_position) = match pos.0 {
+            PendingVirtualPositionInner::Resolved(virtual_position) => return Ok(virtual_position),
+            PendingVirtualPositionInner::Pending {
+                block_index,
+                uncompressed_position,
+            } => (block_index, uncompressed_position),
+        };
+
+        if block_index > self.queued_block_count {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid pending virtual position",
+            ));
+        }
+
+        let compressed_position = loop {
+            if block_index < self.written_block_count {
+                match self.block_positions.iter().find(|(i, _)| *i == block_index) {
+                    Some(&(_, compressed_position)) => break compressed_position,
+                    None => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "pending virtual position was already discarded",
+                        ))
+                    }
+                }
+            } else if block_index == self.queued_block_count {
+                while self.write_deflated_block(true)? {}
+                break self.position;
+            } else if !self.write_deflated_block(true)? {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid pending virtual position",
+                ));
+            }
+        };
+
+        while let Some(&(i, _)) = self.block_positions.front() {
+            if i < block_index {
+                self.block_positions.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        VirtualPosition::try_from((compressed_position, uncompressed_position))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
     }
 
     fn flush_block(&mut self) -> io::Result<()> {
-        let (cdata, crc32, r#isize) = deflate_data(&self.buf, self.compression_level)?;
+        if let Some(deflater) = self.deflater.as_mut() {
+            let data = mem::replace(
+                &mut self.buf,
+                Vec::with_capacity(block::MAX_UNCOMPRESSED_DATA_LENGTH),
+            );
 
-        let inner = self.inner.as_mut().unwrap();
+            deflater.send(data, self.compression_level);
+            self.queued_block_count += 1;
 
-        write_header(inner, cdata.len())?;
-        inner.write_all(&cdata[..])?;
-        write_trailer(inner, crc32, r#isize)?;
+            // Write any blocks that are already compressed, and only wait when every worker is
+            // busy.
+            while self.write_deflated_block(false)? {}
 
-        let block_size = BGZF_HEADER_SIZE + cdata.len() + gz::TRAILER_SIZE;
+            while self.deflater.as_ref().map(|d| d.is_full()).unwrap_or(false) {
+                self.write_deflated_block(true)?;
+            }
+
+            return Ok(());
+        }
+
+        let gz_data = deflate_data(&self.buf, self.compression_level)?;
+
+        let inner = self.inner.as_mut().unwrap();
+        let block_size = write_block(inner, gz_data)?;
         self.position += block_size as u64;
 
         self.buf.clear();
@@ -176,6 +367,43 @@ where
         Ok(())
     }
 
+    // Writes the oldest compressed block from the worker pool, optionally waiting for it to be
+    // compressed. This returns whether a block was written.
+    fn write_deflated_block(&mut self, wait: bool) -> io::Result<bool> {
+        let deflater = match self.deflater.as_mut() {
+            Some(deflater) => deflater,
+            None => return Ok(false),
+        };
+
+        let result = if wait {
+            deflater.recv()
+        } else {
+            deflater.try_recv()
+        };
+
+        let gz_data = match result {
+            Some(result) => result?,
+            None => return Ok(false),
+        };
+
+        let inner = self.inner.as_mut().unwrap();
+        let block_size = write_block(inner, gz_data)?;
+
+        let block_index = self.written_block_count;
+
+        // Blocks after the last pending virtual position are never referred to.
+        if let Some(last_pending_block_index) = self.last_pending_block_index {
+            if block_index <= last_pending_block_index {
+                self.block_positions.push_back((block_index, self.position));
+            }
+        }
+
+        self.written_block_count += 1;
+        self.position += block_size as u64;
+
+        Ok(true)
+    }
+
     /// Attempts to finish the output stream by flushing any remaining buffers.
     ///
     /// This then appends the final BGZF EOF block.
@@ -250,21 +478,34 @@ where
         self.buf.extend_from_slice(&buf[..max_write_len]);
 
         if self.buf.len() >= block::MAX_UNCOMPRESSED_DATA_LENGTH {
-            self.flush()?;
+            self.flush_block()?;
         }
 
         Ok(max_write_len)
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        if self.buf.is_empty() {
-            Ok(())
-        } else {
-            self.flush_block()
+        if !self.buf.is_empty() {
+            self.flush_block()?;
         }
+
+        while self.write_deflated_block(true)? {}
+
+        Ok(())
     }
 }
 
+fn write_block<W>(writer: &mut W, (cdata, crc32, r#isize): GzData) -> io::Result<usize>
+where
+    W: Write,
+{
+    write_header(writer, cdata.len())?;
+    writer.write_all(&cdata[..])?;
+    write_trailer(writer, crc32, r#isize)?;
+
+    Ok(BGZF_HEADER_SIZE + cdata.len() + gz::TRAILER_SIZE)
+}
+
 fn write_header<W>(writer: &mut W, cdata_len: usize) -> io::Result<()>
 where
     W: Write,
@@ -366,6 +607,103 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_virtual_position_with_multiple_workers() -> io::Result<()> {
+        let data = vec![0; block::MAX_UNCOMPRESSED_DATA_LENGTH * 2 + 8];
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(&data)?;
+        let expected = writer.virtual_position();
+
+        let mut writer = Writer::builder(Vec::new()).set_worker_count(3).build();
+        writer.write_all(&data)?;
+        let pos = writer.pending_virtual_position();
+        let actual = writer.resolve_virtual_position(pos)?;
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual.uncompressed(), 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_virtual_position_discards_old_blocks() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mut writer = Writer::builder(Vec::new()).set_worker_count(2).build();
+
+        let chunk = vec![0; block::MAX_UNCOMPRESSED_DATA_LENGTH];
+
+        for _ in 0..16 {
+            writer.write_all(&chunk)?;
+        }
+
+        writer.flush()?;
+        assert!(writer.block_positions.is_empty());
+
+        writer.write_all(&chunk)?;
+        let a = writer.pending_virtual_position();
+        writer.write_all(&chunk)?;
+        let b = writer.pending_virtual_position();
+        writer.write_all(&chunk)?;
+
+        writer.resolve_virtual_position(b)?;
+        assert!(writer.block_positions.len() <= 1);
+
+        // `a` is only pending if its block was still being compressed.
+        if a.get().is_none() {
+            assert!(writer.resolve_virtual_position(a).is_err());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_multiple_workers() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Read;
+
+        use crate::Reader;
+
+        let mut writer = Writer::builder(Vec::new()).set_worker_count(3).build();
+
+        let data: Vec<u8> = (0..=255)
+            .cycle()
+            .take(block::MAX_UNCOMPRESSED_DATA_LENGTH * 5 + 8)
+            .collect();
+        let mut pending_virtual_positions = Vec::new();
+
+        for chunk in data.chunks(16384) {
+            pending_virtual_positions.push(writer.pending_virtual_position());
+            writer.write_all(chunk)?;
+        }
+
+        let mut virtual_positions = Vec::new();
+
+        for pos in pending_virtual_positions {
+            virtual_positions.push(writer.resolve_virtual_position(pos)?);
+        }
+
+        assert!(writer.block_positions.len() <= 1);
+
+        let compressed_data = writer.finish()?;
+
+        let eof_start = compressed_data.len() - BGZF_EOF.len();
+        assert_eq!(&compressed_data[eof_start..], BGZF_EOF);
+
+        let mut reader = Reader::new(io::Cursor::new(&compressed_data));
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, data);
+
+        for (i, pos) in virtual_positions.into_iter().enumerate() {
+            reader.seek(pos)?;
+            let mut buf = [0; 1];
+            reader.read_exact(&mut buf)?;
+            assert_eq!(buf[0], data[i * 16384]);
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_finish() -> io::Result<()> {
         let mut writer = Writer::new(Vec::new());
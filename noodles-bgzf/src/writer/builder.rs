@@ -1,6 +1,6 @@
-use std::io::Write;
+use std::{collections::VecDeque, io::Write};
 
-use super::{CompressionLevel, Writer};
+use super::{deflater::Deflater, CompressionLevel, Writer};
 use crate::block;
 
 /// A BGZF writer builder.
@@ -8,6 +8,7 @@ use crate::block;
 pub struct Builder<W> {
     inner: W,
     compression_level: Option<CompressionLevel>,
+    worker_count: usize,
 }
 
 impl<W> Builder<W>
@@ -18,6 +19,7 @@ where
         Self {
             inner,
             compression_level: None,
+            worker_count: 1,
         }
     }
 
@@ -38,22 +40,63 @@ where
         self
     }
 
-    /// Builds an async BGZF writer.
+    /// Sets a worker count.
+    ///
+    /// When the worker count is greater than 1, full blocks are deflated on a pool of worker
+    /// threads while the caller continues writing. Blocks are still written in order.
+    ///
+    /// By default, the worker count is set to 1, i.e., blocks are deflated on the calling thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::Writer::builder(Vec::new()).set_worker_count(8);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: usize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
+    /// Builds a BGZF writer.
     ///
     /// # Examples
     ///
     /// ```
     /// use noodles_bgzf as bgzf;
-    /// let writer = bgzf::AsyncWriter::builder(Vec::new()).build();
+    /// let writer = bgzf::Writer::builder(Vec::new()).build();
     /// ```
     pub fn build(self) -> Writer<W> {
         let compression_level = self.compression_level.unwrap_or_default();
 
+        let deflater = if self.worker_count > 1 {
+            Some(Deflater::new(self.worker_count))
+        } else {
+            None
+        };
+
         Writer {
             inner: Some(self.inner),
             position: 0,
             buf: Vec::with_capacity(block::MAX_UNCOMPRESSED_DATA_LENGTH),
             compression_level: compression_level.into(),
+            deflater,
+            queued_block_count: 0,
+            written_block_count: 0,
+            last_pending_block_index: None,
+            block_positions: VecDeque::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let builder = Builder::new(Vec::new());
+        assert!(builder.compression_level.is_none());
+        assert_eq!(builder.worker_count, 1);
+    }
+}
@@ -0,0 +1,117 @@
+use std::{
+    collections::VecDeque,
+    fmt, io,
+    thread::{self, JoinHandle},
+};
+
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
+
+use super::{deflate_data, CompressionLevelImpl};
+
+// (CDATA, CRC32, ISIZE)
+pub(super) type GzData = (Vec<u8>, u32, u32);
+
+type Request = (Vec<u8>, CompressionLevelImpl, Sender<io::Result<GzData>>);
+
+/// A pool of worker threads that deflates BGZF blocks.
+///
+/// Each queued block has its own result channel, so compressed blocks are received in the order
+/// they were queued regardless of which worker finishes first.
+pub(super) struct Deflater {
+    worker_count: usize,
+    request_tx: Option<Sender<Request>>,
+    handles: Vec<JoinHandle<()>>,
+    queue: VecDeque<Receiver<io::Result<GzData>>>,
+}
+
+impl Deflater {
+    pub(super) fn new(worker_count: usize) -> Self {
+        let (request_tx, request_rx) = crossbeam_channel::unbounded::<Request>();
+
+        let handles = (0..worker_count)
+            .map(|_| {
+                let request_rx = request_rx.clone();
+
+                thread::spawn(move || {
+                    while let Ok((data, compression_level, tx)) = request_rx.recv() {
+                        let result = deflate_data(&data, compression_level);
+                        tx.send(result).ok();
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            worker_count,
+            request_tx: Some(request_tx),
+            handles,
+            queue: VecDeque::with_capacity(worker_count),
+        }
+    }
+
+    /// Returns the number of blocks that are queued but not yet received.
+    pub(super) fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns whether each worker has a queued block.
+    pub(super) fn is_full(&self) -> bool {
+        self.queue.len() >= self.worker_count
+    }
+
+    /// Queues uncompressed data to be deflated.
+    pub(super) fn send(&mut self, data: Vec<u8>, compression_level: CompressionLevelImpl) {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+
+        if let Some(request_tx) = self.request_tx.as_ref() {
+            // The workers only exit after `request_tx` is dropped.
+            request_tx.send((data, compression_level, tx)).ok();
+        }
+
+        self.queue.push_back(rx);
+    }
+
+    /// Receives the oldest queued block, waiting for it to be deflated.
+    pub(super) fn recv(&mut self) -> Option<io::Result<GzData>> {
+        self.queue.pop_front().map(|rx| {
+            rx.recv()
+                .map_err(|_| io::Error::new(io::ErrorKind::Other, "deflater worker disconnected"))?
+        })
+    }
+
+    /// Receives the oldest queued block if it is already deflated.
+    pub(super) fn try_recv(&mut self) -> Option<io::Result<GzData>> {
+        let result = match self.queue.front()?.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "deflater worker disconnected",
+            )),
+        };
+
+        self.queue.pop_front();
+
+        Some(result)
+    }
+}
+
+impl fmt::Debug for Deflater {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Deflater")
+            .field("worker_count", &self.worker_count)
+            .field("queue_len", &self.queue.len())
+            .finish()
+    }
+}
+
+impl Drop for Deflater {
+    fn drop(&mut self) {
+        self.queue.clear();
+        self.request_tx.take();
+
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::VirtualPosition;
+
+/// A BGZF virtual position that may not be resolved yet.
+///
+/// A writer with multiple workers cannot know the compressed position of a block until all
+/// preceding blocks are compressed. A pending virtual position records the position relative to
+/// the block that contains it and is converted to a [`VirtualPosition`] using
+/// [`super::Writer::resolve_virtual_position`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PendingVirtualPosition(pub(super) Inner);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Inner {
+    Resolved(VirtualPosition),
+    Pending {
+        block_index: u64,
+        uncompressed_position: u16,
+    },
+}
+
+impl PendingVirtualPosition {
+    /// Returns the virtual position if it is already known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// let pending_virtual_position = writer.pending_virtual_position();
+    /// assert_eq!(
+    ///     pending_virtual_position.get(),
+    ///     Some(bgzf::VirtualPosition::from(0))
+    /// );
+    /// ```
+    pub fn get(&self) -> Option<VirtualPosition> {
+        match self.0 {
+            Inner::Resolved(pos) => Some(pos),
+            Inner::Pending { .. } => None,
+        }
+    }
+}
+
+impl From<VirtualPosition> for PendingVirtualPosition {
+    fn from(pos: VirtualPosition) -> Self {
+        Self(Inner::Resolved(pos))
+    }
+}
@@ -4,5 +4,6 @@
 
 pub mod position;
 pub mod region;
+pub mod region_index;
 
-pub use self::{position::Position, region::Region};
+pub use self::{position::Position, region::Region, region_index::RegionIndex};
@@ -0,0 +1,212 @@
+//! In-memory region index.
+
+pub mod interval_tree;
+
+pub use self::interval_tree::IntervalTree;
+
+use std::collections::HashMap;
+
+use crate::{region::Interval, Region};
+
+/// An in-memory index of values keyed by genomic regions.
+///
+/// This holds an [`IntervalTree`] per reference sequence name. It is built from an iterator of
+/// regions and values (see [`FromIterator`]), e.g., from BED, GFF, GTF, or VCF records, which can
+/// be converted to regions.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::{Position, Region, RegionIndex};
+///
+/// let index: RegionIndex<_> = [
+///     (Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?), "g0"),
+///     (Region::new("sq0", Position::try_from(21)?..=Position::try_from(34)?), "g1"),
+///     (Region::new("sq1", Position::try_from(5)?..=Position::try_from(8)?), "g2"),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let region = "sq0:13-21".parse()?;
+/// let values: Vec<_> = index.query(&region).map(|(_, value)| *value).collect();
+/// assert_eq!(values, ["g0", "g1"]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct RegionIndex<T> {
+    trees: HashMap<String, IntervalTree<T>>,
+}
+
+impl<T> RegionIndex<T> {
+    /// Returns the number of regions in the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Region, RegionIndex};
+    ///
+    /// let index: RegionIndex<_> = [(Region::new("sq0", ..), "g0"), (Region::new("sq1", ..), "g1")]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// assert_eq!(index.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.trees.values().map(|tree| tree.len()).sum()
+    }
+
+    /// Returns whether the index has any regions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::RegionIndex;
+    /// let index: RegionIndex<()> = RegionIndex::default();
+    /// assert!(index.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.trees.values().all(|tree| tree.is_empty())
+    }
+
+    /// Returns the interval tree of the reference sequence with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Region, RegionIndex};
+    ///
+    /// let index: RegionIndex<_> = [(Region::new("sq0", ..), "g0")].into_iter().collect();
+    ///
+    /// assert_eq!(index.get("sq0").map(|tree| tree.len()), Some(1));
+    /// assert!(index.get("sq1").is_none());
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&IntervalTree<T>> {
+        self.trees.get(name)
+    }
+
+    /// Returns an iterator over the intervals and values that overlap the given region.
+    ///
+    /// Overlapping intervals are returned sorted by interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region, RegionIndex};
+    ///
+    /// let index: RegionIndex<_> = [
+    ///     (Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?), "g0"),
+    ///     (Region::new("sq1", Position::try_from(8)?..=Position::try_from(13)?), "g1"),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let region = "sq1:5-8".parse()?;
+    /// let values: Vec<_> = index.query(&region).map(|(_, value)| *value).collect();
+    /// assert_eq!(values, ["g1"]);
+    ///
+    /// let region = "sq2".parse()?;
+    /// assert!(index.query(&region).next().is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query(&self, region: &Region) -> impl Iterator<Item = (Interval, &T)> {
+        let interval = region.interval();
+
+        self.get(region.name())
+            .into_iter()
+            .flat_map(move |tree| tree.query(interval))
+    }
+
+    /// Returns the interval and value nearest to the given region and its distance.
+    ///
+    /// Only intervals on the same reference sequence are considered. See
+    /// [`IntervalTree::nearest`] for how distances and ties are handled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::Interval, Position, Region, RegionIndex};
+    ///
+    /// let start = Position::try_from(8)?;
+    /// let end = Position::try_from(13)?;
+    ///
+    /// let index: RegionIndex<_> = [(Region::new("sq0", start..=end), "g0")]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let region = "sq0:21-34".parse()?;
+    /// assert_eq!(index.nearest(&region), Some((Interval::from(start..=end), &"g0", 7)));
+    ///
+    /// let region = "sq1:21-34".parse()?;
+    /// assert!(index.nearest(&region).is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn nearest(&self, region: &Region) -> Option<(Interval, &T, usize)> {
+        self.get(region.name())
+            .and_then(|tree| tree.nearest(region.interval()))
+    }
+}
+
+impl<T> Default for RegionIndex<T> {
+    fn default() -> Self {
+        Self {
+            trees: HashMap::new(),
+        }
+    }
+}
+
+impl<T> FromIterator<(Region, T)> for RegionIndex<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (Region, T)>,
+    {
+        let mut entries: HashMap<String, Vec<(Interval, T)>> = HashMap::new();
+
+        for (region, value) in iter {
+            let interval = region.interval();
+
+            entries
+                .entry(region.name().into())
+                .or_default()
+                .push((interval, value));
+        }
+
+        let trees = entries
+            .into_iter()
+            .map(|(name, entries)| (name, entries.into_iter().collect()))
+            .collect();
+
+        Self { trees }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Position;
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let index: RegionIndex<_> = [
+            (Region::new("sq0", Position::try_from(21)?..), 0),
+            (Region::new("sq1", ..=Position::try_from(8)?), 1),
+            (Region::new("sq0", ..=Position::try_from(5)?), 2),
+            (Region::new("sq0", ..), 3),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(index.len(), 4);
+
+        let t = |s: &str| -> Result<Vec<i32>, Box<dyn std::error::Error>> {
+            let region = s.parse()?;
+            Ok(index.query(&region).map(|(_, i)| *i).collect())
+        };
+
+        assert_eq!(t("sq0")?, [2, 3, 0]);
+        assert_eq!(t("sq0:8-13")?, [3]);
+        assert_eq!(t("sq0:34")?, [3, 0]);
+        assert_eq!(t("sq1:8-13")?, [1]);
+        assert!(t("sq2")?.is_empty());
+
+        Ok(())
+    }
+}
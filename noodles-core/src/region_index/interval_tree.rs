@@ -0,0 +1,411 @@
+//! Interval tree.
+
+use crate::{region::Interval, Position};
+
+struct Node<T> {
+    start: Position,
+    end: Position,
+    // The greatest end in the subtree rooted at this node.
+    max_end: Position,
+    value: T,
+}
+
+/// A static interval tree.
+///
+/// This stores values keyed by closed intervals on a single reference sequence. It is built from
+/// an iterator of intervals and values (see [`FromIterator`]), where unbounded starts and ends are
+/// resolved to [`Position::MIN`] and [`Position::MAX`], respectively.
+///
+/// The tree is stored as an implicit balanced binary search tree over the intervals sorted by
+/// start, augmented with the greatest end of each subtree.
+pub struct IntervalTree<T> {
+    nodes: Vec<Node<T>>,
+    // The index of the node with the greatest end in `nodes[..=i]`.
+    prefix_max_ends: Vec<usize>,
+}
+
+impl<T> IntervalTree<T> {
+    /// Returns the number of intervals in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region_index::IntervalTree, Position};
+    ///
+    /// let tree: IntervalTree<&str> = [(Position::try_from(8)?..=Position::try_from(13)?, "r0")]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// assert_eq!(tree.len(), 1);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns whether the tree has any intervals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::region_index::IntervalTree;
+    /// let tree: IntervalTree<()> = IntervalTree::default();
+    /// assert!(tree.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns an iterator over all intervals and values, sorted by interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::Interval, region_index::IntervalTree, Position};
+    ///
+    /// let a = Position::try_from(8)?..=Position::try_from(13)?;
+    /// let b = Position::try_from(5)?..=Position::try_from(21)?;
+    /// let tree: IntervalTree<_> = [(a.clone(), "r0"), (b.clone(), "r1")].into_iter().collect();
+    ///
+    /// let entries: Vec<_> = tree.iter().collect();
+    /// assert_eq!(entries, [(Interval::from(b), &"r1"), (Interval::from(a), &"r0")]);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (Interval, &T)> {
+        self.nodes
+            .iter()
+            .map(|node| (Interval::from(node.start..=node.end), &node.value))
+    }
+
+    /// Returns an iterator over the intervals and values that overlap the given interval.
+    ///
+    /// Overlapping intervals are returned sorted by interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region_index::IntervalTree, Position};
+    ///
+    /// let tree: IntervalTree<_> = [
+    ///     (Position::try_from(8)?..=Position::try_from(13)?, "r0"),
+    ///     (Position::try_from(21)?..=Position::try_from(34)?, "r1"),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let start = Position::try_from(13)?;
+    /// let end = Position::try_from(21)?;
+    /// let values: Vec<_> = tree.query(start..=end).map(|(_, value)| *value).collect();
+    /// assert_eq!(values, ["r0", "r1"]);
+    ///
+    /// let start = Position::try_from(55)?;
+    /// assert!(tree.query(start..).next().is_none());
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn query<I>(&self, interval: I) -> Query<'_, T>
+    where
+        I: Into<Interval>,
+    {
+        let (start, end) = resolve(interval.into());
+        Query::new(&self.nodes, start, end)
+    }
+
+    /// Returns the interval and value nearest to the given interval and its distance.
+    ///
+    /// The distance is the number of positions between the two intervals, i.e., overlapping and
+    /// adjacent intervals have a distance of 0. If there are overlapping intervals, the first one
+    /// is returned. Otherwise, ties are broken by preferring the preceding interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{region::Interval, region_index::IntervalTree, Position};
+    ///
+    /// let a = Position::try_from(8)?..=Position::try_from(13)?;
+    /// let b = Position::try_from(34)?..=Position::try_from(55)?;
+    /// let tree: IntervalTree<_> = [(a.clone(), "r0"), (b, "r1")].into_iter().collect();
+    ///
+    /// let start = Position::try_from(17)?;
+    /// let end = Position::try_from(21)?;
+    ///
+    /// assert_eq!(tree.nearest(start..=end), Some((Interval::from(a), &"r0", 3)));
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn nearest<I>(&self, interval: I) -> Option<(Interval, &T, usize)>
+    where
+        I: Into<Interval>,
+    {
+        let (start, end) = resolve(interval.into());
+
+        if let Some((interval, value)) = Query::new(&self.nodes, start, end).next() {
+            return Some((interval, value, 0));
+        }
+
+        // Because no intervals overlap, all intervals that start before the query interval also
+        // end before it.
+        let i = self.nodes.partition_point(|node| node.start < start);
+
+        let preceding = i.checked_sub(1).map(|j| {
+            let node = &self.nodes[self.prefix_max_ends[j]];
+            let distance = usize::from(start) - usize::from(node.end) - 1;
+            (node, distance)
+        });
+
+        let following = self.nodes.get(i).map(|node| {
+            let distance = usize::from(node.start) - usize::from(end) - 1;
+            (node, distance)
+        });
+
+        let (node, distance) = match (preceding, following) {
+            (Some(p), Some(f)) => {
+                if f.1 < p.1 {
+                    f
+                } else {
+                    p
+                }
+            }
+            (Some(p), None) => p,
+            (None, Some(f)) => f,
+            (None, None) => return None,
+        };
+
+        Some((Interval::from(node.start..=node.end), &node.value, distance))
+    }
+}
+
+impl<T> Default for IntervalTree<T> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            prefix_max_ends: Vec::new(),
+        }
+    }
+}
+
+impl<I, T> FromIterator<(I, T)> for IntervalTree<T>
+where
+    I: Into<Interval>,
+{
+    fn from_iter<J>(iter: J) -> Self
+    where
+        J: IntoIterator<Item = (I, T)>,
+    {
+        let mut nodes: Vec<_> = iter
+            .into_iter()
+            .map(|(interval, value)| {
+                let (start, end) = resolve(interval.into());
+
+                Node {
+                    start,
+                    end,
+                    max_end: end,
+                    value,
+                }
+            })
+            .collect();
+
+        nodes.sort_by_key(|node| (node.start, node.end));
+
+        index(&mut nodes);
+
+        let mut prefix_max_ends = Vec::with_capacity(nodes.len());
+        let mut max_end_index = 0;
+
+        for (i, node) in nodes.iter().enumerate() {
+            if node.end >= nodes[max_end_index].end {
+                max_end_index = i;
+            }
+
+            prefix_max_ends.push(max_end_index);
+        }
+
+        Self {
+            nodes,
+            prefix_max_ends,
+        }
+    }
+}
+
+/// An iterator over intervals and values that overlap an interval.
+///
+/// This is created by calling [`IntervalTree::query`].
+pub struct Query<'a, T> {
+    nodes: &'a [Node<T>],
+    start: Position,
+    end: Position,
+    // Ranges of nodes to visit. A range of a single node that is marked as visited is checked for
+    // overlap instead of expanded.
+    stack: Vec<(usize, usize, bool)>,
+}
+
+impl<'a, T> Query<'a, T> {
+    fn new(nodes: &'a [Node<T>], start: Position, end: Position) -> Self {
+        Self {
+            nodes,
+            start,
+            end,
+            stack: vec![(0, nodes.len(), false)],
+        }
+    }
+}
+
+impl<'a, T> Iterator for Query<'a, T> {
+    type Item = (Interval, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((lo, hi, is_visited)) = self.stack.pop() {
+            if is_visited {
+                let node = &self.nodes[lo];
+
+                if node.start <= self.end && self.start <= node.end {
+                    return Some((Interval::from(node.start..=node.end), &node.value));
+                }
+
+                continue;
+            }
+
+            if lo >= hi {
+                continue;
+            }
+
+            let mid = lo + (hi - lo) / 2;
+            let node = &self.nodes[mid];
+
+            if node.max_end < self.start {
+                continue;
+            }
+
+            // Visit the left subtree, this node, and then the right subtree. Nodes in the right
+            // subtree start after this node, so they are skipped if this node starts after the
+            // query interval.
+            if node.start <= self.end {
+                self.stack.push((mid + 1, hi, false));
+                self.stack.push((mid, mid + 1, true));
+            }
+
+            self.stack.push((lo, mid, false));
+        }
+
+        None
+    }
+}
+
+fn resolve(interval: Interval) -> (Position, Position) {
+    (
+        interval.start().unwrap_or(Position::MIN),
+        interval.end().unwrap_or(Position::MAX),
+    )
+}
+
+// Sets the greatest end of each subtree and returns the greatest end of the given nodes.
+fn index<T>(nodes: &mut [Node<T>]) -> Option<Position> {
+    if nodes.is_empty() {
+        return None;
+    }
+
+    let mid = nodes.len() / 2;
+    let (left, rest) = nodes.split_at_mut(mid);
+    let (node, right) = rest.split_first_mut()?;
+
+    let mut max_end = node.end;
+
+    for end in [index(left), index(right)].into_iter().flatten() {
+        max_end = max_end.max(end);
+    }
+
+    node.max_end = max_end;
+
+    Some(max_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(intervals: &[(usize, usize)]) -> IntervalTree<usize> {
+        intervals
+            .iter()
+            .enumerate()
+            .map(|(i, &(start, end))| {
+                let start = Position::new(start).unwrap();
+                let end = Position::new(end).unwrap();
+                (start..=end, i)
+            })
+            .collect()
+    }
+
+    fn query(tree: &IntervalTree<usize>, start: usize, end: usize) -> Vec<usize> {
+        let start = Position::new(start).unwrap();
+        let end = Position::new(end).unwrap();
+        tree.query(start..=end).map(|(_, i)| *i).collect()
+    }
+
+    #[test]
+    fn test_query() {
+        let intervals = [
+            (5, 21),
+            (8, 13),
+            (1, 3),
+            (34, 55),
+            (13, 13),
+            (21, 89),
+            (2, 2),
+            (40, 41),
+        ];
+
+        let tree = build(&intervals);
+
+        for start in 1..=100 {
+            for end in start..=100 {
+                let mut expected: Vec<_> = intervals
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &(s, e))| s <= end && start <= e)
+                    .map(|(i, &(s, e))| ((s, e), i))
+                    .collect();
+
+                expected.sort();
+
+                let expected: Vec<_> = expected.into_iter().map(|(_, i)| i).collect();
+
+                assert_eq!(query(&tree, start, end), expected, "{}-{}", start, end);
+            }
+        }
+
+        let tree = build(&[]);
+        assert!(query(&tree, 1, 8).is_empty());
+    }
+
+    #[test]
+    fn test_query_with_unbounded_interval() {
+        let tree = build(&[(5, 8), (13, 21)]);
+        let values: Vec<_> = tree.query(..).map(|(_, i)| *i).collect();
+        assert_eq!(values, [0, 1]);
+
+        let tree: IntervalTree<_> = [(Interval::from(..), 0)].into_iter().collect();
+        assert_eq!(query(&tree, 8, 13), [0]);
+    }
+
+    #[test]
+    fn test_nearest() {
+        fn t(tree: &IntervalTree<usize>, start: usize, end: usize) -> Option<(usize, usize)> {
+            let start = Position::new(start).unwrap();
+            let end = Position::new(end).unwrap();
+            tree.nearest(start..=end)
+                .map(|(_, i, distance)| (*i, distance))
+        }
+
+        let tree = build(&[(5, 21), (8, 13), (34, 55), (89, 144)]);
+
+        assert_eq!(t(&tree, 1, 2), Some((0, 2)));
+        assert_eq!(t(&tree, 1, 4), Some((0, 0)));
+        assert_eq!(t(&tree, 13, 13), Some((0, 0)));
+        assert_eq!(t(&tree, 22, 22), Some((0, 0)));
+        assert_eq!(t(&tree, 25, 27), Some((0, 3)));
+        assert_eq!(t(&tree, 27, 28), Some((0, 5)));
+        assert_eq!(t(&tree, 28, 29), Some((2, 4)));
+        assert_eq!(t(&tree, 200, 233), Some((3, 55)));
+
+        assert!(t(&build(&[]), 1, 8).is_none());
+    }
+}
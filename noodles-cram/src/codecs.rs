@@ -3,3 +3,26 @@ pub mod fqzcomp;
 pub mod name_tokenizer;
 pub mod rans;
 pub mod rans_nx16;
+
+use std::io;
+
+/// Returns the shortest of the given encoded outputs.
+///
+/// Ties are resolved in favor of the earliest output. If there are no candidates, the output is
+/// empty.
+pub(crate) fn smallest<I>(candidates: I) -> io::Result<Vec<u8>>
+where
+    I: IntoIterator<Item = io::Result<Vec<u8>>>,
+{
+    let mut dst: Option<Vec<u8>> = None;
+
+    for result in candidates {
+        let buf = result?;
+
+        if dst.as_ref().map(|b| buf.len() < b.len()).unwrap_or(true) {
+            dst = Some(buf);
+        }
+    }
+
+    Ok(dst.unwrap_or_default())
+}
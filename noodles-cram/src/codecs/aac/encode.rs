@@ -0,0 +1,353 @@
+use std::io::{self, Write};
+
+use byteorder::WriteBytesExt;
+
+use super::{Flags, Model, RangeCoder};
+use crate::{
+    codecs::rans_nx16::{encode_pack, write_pack_meta},
+    writer::num::write_uint7,
+};
+
+const STRIPE_COUNT: usize = 4;
+
+pub fn arith_encode(mut flags: Flags, src: &[u8]) -> io::Result<Vec<u8>> {
+    if src.is_empty() {
+        flags &= Flags::NO_SIZE;
+        flags |= Flags::CAT;
+    }
+
+    if flags.contains(Flags::STRIPE) {
+        return encode_stripe(flags, src);
+    }
+
+    let mut meta = Vec::new();
+    let mut data = src.to_vec();
+
+    if flags.contains(Flags::PACK) {
+        if let Some((p, packed_data)) = encode_pack(&data) {
+            write_pack_meta(&mut meta, &p, packed_data.len())?;
+            data = packed_data;
+        } else {
+            flags.remove(Flags::PACK);
+        }
+    }
+
+    if data.is_empty() {
+        flags.insert(Flags::CAT);
+    }
+
+    let body = if flags.contains(Flags::CAT) {
+        data
+    } else if flags.contains(Flags::EXT) {
+        encode_ext(&data)?
+    } else if flags.contains(Flags::RLE) {
+        if flags.contains(Flags::ORDER) {
+            encode_rle_1(&data)?
+        } else {
+            encode_rle_0(&data)?
+        }
+    } else if flags.contains(Flags::ORDER) {
+        encode_order_1(&data)?
+    } else {
+        encode_order_0(&data)?
+    };
+
+    let mut dst = Vec::with_capacity(meta.len() + body.len() + 6);
+
+    dst.write_u8(flags.bits())?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        write_len(&mut dst, src.len())?;
+    }
+
+    dst.extend(&meta);
+    dst.extend(&body);
+
+    Ok(dst)
+}
+
+fn write_len<W>(writer: &mut W, len: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    let n = u32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    write_uint7(writer, n)
+}
+
+fn encode_stripe(flags: Flags, src: &[u8]) -> io::Result<Vec<u8>> {
+    let chunk_flags = (flags - Flags::STRIPE) | Flags::NO_SIZE;
+
+    let chunks = (0..STRIPE_COUNT)
+        .map(|j| {
+            let chunk: Vec<_> = src.iter().skip(j).step_by(STRIPE_COUNT).copied().collect();
+
+            arith_encode(chunk_flags, &chunk)
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let mut dst = Vec::new();
+
+    dst.write_u8(flags.bits())?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        write_len(&mut dst, src.len())?;
+    }
+
+    dst.write_u8(STRIPE_COUNT as u8)?;
+
+    for chunk in &chunks {
+        write_len(&mut dst, chunk.len())?;
+    }
+
+    for chunk in &chunks {
+        dst.extend(chunk);
+    }
+
+    Ok(dst)
+}
+
+fn encode_ext(src: &[u8]) -> io::Result<Vec<u8>> {
+    use bzip2::write::BzEncoder;
+
+    let mut encoder = BzEncoder::new(Vec::new(), bzip2::Compression::best());
+    encoder.write_all(src)?;
+    encoder.finish()
+}
+
+fn write_max_sym<W>(writer: &mut W, src: &[u8]) -> io::Result<u8>
+where
+    W: Write,
+{
+    let max_sym = src.iter().copied().max().unwrap_or_default();
+    // A symbol count of 256 is written as 0.
+    writer.write_u8(max_sym.wrapping_add(1))?;
+    Ok(max_sym)
+}
+
+fn encode_rle_run<W>(
+    writer: &mut W,
+    range_coder: &mut RangeCoder,
+    model_run: &mut [Model],
+    sym: u8,
+    mut run: usize,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut rctx = usize::from(sym);
+
+    loop {
+        let part = run.min(3);
+        model_run[rctx].encode(writer, range_coder, part as u8)?;
+        run -= part;
+
+        if part < 3 {
+            break;
+        }
+
+        rctx = if rctx < 256 { 256 } else { 257 };
+    }
+
+    Ok(())
+}
+
+fn encode_rle_0(src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dst = Vec::new();
+
+    let max_sym = write_max_sym(&mut dst, src)?;
+
+    let mut model_lit = Model::new(max_sym);
+    let mut model_run = vec![Model::new(3); 258];
+
+    let mut range_coder = RangeCoder::default();
+
+    let mut i = 0;
+
+    while i < src.len() {
+        let b = src[i];
+        model_lit.encode(&mut dst, &mut range_coder, b)?;
+
+        let run = src[i + 1..].iter().take_while(|&&c| c == b).count();
+        encode_rle_run(&mut dst, &mut range_coder, &mut model_run, b, run)?;
+
+        i += run + 1;
+    }
+
+    range_coder.range_encode_end(&mut dst)?;
+
+    Ok(dst)
+}
+
+fn encode_rle_1(src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dst = Vec::new();
+
+    let max_sym = write_max_sym(&mut dst, src)?;
+
+    let mut model_lit = vec![Model::new(max_sym); usize::from(max_sym) + 1];
+    let mut model_run = vec![Model::new(3); 258];
+
+    let mut range_coder = RangeCoder::default();
+
+    let mut i = 0;
+    let mut last = 0;
+
+    while i < src.len() {
+        let b = src[i];
+        model_lit[last].encode(&mut dst, &mut range_coder, b)?;
+        last = usize::from(b);
+
+        let run = src[i + 1..].iter().take_while(|&&c| c == b).count();
+        encode_rle_run(&mut dst, &mut range_coder, &mut model_run, b, run)?;
+
+        i += run + 1;
+    }
+
+    range_coder.range_encode_end(&mut dst)?;
+
+    Ok(dst)
+}
+
+fn encode_order_0(src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dst = Vec::new();
+
+    let max_sym = write_max_sym(&mut dst, src)?;
+
+    let mut model = Model::new(max_sym);
+    let mut range_coder = RangeCoder::default();
+
+    for &b in src {
+        model.encode(&mut dst, &mut range_coder, b)?;
+    }
+
+    range_coder.range_encode_end(&mut dst)?;
+
+    Ok(dst)
+}
+
+fn encode_order_1(src: &[u8]) -> io::Result<Vec<u8>> {
+    let mut dst = Vec::new();
+
+    let max_sym = write_max_sym(&mut dst, src)?;
+
+    let mut models = vec![Model::new(max_sym); usize::from(max_sym) + 1];
+    let mut range_coder = RangeCoder::default();
+
+    let mut last = 0;
+
+    for &b in src {
+        models[last].encode(&mut dst, &mut range_coder, b)?;
+        last = usize::from(b);
+    }
+
+    range_coder.range_encode_end(&mut dst)?;
+
+    Ok(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codecs::aac::arith_decode;
+
+    fn t(flags: Flags, data: &[u8]) -> io::Result<()> {
+        let compressed_data = arith_encode(flags, data)?;
+
+        let mut reader = &compressed_data[..];
+        let actual = arith_decode(&mut reader, data.len())?;
+
+        assert_eq!(actual, data, "flags = {:?}", flags);
+
+        Ok(())
+    }
+
+    fn data() -> Vec<u8> {
+        b"noodles"
+            .iter()
+            .cycle()
+            .take(8191)
+            .enumerate()
+            .map(|(i, &b)| if i % 97 == 0 { 0xff } else { b })
+            .collect()
+    }
+
+    #[test]
+    fn test_arith_encode_order_0() -> io::Result<()> {
+        let actual = arith_encode(Flags::empty(), b"noodles")?;
+
+        let expected = [
+            0x00, // flags = {empty}
+            0x07, // uncompressed len = 7
+            0x74, 0x00, 0xf4, 0xe5, 0xb7, 0x4e, 0x50, 0x0f, 0x2e, 0x97, 0x00,
+        ];
+
+        assert_eq!(actual, expected);
+
+        t(Flags::empty(), &data())?;
+        t(Flags::NO_SIZE, &data())?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_arith_encode_order_1() -> io::Result<()> {
+        let actual = arith_encode(Flags::ORDER, b"noodles")?;
+
+        let expected = [
+            0x01, // flags = ORDER
+            0x07, // uncompressed len = 7
+            0x74, 0x00, 0xf4, 0xe3, 0x83, 0x41, 0xe2, 0x9a, 0xef, 0x53, 0x50, 0x00,
+        ];
+
+        assert_eq!(actual, expected);
+
+        t(Flags::ORDER, &data())?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_arith_encode_stripe() -> io::Result<()> {
+        t(Flags::STRIPE, b"noodles")?;
+        t(Flags::STRIPE | Flags::ORDER, &data())?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_arith_encode_uncompressed() -> io::Result<()> {
+        t(Flags::CAT, b"noodles")?;
+        t(Flags::empty(), b"")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_arith_encode_ext() -> io::Result<()> {
+        t(Flags::EXT, &data())
+    }
+
+    #[test]
+    fn test_arith_encode_rle() -> io::Result<()> {
+        let actual = arith_encode(Flags::RLE, b"noooooooodles")?;
+
+        let expected = [
+            0x40, // flags = RLE
+            0x0d, // uncompressed len = 13
+            0x74, 0x00, 0xf3, 0x4b, 0x21, 0x10, 0xa8, 0xe3, 0x84, 0xfe, 0x6b, 0x22, 0x00,
+        ];
+
+        assert_eq!(actual, expected);
+
+        t(Flags::RLE, &[0; 1024])?;
+        t(Flags::RLE | Flags::ORDER, b"noooooooodles")?;
+        t(Flags::RLE | Flags::ORDER, &data())?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_arith_encode_bit_packing() -> io::Result<()> {
+        t(Flags::PACK, b"nnnnnnn")?;
+        t(Flags::PACK, b"noodles")?;
+        t(Flags::PACK | Flags::ORDER, &data())?;
+        Ok(())
+    }
+}
@@ -1,11 +1,15 @@
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
-use byteorder::ReadBytesExt;
+use byteorder::{ReadBytesExt, WriteBytesExt};
 
 #[derive(Debug)]
 pub struct RangeCoder {
     range: u32,
     code: u32,
+    low: u32,
+    carry: u32,
+    cache: u32,
+    ff_num: u32,
 }
 
 impl RangeCoder {
@@ -43,6 +47,70 @@ impl RangeCoder {
 
         Ok(())
     }
+
+    pub fn range_encode<W>(
+        &mut self,
+        writer: &mut W,
+        sym_low: u32,
+        sym_freq: u32,
+        tot_freq: u32,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let old_low = self.low;
+
+        self.range /= tot_freq;
+        self.low = self.low.wrapping_add(sym_low * self.range);
+        self.range *= sym_freq;
+
+        if self.low < old_low {
+            self.carry += 1;
+        }
+
+        while self.range < (1 << 24) {
+            self.range <<= 8;
+            self.range_shift_low(writer)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn range_encode_end<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        for _ in 0..=4 {
+            self.range_shift_low(writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn range_shift_low<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        // A pending run of 0xff bytes can only be resolved once it is known whether a carry
+        // propagates into it.
+        if self.low < 0xff000000 || self.carry > 0 {
+            writer.write_u8(self.cache.wrapping_add(self.carry) as u8)?;
+
+            for _ in 0..self.ff_num {
+                writer.write_u8(self.carry.wrapping_sub(1) as u8)?;
+            }
+
+            self.ff_num = 0;
+            self.cache = self.low >> 24;
+            self.carry = 0;
+        } else {
+            self.ff_num += 1;
+        }
+
+        self.low <<= 8;
+
+        Ok(())
+    }
 }
 
 impl Default for RangeCoder {
@@ -50,6 +118,10 @@ impl Default for RangeCoder {
         Self {
             range: u32::MAX,
             code: 0,
+            low: 0,
+            carry: 0,
+            cache: 0,
+            ff_num: 0,
         }
     }
 }
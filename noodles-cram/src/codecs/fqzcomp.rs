@@ -1,6 +1,9 @@
+mod encode;
 mod parameter;
 mod parameters;
 
+pub use self::encode::fqz_encode;
+
 use std::{
     cmp,
     io::{self, Read},
@@ -255,7 +258,7 @@ where
 
     let mut a = vec![0; n];
 
-    let mut i = 0;
+    let mut i: u8 = 0;
     j = 0;
     z = 0;
 
@@ -277,7 +280,8 @@ where
             z += 1;
         }
 
-        i += 1;
+        // The last run may be for the symbol 255.
+        i = i.wrapping_add(1);
     }
 
     Ok(a)
@@ -0,0 +1,370 @@
+use std::{
+    cmp,
+    io::{self, Write},
+};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::{
+    fqz_create_models, fqz_update_context,
+    parameter::{self, Parameter},
+    parameters::{self, Parameters, VERSION},
+    Models, Record,
+};
+use crate::{codecs::aac::RangeCoder, writer::num::write_uint7};
+
+// The context is 16 bits wide: `Q_BITS_MAX` bits of quality history, followed by 4 bits of
+// position and 2 bits of delta.
+const Q_BITS_MAX: u8 = 10;
+const P_BITS: u8 = 4;
+
+/// Compresses quality scores using the fqzcomp codec.
+///
+/// `lens` are the lengths of each record in `src`.
+pub fn fqz_encode(lens: &[usize], src: &[u8]) -> io::Result<Vec<u8>> {
+    if lens.iter().sum::<usize>() != src.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "record lengths do not match quality scores length",
+        ));
+    }
+
+    // Empty records are skipped. They do not contribute to the output, and the decoder expects
+    // each record to have at least one quality score.
+    let lens: Vec<_> = lens.iter().copied().filter(|&len| len > 0).collect();
+
+    let mut dst = Vec::new();
+
+    let len =
+        u32::try_from(src.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    write_uint7(&mut dst, len)?;
+
+    let (param, sym_map) = build_parameter(&lens, src);
+
+    let mut params = Parameters {
+        gflags: parameters::Flags::empty(),
+        max_sel: 0,
+        s_tab: Vec::new(),
+        max_sym: param.max_sym,
+        params: vec![param],
+    };
+
+    write_parameters(&mut dst, &params)?;
+
+    let (mut range_coder, mut models) = fqz_create_models(&params);
+    let param = &mut params.params[0];
+
+    let mut record = Record::default();
+    let mut i = 0;
+
+    for &len in &lens {
+        if param.flags.contains(parameter::Flags::DO_LEN) || param.first_len > 0 {
+            encode_length(&mut dst, &mut range_coder, &mut models, len)?;
+            param.last_len = len;
+
+            if !param.flags.contains(parameter::Flags::DO_LEN) {
+                param.first_len = 0;
+            }
+        }
+
+        record.rec_len = len;
+        record.pos = len;
+        record.rec += 1;
+        record.qctx = 0;
+        record.delta = 0;
+        record.prevq = 0;
+
+        let mut ctx = param.context;
+
+        for &q in &src[i..i + len] {
+            let sym = sym_map[usize::from(q)];
+            models.qual[usize::from(ctx)].encode(&mut dst, &mut range_coder, sym)?;
+            ctx = fqz_update_context(param, sym, &mut record);
+            record.pos -= 1;
+        }
+
+        i += len;
+    }
+
+    range_coder.range_encode_end(&mut dst)?;
+
+    Ok(dst)
+}
+
+fn build_parameter(lens: &[usize], src: &[u8]) -> (Parameter, [u8; 256]) {
+    use parameter::Flags;
+
+    let mut is_present = [false; 256];
+
+    for &q in src {
+        is_present[usize::from(q)] = true;
+    }
+
+    let symbols: Vec<_> = (0..=u8::MAX)
+        .filter(|&q| is_present[usize::from(q)])
+        .collect();
+
+    let max_q = symbols.last().copied().unwrap_or_default();
+
+    let mut flags = Flags::HAVE_PTAB | Flags::HAVE_DTAB;
+
+    if lens.windows(2).any(|w| w[0] != w[1]) {
+        flags |= Flags::DO_LEN;
+    }
+
+    let mut sym_map = [0; 256];
+
+    // Sparse alphabets (e.g., binned quality scores) are mapped to a dense range of symbols.
+    let (max_sym, max_coded_sym, q_map) =
+        if !symbols.is_empty() && symbols.len() < usize::from(max_q) + 1 {
+            flags |= Flags::HAVE_QMAP;
+
+            for (i, &q) in symbols.iter().enumerate() {
+                sym_map[usize::from(q)] = i as u8;
+            }
+
+            let n = symbols.len() as u8;
+            (n, n - 1, symbols)
+        } else {
+            for (i, s) in sym_map.iter_mut().enumerate() {
+                *s = i as u8;
+            }
+
+            (max_q, max_q, vec![0; usize::from(max_q)])
+        };
+
+    let q_shift = cmp::max(1, (u8::BITS - max_coded_sym.leading_zeros()) as u8);
+    let q_bits = cmp::min(Q_BITS_MAX, q_shift * 3);
+
+    let max_len = lens.iter().copied().max().unwrap_or_default().min(1023);
+    let mut p_shift = 0;
+
+    while (max_len >> p_shift) >= (1 << P_BITS) {
+        p_shift += 1;
+    }
+
+    let p_tab = (0..1024)
+        .map(|i: usize| cmp::min((1 << P_BITS) - 1, i >> p_shift) as u8)
+        .collect();
+
+    let d_tab = (0..256)
+        .map(|i| match i {
+            0 => 0,
+            1..=3 => 1,
+            4..=8 => 2,
+            _ => 3,
+        })
+        .collect();
+
+    let param = Parameter {
+        context: 0,
+        flags,
+        max_sym,
+        first_len: 1,
+        last_len: 0,
+        q_bits,
+        q_shift,
+        q_loc: 0,
+        s_loc: 0,
+        p_loc: q_bits,
+        d_loc: q_bits + P_BITS,
+        q_map,
+        q_tab: (0..=u8::MAX).collect(),
+        p_tab,
+        d_tab,
+    };
+
+    (param, sym_map)
+}
+
+fn write_parameters<W>(writer: &mut W, parameters: &Parameters) -> io::Result<()>
+where
+    W: Write,
+{
+    use parameters::Flags;
+
+    writer.write_u8(VERSION)?;
+    writer.write_u8(parameters.gflags.bits())?;
+
+    if parameters.gflags.contains(Flags::MULTI_PARAM) {
+        writer.write_u8(parameters.params.len() as u8)?;
+    }
+
+    if parameters.gflags.contains(Flags::HAVE_S_TAB) {
+        writer.write_u8(parameters.max_sel)?;
+        write_array(writer, &parameters.s_tab)?;
+    }
+
+    for param in &parameters.params {
+        write_parameter(writer, param)?;
+    }
+
+    Ok(())
+}
+
+fn write_parameter<W>(writer: &mut W, param: &Parameter) -> io::Result<()>
+where
+    W: Write,
+{
+    use parameter::Flags;
+
+    writer.write_u16::<LittleEndian>(param.context)?;
+    writer.write_u8(param.flags.bits())?;
+    writer.write_u8(param.max_sym)?;
+    writer.write_u8(param.q_bits << 4 | param.q_shift)?;
+    writer.write_u8(param.q_loc << 4 | param.s_loc)?;
+    writer.write_u8(param.p_loc << 4 | param.d_loc)?;
+
+    if param.flags.contains(Flags::HAVE_QMAP) {
+        writer.write_all(&param.q_map)?;
+    }
+
+    if param.flags.contains(Flags::HAVE_QTAB) {
+        write_array(writer, &param.q_tab)?;
+    }
+
+    if param.flags.contains(Flags::HAVE_PTAB) {
+        write_array(writer, &param.p_tab)?;
+    }
+
+    if param.flags.contains(Flags::HAVE_DTAB) {
+        write_array(writer, &param.d_tab)?;
+    }
+
+    Ok(())
+}
+
+fn encode_length<W>(
+    writer: &mut W,
+    range_coder: &mut RangeCoder,
+    models: &mut Models,
+    len: usize,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let n = u32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    for (i, model) in models.len.iter_mut().enumerate() {
+        let b = (n >> (8 * i)) as u8;
+        model.encode(writer, range_coder, b)?;
+    }
+
+    Ok(())
+}
+
+// `a` must be a nondecreasing array of consecutive values starting at 0.
+fn write_array<W>(writer: &mut W, a: &[u8]) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut runs = Vec::new();
+    let (mut i, mut z) = (0, 0);
+    let mut value = 0;
+
+    while i < a.len() {
+        let start = i;
+
+        while i < a.len() && usize::from(a[i]) == value {
+            i += 1;
+        }
+
+        let mut run_len = i - start;
+
+        loop {
+            let part = cmp::min(run_len, 255);
+            runs.push(part as u8);
+            run_len -= part;
+            z += part;
+
+            if part != 255 || z == a.len() {
+                break;
+            }
+        }
+
+        value += 1;
+    }
+
+    let mut last = 0;
+    let mut j = 0;
+
+    while j < runs.len() {
+        let run = runs[j];
+        writer.write_u8(run)?;
+        j += 1;
+
+        if run == last {
+            let copy = runs[j..]
+                .iter()
+                .take_while(|&&r| r == run)
+                .take(255)
+                .count();
+            writer.write_u8(copy as u8)?;
+            j += copy;
+        }
+
+        last = run;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codecs::fqzcomp::{fqz_decode, read_array};
+
+    #[test]
+    fn test_fqz_encode() -> io::Result<()> {
+        fn t(lens: &[usize], data: &[u8]) -> io::Result<()> {
+            let compressed_data = fqz_encode(lens, data)?;
+            let mut reader = &compressed_data[..];
+            assert_eq!(fqz_decode(&mut reader)?, data);
+            Ok(())
+        }
+
+        let data = b"noodles".map(|b| b - b'!');
+        t(&[data.len()], &data)?;
+
+        let data: Vec<_> = (0..4096).map(|i| ((i * 7) % 41) as u8).collect();
+        t(&[1024, 1024, 1024, 1024], &data)?;
+        t(&[100, 2000, 1, 0, 1995], &data)?;
+
+        let data: Vec<_> = (0..4096).map(|i| [2, 12, 23, 37][i % 4]).collect();
+        t(&[151; 4096 / 151], &data[..4096 / 151 * 151])?;
+
+        t(&[], &[])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fqz_encode_with_mismatched_lengths() {
+        assert!(matches!(
+            fqz_encode(&[2], b"ndls"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn test_write_array() -> io::Result<()> {
+        fn t(a: &[u8]) -> io::Result<()> {
+            let mut buf = Vec::new();
+            write_array(&mut buf, a)?;
+
+            let mut reader = &buf[..];
+            assert_eq!(read_array(&mut reader, a.len())?, a);
+            assert!(reader.is_empty());
+
+            Ok(())
+        }
+
+        t(&(0..=u8::MAX).collect::<Vec<_>>())?;
+        t(&[0; 256])?;
+        t(&(0..1024).map(|i| (i >> 6) as u8).collect::<Vec<_>>())?;
+        t(&(0..1024).map(|i| (i / 255) as u8).collect::<Vec<_>>())?;
+        t(&(0..1024).map(|i| (i / 510) as u8).collect::<Vec<_>>())?;
+
+        Ok(())
+    }
+}
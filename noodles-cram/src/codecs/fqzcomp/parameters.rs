@@ -11,7 +11,7 @@ use super::{
     read_array,
 };
 
-const VERSION: u8 = 5;
+pub(super) const VERSION: u8 = 5;
 
 pub struct Parameters {
     pub gflags: Flags,
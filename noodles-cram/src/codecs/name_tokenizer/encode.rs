@@ -0,0 +1,384 @@
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::{Token, Type};
+use crate::{
+    codecs::{aac, rans_nx16, smallest},
+    writer::num::write_uint7,
+};
+
+// The decoder keeps at most 128 tokens per name, including the leading distance token and the
+// trailing end token.
+const MAX_TOKEN_COUNT: usize = 126;
+
+const TYPE_COUNT: usize = 13;
+
+const NUL: u8 = 0x00;
+
+/// Compresses NUL-terminated read names using the name tokenization codec.
+pub fn encode_names(src: &[u8], use_arith: bool) -> io::Result<Vec<u8>> {
+    let names: Vec<_> = match src.split_last() {
+        Some((&NUL, buf)) => buf.split(|&b| b == NUL).collect(),
+        Some(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "missing name NUL terminator",
+            ))
+        }
+        None => Vec::new(),
+    };
+
+    let tokens = names
+        .iter()
+        .map(|name| tokenize(name))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let mut streams = vec![TokenStreams::default()];
+
+    for (i, (name, name_tokens)) in names.iter().zip(&tokens).enumerate() {
+        if i > 0 && *name == names[i - 1] {
+            streams[0].write_type(Type::Dup);
+            streams[0].get_mut(Type::Dup).write_u32::<LittleEndian>(1)?;
+            continue;
+        }
+
+        let (dist, prev_tokens) = if i > 0 {
+            (1, Some(&tokens[i - 1]))
+        } else {
+            (0, None)
+        };
+
+        streams[0].write_type(Type::Diff);
+        streams[0]
+            .get_mut(Type::Diff)
+            .write_u32::<LittleEndian>(dist)?;
+
+        for (t, token) in name_tokens.iter().enumerate() {
+            if streams.len() <= t + 1 {
+                streams.push(TokenStreams::default());
+            }
+
+            let prev_token = prev_tokens.and_then(|tokens| tokens.get(t));
+            streams[t + 1].write_token(token, prev_token)?;
+        }
+
+        let t = name_tokens.len() + 1;
+
+        if streams.len() <= t {
+            streams.push(TokenStreams::default());
+        }
+
+        streams[t].write_type(Type::End);
+    }
+
+    let mut dst = Vec::new();
+
+    let ulen =
+        u32::try_from(src.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    dst.write_u32::<LittleEndian>(ulen)?;
+
+    let n_names =
+        u32::try_from(names.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    dst.write_u32::<LittleEndian>(n_names)?;
+
+    dst.write_u8(u8::from(use_arith))?;
+
+    for token_streams in &streams {
+        write_token_streams(&mut dst, token_streams, use_arith)?;
+    }
+
+    Ok(dst)
+}
+
+fn tokenize(name: &[u8]) -> io::Result<Vec<Token>> {
+    fn build_string(buf: &[u8]) -> String {
+        buf.iter().copied().map(char::from).collect()
+    }
+
+    if !name.is_ascii() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid name: expected ASCII characters",
+        ));
+    }
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < name.len() {
+        if tokens.len() == MAX_TOKEN_COUNT - 1 {
+            tokens.push(Token::String(build_string(&name[i..])));
+            break;
+        }
+
+        let b = name[i];
+
+        let len = if b.is_ascii_digit() {
+            name[i..].iter().take_while(|b| b.is_ascii_digit()).count()
+        } else if b.is_ascii_alphabetic() {
+            name[i..]
+                .iter()
+                .take_while(|b| b.is_ascii_alphabetic())
+                .count()
+        } else {
+            1
+        };
+
+        let buf = &name[i..i + len];
+
+        // Digit runs longer than 9 digits may not fit in a u32 and are stored as strings.
+        let token = if b.is_ascii_digit() && len <= 9 {
+            let n = buf.iter().fold(0, |n, &b| n * 10 + u32::from(b - b'0'));
+
+            if b == b'0' && len > 1 {
+                Token::PaddedDigits(n, len as u8)
+            } else {
+                Token::Digits(n)
+            }
+        } else if b.is_ascii_digit() || b.is_ascii_alphabetic() {
+            Token::String(build_string(buf))
+        } else {
+            Token::Char(char::from(b))
+        };
+
+        tokens.push(token);
+
+        i += len;
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug)]
+struct TokenStreams(Vec<Vec<u8>>);
+
+impl Default for TokenStreams {
+    fn default() -> Self {
+        Self(vec![Vec::new(); TYPE_COUNT])
+    }
+}
+
+impl TokenStreams {
+    fn get(&self, ty: Type) -> &[u8] {
+        &self.0[usize::from(ty)]
+    }
+
+    fn get_mut(&mut self, ty: Type) -> &mut Vec<u8> {
+        &mut self.0[usize::from(ty)]
+    }
+
+    fn write_type(&mut self, ty: Type) {
+        self.get_mut(Type::Type).push(u8::from(ty));
+    }
+
+    fn write_token(&mut self, token: &Token, prev_token: Option<&Token>) -> io::Result<()> {
+        match (token, prev_token) {
+            (_, Some(prev_token)) if token == prev_token => {
+                self.write_type(Type::Match);
+            }
+            (Token::Digits(n), Some(Token::Digits(m))) if n > m && n - m <= 255 => {
+                self.write_type(Type::Delta);
+                self.get_mut(Type::Delta).push((n - m) as u8);
+            }
+            (Token::PaddedDigits(n, width), Some(Token::PaddedDigits(m, prev_width)))
+                if width == prev_width && n > m && n - m <= 255 =>
+            {
+                self.write_type(Type::Delta0);
+                self.get_mut(Type::Delta0).push((n - m) as u8);
+            }
+            (Token::Char(c), _) => {
+                self.write_type(Type::Char);
+                self.get_mut(Type::Char).push(*c as u8);
+            }
+            (Token::String(s), _) => {
+                self.write_type(Type::String);
+
+                let buf = self.get_mut(Type::String);
+                buf.extend(s.as_bytes());
+                buf.push(NUL);
+            }
+            (Token::Digits(n), _) => {
+                self.write_type(Type::Digits);
+                self.get_mut(Type::Digits).write_u32::<LittleEndian>(*n)?;
+            }
+            (Token::PaddedDigits(n, width), _) => {
+                self.write_type(Type::Digits0);
+                self.get_mut(Type::Digits0).write_u32::<LittleEndian>(*n)?;
+                self.get_mut(Type::DZLen).push(*width);
+            }
+            (Token::Nop, _) => self.write_type(Type::Nop),
+        }
+
+        Ok(())
+    }
+}
+
+fn write_token_streams<W>(
+    writer: &mut W,
+    token_streams: &TokenStreams,
+    use_arith: bool,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    const TOKEN_NEW: u8 = 0x80;
+
+    const TYPES: [Type; 9] = [
+        Type::String,
+        Type::Char,
+        Type::Digits0,
+        Type::DZLen,
+        Type::Dup,
+        Type::Diff,
+        Type::Digits,
+        Type::Delta,
+        Type::Delta0,
+    ];
+
+    write_token_stream(
+        writer,
+        TOKEN_NEW | u8::from(Type::Type),
+        token_streams.get(Type::Type),
+        use_arith,
+    )?;
+
+    for &ty in &TYPES {
+        let buf = token_streams.get(ty);
+
+        if !buf.is_empty() {
+            write_token_stream(writer, u8::from(ty), buf, use_arith)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_token_stream<W>(writer: &mut W, ttype: u8, src: &[u8], use_arith: bool) -> io::Result<()>
+where
+    W: Write,
+{
+    let buf = if use_arith {
+        use aac::{arith_encode, Flags};
+
+        smallest(
+            [
+                Flags::empty(),
+                Flags::ORDER,
+                Flags::RLE,
+                Flags::ORDER | Flags::RLE,
+                Flags::CAT,
+            ]
+            .iter()
+            .map(|&flags| arith_encode(flags, src)),
+        )?
+    } else {
+        use rans_nx16::{rans_encode_nx16, Flags};
+
+        smallest(
+            [
+                Flags::empty(),
+                Flags::ORDER,
+                Flags::PACK,
+                Flags::RLE,
+                Flags::CAT,
+            ]
+            .iter()
+            .map(|&flags| rans_encode_nx16(flags, src)),
+        )?
+    };
+
+    writer.write_u8(ttype)?;
+
+    let clen =
+        u32::try_from(buf.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    write_uint7(writer, clen)?;
+
+    writer.write_all(&buf)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codecs::name_tokenizer::decode_names;
+
+    fn t(names: &[&str], use_arith: bool) -> io::Result<()> {
+        let mut src = Vec::new();
+
+        for name in names {
+            src.extend(name.as_bytes());
+            src.push(NUL);
+        }
+
+        let compressed_data = encode_names(&src, use_arith)?;
+
+        let mut reader = &compressed_data[..];
+        let actual = decode_names(&mut reader)?;
+
+        assert_eq!(actual, names);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_names() -> io::Result<()> {
+        let names = [
+            "I17_08765:2:123:61541:01763#9",
+            "I17_08765:2:123:1636:08611#9",
+            "I17_08765:2:124:45613:16161#9",
+            "I17_08765:2:124:45613:16161#9",
+            "I17_08765:2:124:45614:16162#9",
+            "I17_08765:2:124:45614:16162",
+            "r0",
+            "",
+            "r000000000000001",
+            "r00007",
+            "r00009",
+            "r10",
+        ];
+
+        t(&names, false)?;
+        t(&names, true)?;
+        t(&[], false)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_names_with_many_tokens() -> io::Result<()> {
+        let name = "a:".repeat(100);
+        t(&[&name, &name[1..], &name], false)
+    }
+
+    #[test]
+    fn test_encode_names_with_invalid_input() {
+        assert!(matches!(
+            encode_names(b"r0", false),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            encode_names("r\u{e9}\0".as_bytes(), false),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn test_tokenize() -> io::Result<()> {
+        assert_eq!(
+            tokenize(b"I17_08765:2")?,
+            [
+                Token::String(String::from("I")),
+                Token::Digits(17),
+                Token::Char('_'),
+                Token::PaddedDigits(8765, 5),
+                Token::Char(':'),
+                Token::Digits(2),
+            ]
+        );
+
+        Ok(())
+    }
+}
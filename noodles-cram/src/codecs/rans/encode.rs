@@ -1,7 +1,10 @@
 mod order_0;
 mod order_1;
 
-use std::io::{self, Write};
+use std::{
+    cmp,
+    io::{self, Write},
+};
 
 use byteorder::{LittleEndian, WriteBytesExt};
 
@@ -13,7 +16,6 @@ const BASE: usize = 256;
 // Lower bound `L`.
 const LOWER_BOUND: u32 = 0x800000;
 
-#[allow(dead_code)]
 pub fn rans_encode(order: Order, data: &[u8]) -> io::Result<Vec<u8>> {
     let compressed_blob = match order {
         Order::Zero => {
@@ -89,7 +91,14 @@ fn normalize_frequencies(frequencies: &[u32]) -> Vec<u32> {
     let mut normalized_frequencies = vec![0; frequencies.len()];
 
     for (i, &f) in frequencies.iter().enumerate() {
-        let normalized_frequency = f * SCALE / sum;
+        if f == 0 {
+            continue;
+        }
+
+        // A symbol that is present must keep a nonzero frequency to be encodable.
+        let normalized_frequency =
+            cmp::max(1, (u64::from(f) * u64::from(SCALE) / u64::from(sum)) as u32);
+
         normalized_frequencies[i] = normalized_frequency;
         normalized_sum += normalized_frequency;
     }
@@ -101,6 +110,19 @@ fn normalize_frequencies(frequencies: &[u32]) -> Vec<u32> {
         normalized_frequencies[max_index] += SCALE - normalized_sum;
     }
 
+    // Conversely, rounding rare symbols up can overshoot the scale value, in which case the
+    // largest frequencies are reduced.
+    while normalized_sum > SCALE {
+        let (i, _) = normalized_frequencies
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, &f)| f)
+            .unwrap();
+
+        normalized_frequencies[i] -= 1;
+        normalized_sum -= 1;
+    }
+
     normalized_frequencies
 }
 
@@ -170,6 +192,29 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_rans_encode_round_trip() -> io::Result<()> {
+        use crate::codecs::rans::rans_decode;
+
+        fn t(order: Order, data: &[u8]) -> io::Result<()> {
+            let compressed_data = rans_encode(order, data)?;
+            let mut reader = &compressed_data[..];
+            assert_eq!(rans_decode(&mut reader)?, data, "order = {:?}", order);
+            Ok(())
+        }
+
+        t(Order::Zero, &[])?;
+
+        for order in [Order::Zero, Order::One] {
+            for len in [4, 148, 1021] {
+                let data: Vec<_> = (0..len).map(|i| ((i * 7919 + i * i) % 10) as u8).collect();
+                t(order, &data)?;
+            }
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_write_header() -> io::Result<()> {
         let mut writer = Vec::new();
@@ -12,7 +12,13 @@ use super::{
 };
 
 pub fn encode(data: &[u8]) -> io::Result<(Vec<u32>, Vec<u8>)> {
-    let frequencies = build_frequencies(data, BASE);
+    let mut frequencies = build_frequencies(data, BASE);
+
+    // The decoder always reads at least one symbol frequency, so an empty input is given a
+    // frequency table with a single symbol.
+    if data.is_empty() {
+        frequencies[0] = 1;
+    }
 
     let freq = normalize_frequencies(&frequencies);
     let cfreq = build_cumulative_frequencies(&freq);
@@ -124,7 +130,13 @@ mod tests {
     #[test]
     fn test_write_frequencies() -> io::Result<()> {
         let data = b"abracadabra";
-        let frequencies = build_frequencies(data, BASE);
+        let mut frequencies = build_frequencies(data, BASE);
+
+        // The decoder always reads at least one symbol frequency, so an empty input is given a
+        // frequency table with a single symbol.
+        if data.is_empty() {
+            frequencies[0] = 1;
+        }
         let normalized_frequencies = normalize_frequencies(&frequencies);
 
         let mut writer = Vec::new();
@@ -1,11 +1,15 @@
+mod encode;
 mod flags;
 
-use std::io::{self, Cursor, Read};
+pub use self::{
+    encode::{encode_pack, rans_encode_nx16, write_pack_meta},
+    flags::Flags,
+};
 
-use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{self, Cursor, Read};
 
-use self::flags::Flags;
 use crate::reader::num::read_uint7;
+use byteorder::{LittleEndian, ReadBytesExt};
 
 pub fn rans_decode_nx16<R>(reader: &mut R, mut len: usize) -> io::Result<Vec<u8>>
 where
@@ -0,0 +1,647 @@
+use std::{
+    cmp,
+    io::{self, Write},
+};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::Flags;
+use crate::writer::num::write_uint7;
+
+// Lower bound `L` of the rANS state.
+const LOWER_BOUND: u32 = 1 << 15;
+
+pub fn rans_encode_nx16(mut flags: Flags, src: &[u8]) -> io::Result<Vec<u8>> {
+    if src.is_empty() {
+        flags &= Flags::NO_SIZE;
+        flags |= Flags::CAT;
+    }
+
+    if flags.contains(Flags::STRIPE) {
+        return encode_stripe(flags, src);
+    }
+
+    let n = state_count(flags);
+
+    let mut meta = Vec::new();
+    let mut data = src.to_vec();
+
+    if flags.contains(Flags::PACK) {
+        if let Some((p, packed_data)) = encode_pack(&data) {
+            write_pack_meta(&mut meta, &p, packed_data.len())?;
+            data = packed_data;
+        } else {
+            flags.remove(Flags::PACK);
+        }
+    }
+
+    if data.is_empty() {
+        flags.remove(Flags::RLE);
+        flags.insert(Flags::CAT);
+    }
+
+    if flags.contains(Flags::RLE) {
+        let (rle_meta, literals) = encode_rle(&data);
+        write_rle_meta(&mut meta, &rle_meta, literals.len(), n)?;
+        data = literals;
+    }
+
+    let body = if flags.contains(Flags::CAT) {
+        data
+    } else if flags.contains(Flags::ORDER) {
+        rans_encode_nx16_1(&data, n)?
+    } else {
+        rans_encode_nx16_0(&data, n)?
+    };
+
+    let mut dst = Vec::with_capacity(meta.len() + body.len() + 6);
+
+    dst.write_u8(flags.bits())?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        write_len(&mut dst, src.len())?;
+    }
+
+    dst.extend(&meta);
+    dst.extend(&body);
+
+    Ok(dst)
+}
+
+fn state_count(flags: Flags) -> usize {
+    if flags.contains(Flags::N32) {
+        32
+    } else {
+        4
+    }
+}
+
+fn write_len<W>(writer: &mut W, len: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    let n = u32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    write_uint7(writer, n)
+}
+
+fn write_alphabet<W>(writer: &mut W, alphabet: &[bool; 256]) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut rle = 0;
+
+    for (i, &is_present) in alphabet.iter().enumerate() {
+        if !is_present {
+            continue;
+        }
+
+        if rle > 0 {
+            rle -= 1;
+            continue;
+        }
+
+        writer.write_u8(i as u8)?;
+
+        if i > 0 && alphabet[i - 1] {
+            rle = alphabet[i + 1..].iter().take_while(|&&b| b).count();
+            writer.write_u8(rle as u8)?;
+        }
+    }
+
+    writer.write_u8(0x00)?;
+
+    Ok(())
+}
+
+fn normalize_frequencies(frequencies: &[u32], bits: u32) -> Vec<u32> {
+    let scale = 1 << bits;
+    let sum: u64 = frequencies.iter().copied().map(u64::from).sum();
+
+    let mut normalized_frequencies = vec![0; frequencies.len()];
+
+    if sum == 0 {
+        return normalized_frequencies;
+    }
+
+    let mut normalized_sum = 0;
+    let mut max_index = 0;
+
+    for (i, &f) in frequencies.iter().enumerate() {
+        if f == 0 {
+            continue;
+        }
+
+        // Every symbol that is present must keep a nonzero frequency.
+        let g = cmp::max(1, (u64::from(f) * u64::from(scale) / sum) as u32);
+
+        if g > normalized_frequencies[max_index] {
+            max_index = i;
+        }
+
+        normalized_frequencies[i] = g;
+        normalized_sum += g;
+    }
+
+    if normalized_sum < scale {
+        normalized_frequencies[max_index] += scale - normalized_sum;
+    }
+
+    while normalized_sum > scale {
+        let (i, _) = normalized_frequencies
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, &f)| f)
+            .unwrap();
+
+        normalized_frequencies[i] -= 1;
+        normalized_sum -= 1;
+    }
+
+    normalized_frequencies
+}
+
+fn build_cumulative_frequencies(frequencies: &[u32]) -> Vec<u32> {
+    let mut cumulative_frequencies = vec![0; frequencies.len()];
+
+    for i in 0..frequencies.len() - 1 {
+        cumulative_frequencies[i + 1] = cumulative_frequencies[i] + frequencies[i];
+    }
+
+    cumulative_frequencies
+}
+
+fn rans_encode_symbol(words: &mut Vec<u16>, mut r: u32, c: u32, f: u32, bits: u32) -> u32 {
+    let r_max = ((LOWER_BOUND >> bits) << 16) * f;
+
+    if r >= r_max {
+        words.push(r as u16);
+        r >>= 16;
+    }
+
+    ((r / f) << bits) + (r % f) + c
+}
+
+fn write_states_and_words<W>(writer: &mut W, states: &[u32], words: &[u16]) -> io::Result<()>
+where
+    W: Write,
+{
+    for &s in states {
+        writer.write_u32::<LittleEndian>(s)?;
+    }
+
+    // Words are emitted in reverse while encoding; the decoder reads them forward.
+    for &w in words.iter().rev() {
+        writer.write_u16::<LittleEndian>(w)?;
+    }
+
+    Ok(())
+}
+
+fn write_frequencies_nx16_0<W>(writer: &mut W, freqs: &[u32]) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut alphabet = [false; 256];
+
+    for (a, &f) in alphabet.iter_mut().zip(freqs) {
+        *a = f > 0;
+    }
+
+    write_alphabet(writer, &alphabet)?;
+
+    for &f in freqs.iter().filter(|&&f| f > 0) {
+        write_uint7(writer, f)?;
+    }
+
+    Ok(())
+}
+
+fn rans_encode_nx16_0(src: &[u8], n: usize) -> io::Result<Vec<u8>> {
+    const BITS: u32 = 12;
+
+    let mut frequencies = [0; 256];
+
+    for &b in src {
+        frequencies[usize::from(b)] += 1;
+    }
+
+    let freqs = normalize_frequencies(&frequencies, BITS);
+    let cumulative_freqs = build_cumulative_frequencies(&freqs);
+
+    let mut dst = Vec::new();
+    write_frequencies_nx16_0(&mut dst, &freqs)?;
+
+    let mut states = vec![LOWER_BOUND; n];
+    let mut words = Vec::new();
+
+    for (i, &sym) in src.iter().enumerate().rev() {
+        let j = i % n;
+        let s = usize::from(sym);
+        states[j] = rans_encode_symbol(&mut words, states[j], cumulative_freqs[s], freqs[s], BITS);
+    }
+
+    write_states_and_words(&mut dst, &states, &words)?;
+
+    Ok(dst)
+}
+
+fn write_frequencies_nx16_1<W>(
+    writer: &mut W,
+    alphabet: &[bool; 256],
+    freqs: &[Vec<u32>],
+    bits: u32,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut buf = Vec::new();
+    write_alphabet(&mut buf, alphabet)?;
+
+    let symbols: Vec<_> = (0..alphabet.len()).filter(|&i| alphabet[i]).collect();
+
+    for &i in &symbols {
+        let mut run = 0;
+
+        for (k, &j) in symbols.iter().enumerate() {
+            if run > 0 {
+                run -= 1;
+                continue;
+            }
+
+            let f = freqs[i][j];
+            write_uint7(&mut buf, f)?;
+
+            if f == 0 {
+                run = symbols[k + 1..]
+                    .iter()
+                    .take_while(|&&j| freqs[i][j] == 0)
+                    .take(255)
+                    .count();
+
+                buf.write_u8(run as u8)?;
+            }
+        }
+    }
+
+    let compressed_buf = rans_encode_nx16_0(&buf, 4)?;
+    let comp = (bits as u8) << 4;
+
+    if compressed_buf.len() < buf.len() {
+        writer.write_u8(comp | 0x01)?;
+        write_len(writer, buf.len())?;
+        write_len(writer, compressed_buf.len())?;
+        writer.write_all(&compressed_buf)?;
+    } else {
+        writer.write_u8(comp)?;
+        writer.write_all(&buf)?;
+    }
+
+    Ok(())
+}
+
+fn rans_encode_nx16_1(src: &[u8], n: usize) -> io::Result<Vec<u8>> {
+    const BITS: u32 = 12;
+
+    // Each state encodes a contiguous segment of the input. The last state also handles the
+    // remainder.
+    let segment_len = src.len() / n;
+
+    let segment_start = |j: usize| j * segment_len;
+    let context = |i: usize| {
+        if i == 0 || (i % segment_len.max(1) == 0 && i < n * segment_len) {
+            0
+        } else {
+            usize::from(src[i - 1])
+        }
+    };
+
+    let mut alphabet = [false; 256];
+    alphabet[0] = true;
+
+    let mut frequencies = vec![vec![0; 256]; 256];
+
+    for (i, &b) in src.iter().enumerate() {
+        alphabet[usize::from(b)] = true;
+        frequencies[context(i)][usize::from(b)] += 1;
+    }
+
+    let freqs: Vec<_> = frequencies
+        .iter()
+        .map(|f| normalize_frequencies(f, BITS))
+        .collect();
+
+    let cumulative_freqs: Vec<_> = freqs
+        .iter()
+        .map(|f| build_cumulative_frequencies(f))
+        .collect();
+
+    let mut dst = Vec::new();
+    write_frequencies_nx16_1(&mut dst, &alphabet, &freqs, BITS)?;
+
+    let mut states = vec![LOWER_BOUND; n];
+    let mut words = Vec::new();
+
+    let m = n - 1;
+
+    for i in (n * segment_len..src.len()).rev() {
+        let ctx = context(i);
+        let s = usize::from(src[i]);
+
+        states[m] = rans_encode_symbol(
+            &mut words,
+            states[m],
+            cumulative_freqs[ctx][s],
+            freqs[ctx][s],
+            BITS,
+        );
+    }
+
+    for i in (0..segment_len).rev() {
+        for j in (0..n).rev() {
+            let k = segment_start(j) + i;
+            let ctx = context(k);
+            let s = usize::from(src[k]);
+
+            states[j] = rans_encode_symbol(
+                &mut words,
+                states[j],
+                cumulative_freqs[ctx][s],
+                freqs[ctx][s],
+                BITS,
+            );
+        }
+    }
+
+    write_states_and_words(&mut dst, &states, &words)?;
+
+    Ok(dst)
+}
+
+fn encode_stripe(flags: Flags, src: &[u8]) -> io::Result<Vec<u8>> {
+    // The number of stripes matches the number of states.
+    let x = state_count(flags);
+
+    let chunk_flags = (flags - Flags::STRIPE - Flags::N32) | Flags::NO_SIZE;
+
+    let chunks = (0..x)
+        .map(|j| {
+            let chunk: Vec<_> = src.iter().skip(j).step_by(x).copied().collect();
+            rans_encode_nx16(chunk_flags, &chunk)
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let mut dst = Vec::new();
+
+    dst.write_u8(flags.bits())?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        write_len(&mut dst, src.len())?;
+    }
+
+    dst.write_u8(x as u8)?;
+
+    for chunk in &chunks {
+        write_len(&mut dst, chunk.len())?;
+    }
+
+    for chunk in &chunks {
+        dst.extend(chunk);
+    }
+
+    Ok(dst)
+}
+
+fn encode_rle(src: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    // A symbol is worth run-length encoding when it repeats more often than it starts a new
+    // run.
+    let mut scores = [0i64; 256];
+    let mut last_sym = None;
+
+    for &b in src {
+        if last_sym == Some(b) {
+            scores[usize::from(b)] += 1;
+        } else {
+            scores[usize::from(b)] -= 1;
+            last_sym = Some(b);
+        }
+    }
+
+    let mut is_rle_sym = [false; 256];
+
+    for (is_rle, &score) in is_rle_sym.iter_mut().zip(scores.iter()) {
+        *is_rle = score > 0;
+    }
+
+    // At least one symbol must be listed.
+    if !is_rle_sym.iter().any(|&b| b) {
+        let i = src[0];
+        is_rle_sym[usize::from(i)] = true;
+    }
+
+    let symbols: Vec<_> = (0..=u8::MAX)
+        .filter(|&b| is_rle_sym[usize::from(b)])
+        .collect();
+
+    let mut meta = Vec::new();
+    // A symbol count of 256 is written as 0.
+    meta.push(symbols.len() as u8);
+    meta.extend(&symbols);
+
+    let mut literals = Vec::new();
+    let mut i = 0;
+
+    while i < src.len() {
+        let sym = src[i];
+        literals.push(sym);
+
+        if is_rle_sym[usize::from(sym)] {
+            let run = src[i + 1..].iter().take_while(|&&b| b == sym).count();
+            // `run` is bounded by the input length, which is already checked to fit in a u32.
+            write_uint7(&mut meta, run as u32).expect("write to Vec failed");
+            i += run + 1;
+        } else {
+            i += 1;
+        }
+    }
+
+    (meta, literals)
+}
+
+fn write_rle_meta<W>(writer: &mut W, rle_meta: &[u8], len: usize, n: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    let compressed_rle_meta = rans_encode_nx16_0(rle_meta, n)?;
+
+    if compressed_rle_meta.len() < rle_meta.len() {
+        write_len(writer, rle_meta.len() * 2)?;
+        write_len(writer, len)?;
+        write_len(writer, compressed_rle_meta.len())?;
+        writer.write_all(&compressed_rle_meta)?;
+    } else {
+        write_len(writer, rle_meta.len() * 2 + 1)?;
+        write_len(writer, len)?;
+        writer.write_all(rle_meta)?;
+    }
+
+    Ok(())
+}
+
+pub fn encode_pack(src: &[u8]) -> Option<(Vec<u8>, Vec<u8>)> {
+    let mut is_present = [false; 256];
+
+    for &b in src {
+        is_present[usize::from(b)] = true;
+    }
+
+    let p: Vec<_> = (0..=u8::MAX)
+        .filter(|&b| is_present[usize::from(b)])
+        .collect();
+
+    let bits_per_symbol = match p.len() {
+        0 => return None,
+        1 => return Some((p, Vec::new())),
+        2 => 1,
+        3..=4 => 2,
+        5..=16 => 4,
+        _ => return None,
+    };
+
+    let mut map = [0; 256];
+
+    for (i, &sym) in p.iter().enumerate() {
+        map[usize::from(sym)] = i as u8;
+    }
+
+    let symbols_per_byte = 8 / bits_per_symbol;
+    let mut dst = vec![0; (src.len() + symbols_per_byte - 1) / symbols_per_byte];
+
+    for (i, &b) in src.iter().enumerate() {
+        let shift = (i % symbols_per_byte) * bits_per_symbol;
+        dst[i / symbols_per_byte] |= map[usize::from(b)] << shift;
+    }
+
+    Some((p, dst))
+}
+
+pub fn write_pack_meta<W>(writer: &mut W, p: &[u8], len: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u8(p.len() as u8)?;
+    writer.write_all(p)?;
+    write_len(writer, len)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codecs::rans_nx16::rans_decode_nx16;
+
+    fn t(flags: Flags, data: &[u8]) -> io::Result<()> {
+        let compressed_data = rans_encode_nx16(flags, data)?;
+
+        let mut reader = &compressed_data[..];
+        let actual = rans_decode_nx16(&mut reader, data.len())?;
+
+        assert_eq!(actual, data, "flags = {:?}", flags);
+        assert!(reader.is_empty());
+
+        Ok(())
+    }
+
+    fn data() -> Vec<u8> {
+        b"noodles"
+            .iter()
+            .cycle()
+            .take(8191)
+            .enumerate()
+            .map(|(i, &b)| if i % 97 == 0 { b'n' } else { b })
+            .collect()
+    }
+
+    #[test]
+    fn test_rans_encode_nx16_order_0() -> io::Result<()> {
+        t(Flags::empty(), b"noodles")?;
+        t(Flags::empty(), &data())?;
+        t(Flags::N32, &data())?;
+        t(Flags::NO_SIZE, &data())?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_encode_nx16_order_1() -> io::Result<()> {
+        t(Flags::ORDER, b"n")?;
+        t(Flags::ORDER, b"noodles")?;
+        t(Flags::ORDER, &data())?;
+        t(Flags::ORDER | Flags::N32, b"noodles")?;
+        t(Flags::ORDER | Flags::N32, &data())?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_encode_nx16_stripe() -> io::Result<()> {
+        t(Flags::STRIPE, b"noodles")?;
+        t(Flags::STRIPE | Flags::ORDER, &data())?;
+        t(Flags::STRIPE | Flags::N32, &data())?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_encode_nx16_uncompressed() -> io::Result<()> {
+        t(Flags::CAT, b"noodles")?;
+        t(Flags::empty(), b"")?;
+        t(Flags::ORDER | Flags::RLE | Flags::PACK, b"")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_encode_nx16_rle() -> io::Result<()> {
+        t(Flags::RLE, b"noooooooodles")?;
+        t(Flags::RLE | Flags::ORDER, b"noooooooodles")?;
+        t(Flags::RLE, b"abcdefgh")?;
+        t(Flags::RLE, &[0; 1024])?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_encode_nx16_bit_packing() -> io::Result<()> {
+        t(Flags::PACK, b"nnnnnnn")?;
+        t(Flags::PACK, b"nonono")?;
+        t(Flags::PACK, b"nodnod")?;
+        t(Flags::PACK, b"noodles")?;
+        t(Flags::PACK | Flags::RLE | Flags::ORDER, &data())?;
+        t(Flags::PACK, &(0..=u8::MAX).collect::<Vec<_>>())?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_alphabet() -> io::Result<()> {
+        let mut alphabet = [false; 256];
+
+        for &b in b"noodles" {
+            alphabet[usize::from(b)] = true;
+        }
+
+        let mut buf = Vec::new();
+        write_alphabet(&mut buf, &alphabet)?;
+
+        assert_eq!(buf, [0x64, 0x65, 0x00, 0x6c, 0x6e, 0x6f, 0x00, 0x73, 0x00]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_frequencies() {
+        let mut frequencies = [0; 256];
+        frequencies[0] = 1_000_000_000;
+        frequencies[1] = 1;
+
+        let normalized_frequencies = normalize_frequencies(&frequencies, 12);
+
+        assert_eq!(normalized_frequencies[0], 4095);
+        assert_eq!(normalized_frequencies[1], 1);
+        assert_eq!(normalized_frequencies.iter().sum::<u32>(), 4096);
+    }
+}
@@ -99,7 +99,14 @@ impl Block {
             CompressionMethod::NameTokenizer => {
                 let mut reader = self.data();
                 let names = decode_names(&mut reader)?;
-                let data: Vec<_> = names.into_iter().flat_map(|s| s.into_bytes()).collect();
+                let data: Vec<_> = names
+                    .into_iter()
+                    .flat_map(|s| {
+                        let mut buf = s.into_bytes();
+                        buf.push(0x00);
+                        buf
+                    })
+                    .collect();
                 Ok(Bytes::from(data))
             }
         }
@@ -1,6 +1,14 @@
 use std::io::{self, Write};
 
 use super::{Block, CompressionMethod, ContentType};
+use crate::codecs::{
+    aac::{self, arith_encode},
+    fqzcomp::fqz_encode,
+    name_tokenizer::encode_names,
+    rans::{rans_encode, Order},
+    rans_nx16::{self, rans_encode_nx16},
+    smallest,
+};
 
 use bytes::Bytes;
 use bzip2::write::BzEncoder;
@@ -48,10 +56,15 @@ impl Builder {
     ///
     /// This sets the compression method, the uncompressed size to the length of the given data,
     /// and the data to the compressed output of the given data.
+    ///
+    /// `record_lengths` are the lengths of each record in the given data. They are only used by
+    /// codecs that model records, i.e., fqzcomp. If they are not given, the data is compressed as
+    /// a single record.
     pub fn compress_and_set_data(
         mut self,
         data: Vec<u8>,
         compression_method: CompressionMethod,
+        record_lengths: Option<&[usize]>,
     ) -> io::Result<Self> {
         self.compression_method = compression_method;
         self.uncompressed_len = data.len();
@@ -73,10 +86,52 @@ impl Builder {
                 encoder.write_all(&data)?;
                 encoder.finish()?
             }
-            _ => unimplemented!(
-                "compress_and_set_data: unhandled compression method: {:?}",
-                compression_method
-            ),
+            CompressionMethod::Rans4x8 => {
+                // The order-1 encoder splits the input into 4 interleaved streams and requires at
+                // least one byte per stream.
+                let orders: &[Order] = if data.len() < 4 {
+                    &[Order::Zero]
+                } else {
+                    &[Order::Zero, Order::One]
+                };
+
+                smallest(orders.iter().map(|&order| rans_encode(order, &data)))?
+            }
+            CompressionMethod::RansNx16 => {
+                use rans_nx16::Flags;
+
+                // Every combination of the transforms (ORDER, PACK, RLE, and STRIPE) is tried.
+                let transforms = [Flags::ORDER, Flags::PACK, Flags::RLE, Flags::STRIPE];
+
+                smallest((0..1 << transforms.len()).map(|i| {
+                    let flags = transforms
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, _)| i & (1 << j) != 0)
+                        .fold(Flags::empty(), |flags, (_, &flag)| flags | flag);
+
+                    rans_encode_nx16(flags, &data)
+                }))?
+            }
+            CompressionMethod::AdaptiveArithmeticCoding => {
+                use aac::Flags;
+
+                smallest(
+                    [
+                        Flags::empty(),
+                        Flags::ORDER,
+                        Flags::PACK,
+                        Flags::ORDER | Flags::PACK,
+                    ]
+                    .iter()
+                    .map(|&flags| arith_encode(flags, &data)),
+                )?
+            }
+            CompressionMethod::Fqzcomp => match record_lengths {
+                Some(lens) => fqz_encode(lens, &data)?,
+                None => fqz_encode(&[data.len()], &data)?,
+            },
+            CompressionMethod::NameTokenizer => encode_names(&data, false)?,
         };
 
         self.data = Bytes::from(data);
@@ -94,3 +149,74 @@ impl Builder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compress_and_set_data() -> io::Result<()> {
+        fn t(data: &[u8], compression_method: CompressionMethod) -> io::Result<()> {
+            let block = Builder::default()
+                .set_content_type(ContentType::ExternalData)
+                .compress_and_set_data(data.to_vec(), compression_method, None)?
+                .build();
+
+            assert_eq!(block.compression_method(), compression_method);
+            assert_eq!(block.uncompressed_len(), data.len());
+            assert_eq!(&block.decompressed_data()?[..], data);
+
+            Ok(())
+        }
+
+        for &compression_method in &[
+            CompressionMethod::None,
+            CompressionMethod::Gzip,
+            CompressionMethod::Bzip2,
+            CompressionMethod::Lzma,
+            CompressionMethod::Rans4x8,
+            CompressionMethod::RansNx16,
+            CompressionMethod::AdaptiveArithmeticCoding,
+            CompressionMethod::Fqzcomp,
+            CompressionMethod::NameTokenizer,
+        ] {
+            t(b"ndls\x00ndls\x00noodles\x00", compression_method)?;
+            t(b"n\x00", compression_method)?;
+            t(b"", compression_method)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compress_and_set_data_with_fqzcomp_record_lengths() -> io::Result<()> {
+        // Quality scores that degrade along each read.
+        let record_lengths = [36, 151, 75, 100, 50, 151, 120, 75];
+        let data: Vec<_> = record_lengths
+            .iter()
+            .cycle()
+            .take(64)
+            .flat_map(|&len| (0..len).map(|i| 40 - (i / 8) as u8))
+            .collect();
+        let record_lengths: Vec<_> = record_lengths.iter().copied().cycle().take(64).collect();
+
+        let single_record_block = Builder::default()
+            .set_content_type(ContentType::ExternalData)
+            .compress_and_set_data(data.clone(), CompressionMethod::Fqzcomp, None)?
+            .build();
+
+        let block = Builder::default()
+            .set_content_type(ContentType::ExternalData)
+            .compress_and_set_data(
+                data.clone(),
+                CompressionMethod::Fqzcomp,
+                Some(&record_lengths),
+            )?
+            .build();
+
+        assert_eq!(&block.decompressed_data()?[..], &data[..]);
+        assert!(block.data().len() < single_record_block.data().len());
+
+        Ok(())
+    }
+}
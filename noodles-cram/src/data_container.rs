@@ -11,6 +11,8 @@ pub(crate) use self::{
 };
 pub use self::{compression_header::CompressionHeader, slice::Slice};
 
+use crate::writer::Options;
+
 /// A CRAM data container.
 pub struct DataContainer {
     compression_header: CompressionHeader,
@@ -18,8 +20,8 @@ pub struct DataContainer {
 }
 
 impl DataContainer {
-    pub(crate) fn builder(record_counter: u64) -> Builder {
-        Builder::new(record_counter)
+    pub(crate) fn builder(record_counter: u64, options: &Options) -> Builder {
+        Builder::new(record_counter, options)
     }
 
     pub(crate) fn new(compression_header: CompressionHeader, slices: Vec<Slice>) -> Self {
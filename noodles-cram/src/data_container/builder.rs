@@ -1,10 +1,13 @@
-use std::{io, mem, num};
+use std::{io, iter, mem, num};
 
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
-use super::{slice, CompressionHeader, DataContainer, Slice};
-use crate::{writer::Options, Record};
+use super::{slice, CompressionHeader, DataContainer, ReferenceSequenceContext, Slice};
+use crate::{
+    writer::{BlockCompressor, Options},
+    Record,
+};
 
 const MAX_SLICE_COUNT: usize = 1;
 
@@ -12,6 +15,7 @@ const MAX_SLICE_COUNT: usize = 1;
 pub struct Builder {
     slice_builder: slice::Builder,
     slice_builders: Vec<slice::Builder>,
+    records_per_slice: usize,
     record_counter: u64,
     base_count: u64,
 }
@@ -24,10 +28,13 @@ pub enum AddRecordError {
 }
 
 impl Builder {
-    pub fn new(record_counter: u64) -> Self {
+    pub fn new(record_counter: u64, options: &Options) -> Self {
+        let records_per_slice = options.profile.records_per_slice();
+
         Self {
-            slice_builder: Slice::builder(),
+            slice_builder: Slice::builder(records_per_slice),
             slice_builders: Vec::new(),
+            records_per_slice,
             record_counter,
             base_count: 0,
         }
@@ -41,6 +48,17 @@ impl Builder {
         self.base_count
     }
 
+    /// Returns the reference sequence IDs of the slices that refer to a single reference sequence.
+    pub fn reference_sequence_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.slice_builders
+            .iter()
+            .chain(iter::once(&self.slice_builder))
+            .filter_map(|builder| match builder.reference_sequence_context() {
+                ReferenceSequenceContext::Some(context) => Some(context.reference_sequence_id()),
+                _ => None,
+            })
+    }
+
     pub fn add_record(&mut self, record: Record) -> Result<(), AddRecordError> {
         if self.slice_builders.len() >= MAX_SLICE_COUNT {
             return Err(AddRecordError::ContainerFull(record));
@@ -54,7 +72,10 @@ impl Builder {
             }
             Err(e) => match e {
                 slice::builder::AddRecordError::SliceFull(r) => {
-                    let slice_builder = mem::take(&mut self.slice_builder);
+                    let slice_builder = mem::replace(
+                        &mut self.slice_builder,
+                        Slice::builder(self.records_per_slice),
+                    );
                     self.slice_builders.push(slice_builder);
                     Err(AddRecordError::SliceFull(r))
                 }
@@ -65,6 +86,7 @@ impl Builder {
     pub fn build(
         mut self,
         options: &Options,
+        block_compressor: &mut BlockCompressor,
         reference_sequence_repository: &fasta::Repository,
         header: &sam::Header,
     ) -> io::Result<DataContainer> {
@@ -90,6 +112,8 @@ impl Builder {
             .into_iter()
             .map(|builder| {
                 builder.build(
+                    &options,
+                    block_compressor,
                     reference_sequence_repository,
                     header,
                     &compression_header,
@@ -71,6 +71,41 @@ pub enum DataSeries {
 impl DataSeries {
     /// The number of data series variants.
     pub(crate) const LEN: usize = 28;
+
+    /// The data series variants, excluding the legacy CRAM 1.0 data series.
+    ///
+    /// The default data series encoding map assigns each data series the block content ID of its
+    /// position in this list plus one.
+    pub(crate) const ALL: [Self; Self::LEN] = [
+        Self::BamBitFlags,
+        Self::CramBitFlags,
+        Self::ReferenceId,
+        Self::ReadLengths,
+        Self::InSeqPositions,
+        Self::ReadGroups,
+        Self::ReadNames,
+        Self::NextMateBitFlags,
+        Self::NextFragmentReferenceSequenceId,
+        Self::NextMateAlignmentStart,
+        Self::TemplateSize,
+        Self::DistanceToNextFragment,
+        Self::TagIds,
+        Self::NumberOfReadFeatures,
+        Self::ReadFeaturesCodes,
+        Self::InReadPositions,
+        Self::DeletionLengths,
+        Self::StretchesOfBases,
+        Self::StretchesOfQualityScores,
+        Self::BaseSubstitutionCodes,
+        Self::Insertion,
+        Self::ReferenceSkipLength,
+        Self::Padding,
+        Self::HardClip,
+        Self::SoftClip,
+        Self::MappingQualities,
+        Self::Bases,
+        Self::QualityScores,
+    ];
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
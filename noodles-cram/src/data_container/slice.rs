@@ -28,8 +28,8 @@ pub struct Slice {
 }
 
 impl Slice {
-    pub(crate) fn builder() -> Builder {
-        Builder::default()
+    pub(crate) fn builder(max_record_count: usize) -> Builder {
+        Builder::new(max_record_count)
     }
 
     pub(crate) fn new(header: Header, core_data_block: Block, external_blocks: Vec<Block>) -> Self {
@@ -10,20 +10,25 @@ use crate::{
         Block,
     },
     data_container::{
-        compression_header::data_series_encoding_map::DataSeries, CompressionHeader,
-        ReferenceSequenceContext,
+        compression_header::{data_series_encoding_map::DataSeries, encoding::codec::Byte},
+        CompressionHeader, ReferenceSequenceContext,
     },
     record::Flags,
-    writer, BitWriter, Record,
+    writer::{self, BlockCompressor, ReferenceMode},
+    BitWriter, Record,
 };
 
 use super::{Header, Slice};
 
 const CORE_DATA_BLOCK_CONTENT_ID: i32 = 0;
-const MAX_RECORD_COUNT: usize = 10240;
 
-#[derive(Debug, Default)]
+// The block content IDs of data series blocks are 1 to `DataSeries::LEN`, and tag blocks use the
+// encoded tag key, which is always larger.
+const EMBEDDED_REFERENCE_BASES_BLOCK_CONTENT_ID: i32 = DataSeries::LEN as i32 + 1;
+
+#[derive(Debug)]
 pub struct Builder {
+    max_record_count: usize,
     records: Vec<Record>,
     reference_sequence_context: ReferenceSequenceContext,
 }
@@ -34,6 +39,14 @@ pub enum AddRecordError {
 }
 
 impl Builder {
+    pub fn new(max_record_count: usize) -> Self {
+        Self {
+            max_record_count,
+            records: Vec::new(),
+            reference_sequence_context: ReferenceSequenceContext::default(),
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.records.is_empty()
     }
@@ -47,7 +60,7 @@ impl Builder {
     }
 
     pub fn add_record(&mut self, record: Record) -> Result<&Record, AddRecordError> {
-        if self.records.len() >= MAX_RECORD_COUNT {
+        if self.records.len() >= self.max_record_count {
             return Err(AddRecordError::SliceFull(record));
         }
 
@@ -77,26 +90,26 @@ impl Builder {
 
     pub fn build(
         mut self,
+        options: &writer::Options,
+        block_compressor: &mut BlockCompressor,
         reference_sequence_repostitory: &fasta::repository::Repository,
         header: &sam::Header,
         compression_header: &CompressionHeader,
         record_counter: u64,
     ) -> io::Result<Slice> {
-        let (core_data_block, external_blocks) = write_records(
+        let (core_data_block, mut external_blocks) = write_records(
+            options,
+            block_compressor,
             compression_header,
             self.reference_sequence_context,
             &mut self.records,
         )?;
 
-        let mut block_content_ids = Vec::with_capacity(external_blocks.len() + 1);
-        block_content_ids.push(core_data_block.content_id());
+        let mut embedded_reference_bases_block_content_id = None;
 
-        for block in &external_blocks {
-            block_content_ids.push(block.content_id());
-        }
-
-        let reference_md5 = match self.reference_sequence_context {
-            ReferenceSequenceContext::Some(context) => {
+        let reference_md5 = match (options.reference_mode, self.reference_sequence_context) {
+            (ReferenceMode::None, _) => [0; 16],
+            (reference_mode, ReferenceSequenceContext::Some(context)) => {
                 let reference_sequence_name = header
                     .reference_sequences()
                     .get_index(context.reference_sequence_id())
@@ -111,25 +124,52 @@ impl Builder {
                 let (start, end) = (context.alignment_start(), context.alignment_end());
                 let sequence = &reference_sequence[start..=end];
 
+                if reference_mode == ReferenceMode::Embedded {
+                    let block = block_compressor.compress(
+                        block::ContentType::ExternalData,
+                        EMBEDDED_REFERENCE_BASES_BLOCK_CONTENT_ID,
+                        sequence.to_vec(),
+                        &compression_methods(options, None),
+                    )?;
+
+                    external_blocks.push(block);
+                    embedded_reference_bases_block_content_id =
+                        Some(EMBEDDED_REFERENCE_BASES_BLOCK_CONTENT_ID);
+                }
+
                 calculate_normalized_sequence_digest(sequence)
             }
             _ => [0; 16],
         };
 
-        let header = Header::builder()
+        let mut block_content_ids = Vec::with_capacity(external_blocks.len() + 1);
+        block_content_ids.push(core_data_block.content_id());
+
+        for block in &external_blocks {
+            block_content_ids.push(block.content_id());
+        }
+
+        let mut builder = Header::builder()
             .set_reference_sequence_context(self.reference_sequence_context)
             .set_record_count(self.records.len())
             .set_record_counter(record_counter)
             .set_block_count(block_content_ids.len())
             .set_block_content_ids(block_content_ids)
-            .set_reference_md5(reference_md5)
-            .build();
+            .set_reference_md5(reference_md5);
+
+        if let Some(id) = embedded_reference_bases_block_content_id {
+            builder = builder.set_embedded_reference_bases_block_content_id(id);
+        }
+
+        let header = builder.build();
 
         Ok(Slice::new(header, core_data_block, external_blocks))
     }
 }
 
 fn write_records(
+    options: &writer::Options,
+    block_compressor: &mut BlockCompressor,
     compression_header: &CompressionHeader,
     reference_sequence_context: ReferenceSequenceContext,
     records: &mut [Record],
@@ -137,10 +177,12 @@ fn write_records(
     let mut core_data_writer = BitWriter::new(Vec::new());
 
     let mut external_data_writers = HashMap::new();
+    let mut block_content_id_data_series = HashMap::new();
 
-    for i in 0..DataSeries::LEN {
+    for (i, &data_series) in DataSeries::ALL.iter().enumerate() {
         let block_content_id = (i + 1) as i32;
         external_data_writers.insert(block_content_id, Vec::new());
+        block_content_id_data_series.insert(block_content_id, data_series);
     }
 
     for &block_content_id in compression_header.tag_encoding_map().keys() {
@@ -156,33 +198,92 @@ fn write_records(
 
     set_mates(records);
 
+    // The quality scores of each record are tracked to give the lengths of the records in the
+    // quality scores block, which are used by record-aware codecs (i.e., fqzcomp).
+    let quality_scores_block_content_id = compression_header
+        .data_series_encoding_map()
+        .quality_scores_encoding()
+        .and_then(|encoding| match encoding.get() {
+            Byte::External(block_content_id) => Some(*block_content_id),
+            Byte::Huffman(..) => None,
+        });
+
+    let quality_scores_len = |writer: &writer::record::Writer<'_, _, Vec<u8>>| {
+        quality_scores_block_content_id
+            .and_then(|id| writer.external_data_writers().get(&id))
+            .map(|buf| buf.len())
+            .unwrap_or_default()
+    };
+
+    let mut quality_scores_lengths = Vec::with_capacity(records.len());
+
     for record in records {
+        let start = quality_scores_len(&record_writer);
         record_writer.write_record(record)?;
+        let end = quality_scores_len(&record_writer);
+        quality_scores_lengths.push(end - start);
     }
 
+    let candidates = |block_content_id| {
+        let data_series = block_content_id_data_series.get(&block_content_id).copied();
+        compression_methods(options, data_series)
+    };
+
     let core_data_block = core_data_writer.finish().and_then(|buf| {
-        Block::builder()
-            .set_content_type(block::ContentType::CoreData)
-            .set_content_id(CORE_DATA_BLOCK_CONTENT_ID)
-            .compress_and_set_data(buf, CompressionMethod::Gzip)
-            .map(|builder| builder.build())
+        block_compressor.compress(
+            block::ContentType::CoreData,
+            CORE_DATA_BLOCK_CONTENT_ID,
+            buf,
+            &candidates(CORE_DATA_BLOCK_CONTENT_ID),
+        )
     })?;
 
-    let external_blocks: Vec<_> = external_data_writers
+    // External blocks are written in block content ID order so that the output does not depend
+    // on the iteration order of the writers.
+    let mut external_data: Vec<_> = external_data_writers
         .into_iter()
         .filter(|(_, buf)| !buf.is_empty())
+        .collect();
+
+    external_data.sort_unstable_by_key(|(block_content_id, _)| *block_content_id);
+
+    let external_blocks: Vec<_> = external_data
+        .into_iter()
         .map(|(block_content_id, buf)| {
-            Block::builder()
-                .set_content_type(block::ContentType::ExternalData)
-                .set_content_id(block_content_id)
-                .compress_and_set_data(buf, CompressionMethod::Gzip)
-                .map(|builder| builder.build())
+            let record_lengths = if Some(block_content_id) == quality_scores_block_content_id {
+                Some(&quality_scores_lengths[..])
+            } else {
+                None
+            };
+
+            block_compressor.compress_with_record_lengths(
+                block::ContentType::ExternalData,
+                block_content_id,
+                buf,
+                record_lengths,
+                &candidates(block_content_id),
+            )
         })
         .collect::<Result<_, _>>()?;
 
     Ok((core_data_block, external_blocks))
 }
 
+fn compression_methods(
+    options: &writer::Options,
+    data_series: Option<DataSeries>,
+) -> Vec<CompressionMethod> {
+    if options.adaptive_block_compression {
+        options
+            .profile
+            .compression_methods(options.version, data_series)
+    } else {
+        vec![options
+            .profile
+            .compression_method(options.version, data_series)]
+    }
+}
+
 fn set_mates(records: &mut [Record]) {
     assert!(!records.is_empty());
 
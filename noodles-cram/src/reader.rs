@@ -1,14 +1,16 @@
 //! CRAM reader and record iterator.
 
+mod builder;
 pub(crate) mod container;
 pub(crate) mod data_container;
+mod decoder;
 pub(crate) mod header_container;
 pub(crate) mod num;
 mod query;
 pub(crate) mod record;
 mod records;
 
-pub use self::records::Records;
+pub use self::{builder::Builder, records::Records};
 
 use std::io::{self, Read, Seek, SeekFrom};
 
@@ -50,12 +52,27 @@ use crate::data_container::DataContainer;
 pub struct Reader<R> {
     inner: R,
     buf: BytesMut,
+    worker_count: usize,
 }
 
 impl<R> Reader<R>
 where
     R: Read,
 {
+    /// Creates a CRAM reader builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let builder = cram::Reader::builder(&data[..]);
+    /// let reader = builder.build();
+    /// ```
+    pub fn builder(inner: R) -> Builder<R> {
+        Builder::new(inner)
+    }
+
     /// Creates a CRAM reader.
     ///
     /// # Examples
@@ -67,10 +84,7 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn new(reader: R) -> Self {
-        Self {
-            inner: reader,
-            buf: BytesMut::new(),
-        }
+        Builder::new(reader).build()
     }
 
     /// Returns a reference to the underlying reader.
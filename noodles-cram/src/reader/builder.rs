@@ -0,0 +1,72 @@
+use std::io::Read;
+
+use bytes::BytesMut;
+
+use super::Reader;
+
+/// A CRAM reader builder.
+#[derive(Debug)]
+pub struct Builder<R> {
+    inner: R,
+    worker_count: usize,
+}
+
+impl<R> Builder<R>
+where
+    R: Read,
+{
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            worker_count: 1,
+        }
+    }
+
+    /// Sets a worker count.
+    ///
+    /// When the worker count is greater than 1, the records iterator reads ahead data containers
+    /// and decodes their slices on a pool of worker threads. Records are still emitted in stream
+    /// order.
+    ///
+    /// By default, the worker count is set to 1, i.e., slices are decoded on the calling thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let builder = cram::Reader::builder(&data[..]).set_worker_count(8);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: usize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
+    /// Builds a CRAM reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let reader = cram::Reader::builder(&data[..]).build();
+    /// ```
+    pub fn build(self) -> Reader<R> {
+        Reader {
+            inner: self.inner,
+            buf: BytesMut::new(),
+            worker_count: self.worker_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let builder = Builder::new(&[][..]);
+        assert_eq!(builder.worker_count, 1);
+    }
+}
@@ -0,0 +1,64 @@
+use std::{io, sync::Arc};
+
+use crate::{worker_pool::WorkerPool, DataContainer, Record};
+
+type Output = (Arc<DataContainer>, usize, io::Result<Vec<Record>>);
+
+/// A decoded slice.
+///
+/// The records are not yet resolved, as resolving them requires the reference sequence
+/// repository, which is only accessed on the calling thread.
+pub(super) struct DecodedSlice {
+    pub(super) data_container: Arc<DataContainer>,
+    pub(super) slice_index: usize,
+    pub(super) records: Vec<Record>,
+}
+
+/// A slice decoder that reads ahead.
+///
+/// Slices of queued data containers are decoded on worker threads and received in reading order.
+pub(super) struct Decoder {
+    pool: WorkerPool<(Arc<DataContainer>, usize), Output>,
+}
+
+impl Decoder {
+    pub(super) fn new(worker_count: usize) -> Self {
+        let pool = WorkerPool::new(
+            worker_count,
+            |(data_container, i): (Arc<DataContainer>, usize)| {
+                let slice = &data_container.slices()[i];
+                let result = slice.records(data_container.compression_header());
+                (data_container, i, result)
+            },
+        );
+
+        Self { pool }
+    }
+
+    /// Returns whether each worker has a queued slice.
+    pub(super) fn is_full(&self) -> bool {
+        self.pool.is_full()
+    }
+
+    /// Queues all slices of a data container to be decoded.
+    pub(super) fn send(&mut self, data_container: DataContainer) {
+        let data_container = Arc::new(data_container);
+
+        for i in 0..data_container.slices().len() {
+            self.pool.send((data_container.clone(), i));
+        }
+    }
+
+    /// Receives the oldest queued slice, waiting for it to be decoded.
+    pub(super) fn recv(&mut self) -> Option<io::Result<DecodedSlice>> {
+        self.pool.recv().map(|result| {
+            let (data_container, slice_index, records) = result?;
+
+            Ok(DecodedSlice {
+                data_container,
+                slice_index,
+                records: records?,
+            })
+        })
+    }
+}
@@ -6,7 +6,7 @@ use std::{
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
-use super::Reader;
+use super::{decoder::Decoder, Reader};
 use crate::Record;
 
 /// An iterator over records of a CRAM reader.
@@ -20,6 +20,8 @@ where
     reference_sequence_repository: &'a fasta::Repository,
     header: &'a sam::Header,
     records: vec::IntoIter<Record>,
+    decoder: Option<Decoder>,
+    is_eof: bool,
 }
 
 impl<'a, R> Records<'a, R>
@@ -31,11 +33,19 @@ where
         reference_sequence_repository: &'a fasta::Repository,
         header: &'a sam::Header,
     ) -> Self {
+        let decoder = if reader.worker_count > 1 {
+            Some(Decoder::new(reader.worker_count))
+        } else {
+            None
+        };
+
         Self {
             reader,
             reference_sequence_repository,
             header,
             records: Vec::new().into_iter(),
+            decoder,
+            is_eof: false,
         }
     }
 
@@ -70,6 +80,42 @@ where
 
         Ok(false)
     }
+
+    // Reads ahead data containers to keep the decoder workers busy and resolves the records of the
+    // oldest decoded slice. Without workers, this reads the next container on the calling thread.
+    fn read_records(&mut self) -> io::Result<bool> {
+        let decoder = match self.decoder.as_mut() {
+            Some(decoder) => decoder,
+            None => return self.read_container_records(),
+        };
+
+        while !self.is_eof && !decoder.is_full() {
+            match self.reader.read_data_container()? {
+                Some(container) => decoder.send(container),
+                None => self.is_eof = true,
+            }
+        }
+
+        let decoded_slice = match decoder.recv().transpose()? {
+            Some(decoded_slice) => decoded_slice,
+            None => return Ok(true),
+        };
+
+        let container = &decoded_slice.data_container;
+        let slice = &container.slices()[decoded_slice.slice_index];
+        let mut records = decoded_slice.records;
+
+        slice.resolve_records(
+            self.reference_sequence_repository,
+            self.header,
+            container.compression_header(),
+            &mut records,
+        )?;
+
+        self.records = records.into_iter();
+
+        Ok(false)
+    }
 }
 
 impl<'a, R> Iterator for Records<'a, R>
@@ -82,7 +128,7 @@ where
         loop {
             match self.records.next() {
                 Some(r) => return Some(Ok(r)),
-                None => match self.read_container_records() {
+                None => match self.read_records() {
                     Ok(true) => return None,
                     Ok(false) => {}
                     Err(e) => return Some(Err(e)),
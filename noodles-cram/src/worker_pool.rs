@@ -0,0 +1,133 @@
+use std::{
+    collections::VecDeque,
+    fmt, io,
+    thread::{self, JoinHandle},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+
+type Request<T, U> = (T, Sender<U>);
+
+/// A pool of worker threads that applies a function to queued inputs.
+///
+/// Outputs are received in the order their inputs were queued.
+pub(crate) struct WorkerPool<T, U> {
+    worker_count: usize,
+    request_tx: Option<Sender<Request<T, U>>>,
+    handles: Vec<JoinHandle<()>>,
+    queue: VecDeque<Receiver<U>>,
+}
+
+impl<T, U> WorkerPool<T, U>
+where
+    T: Send + 'static,
+    U: Send + 'static,
+{
+    /// Creates a pool of `worker_count` threads that each call `f` on received inputs.
+    pub(crate) fn new<F>(worker_count: usize, f: F) -> Self
+    where
+        F: Fn(T) -> U + Clone + Send + 'static,
+    {
+        let (request_tx, request_rx) = crossbeam_channel::unbounded::<Request<T, U>>();
+
+        let handles = (0..worker_count)
+            .map(|_| {
+                let request_rx = request_rx.clone();
+                let f = f.clone();
+
+                thread::spawn(move || {
+                    while let Ok((input, tx)) = request_rx.recv() {
+                        // The receiver is dropped when the pool is dropped.
+                        tx.send(f(input)).ok();
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            worker_count,
+            request_tx: Some(request_tx),
+            handles,
+            queue: VecDeque::with_capacity(worker_count),
+        }
+    }
+
+    /// Returns whether there are at least as many queued inputs as workers.
+    pub(crate) fn is_full(&self) -> bool {
+        self.queue.len() >= self.worker_count
+    }
+
+    /// Queues an input.
+    pub(crate) fn send(&mut self, input: T) {
+        // Each input has its own result channel, which is what keeps the outputs in order.
+        let (tx, rx) = crossbeam_channel::bounded(1);
+
+        if let Some(request_tx) = self.request_tx.as_ref() {
+            // The workers only exit after `request_tx` is dropped.
+            request_tx.send((input, tx)).ok();
+        }
+
+        self.queue.push_back(rx);
+    }
+
+    /// Receives the output of the oldest queued input, waiting for it to be processed.
+    pub(crate) fn recv(&mut self) -> Option<io::Result<U>> {
+        self.queue.pop_front().map(|rx| {
+            rx.recv()
+                .map_err(|_| io::Error::new(io::ErrorKind::Other, "worker disconnected"))
+        })
+    }
+}
+
+impl<T, U> fmt::Debug for WorkerPool<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkerPool")
+            .field("worker_count", &self.worker_count)
+            .field("queue_len", &self.queue.len())
+            .finish()
+    }
+}
+
+impl<T, U> Drop for WorkerPool<T, U> {
+    fn drop(&mut self) {
+        self.queue.clear();
+        self.request_tx.take();
+
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_recv() -> io::Result<()> {
+        // Earlier inputs take longer, so later ones finish first.
+        let mut pool = WorkerPool::new(4, |n: u64| {
+            thread::sleep(Duration::from_millis(8 * (4 - n)));
+            n
+        });
+
+        for n in 0..4 {
+            pool.send(n);
+        }
+
+        assert!(pool.is_full());
+
+        let mut actual = Vec::new();
+
+        while let Some(result) = pool.recv() {
+            actual.push(result?);
+        }
+
+        assert_eq!(actual, [0, 1, 2, 3]);
+        assert!(!pool.is_full());
+
+        Ok(())
+    }
+}
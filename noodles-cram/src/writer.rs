@@ -1,22 +1,30 @@
+//! CRAM writer.
+
+mod block_compressor;
 mod builder;
 mod container;
 pub(crate) mod data_container;
+mod encoder;
 mod header_container;
 pub(crate) mod num;
 mod options;
+pub mod profile;
 pub(crate) mod record;
+pub mod reference_mode;
 
-pub use self::builder::Builder;
-pub(crate) use self::options::Options;
+pub(crate) use self::{block_compressor::BlockCompressor, options::Options};
+pub use self::{builder::Builder, profile::Profile, reference_mode::ReferenceMode};
 
 use std::{
     io::{self, Write},
     mem,
+    sync::Arc,
 };
 
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
+use self::encoder::Encoder;
 use super::{file_definition::Version, DataContainer, FileDefinition, Record, MAGIC_NUMBER};
 
 /// A CRAM writer.
@@ -50,8 +58,11 @@ where
     inner: W,
     reference_sequence_repository: fasta::Repository,
     options: Options,
+    block_compressor: BlockCompressor,
     data_container_builder: crate::data_container::Builder,
     record_counter: u64,
+    encoder: Option<Encoder>,
+    header: Option<Arc<sam::Header>>,
 }
 
 impl<W> Writer<W>
@@ -116,13 +127,24 @@ where
     /// ```
     pub fn try_finish(&mut self, header: &sam::Header) -> io::Result<()> {
         use self::container::write_eof_container;
+
         self.flush(header)?;
+
+        if let Some(encoder) = self.encoder.as_mut() {
+            while let Some(result) = encoder.recv() {
+                let (buf, block_compressor) = result?;
+                self.block_compressor = block_compressor;
+                self.inner.write_all(&buf)?;
+            }
+        }
+
         write_eof_container(&mut self.inner)
     }
 
     /// Writes a CRAM file definition.
     ///
-    /// The file ID is set as a blank value (`[0x00; 20]`).
+    /// The format version is the one given to [`Builder::set_version`], and the file ID is set as
+    /// a blank value (`[0x00; 20]`).
     ///
     /// # Examples
     ///
@@ -145,7 +167,16 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn write_file_definition(&mut self) -> io::Result<()> {
-        let file_definition = FileDefinition::default();
+        let version = self.options.version;
+
+        if version != Version::new(3, 0) && version != Version::new(3, 1) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unsupported CRAM version: expected 3.0 or 3.1",
+            ));
+        }
+
+        let file_definition = FileDefinition::new(version, Default::default());
         write_file_definition(&mut self.inner, &file_definition)
     }
 
@@ -153,7 +184,8 @@ where
     ///
     /// The position of the stream is expected to be directly after the file definition.
     ///
-    /// Reference sequence dictionary entries must have MD5 checksums (`M5`) set.
+    /// Reference sequence dictionary entries must have MD5 checksums (`M5`) set, unless the
+    /// reference mode is [`ReferenceMode::None`].
     ///
     /// # Examples
     ///
@@ -173,7 +205,9 @@ where
     /// ```
     pub fn write_file_header(&mut self, header: &sam::Header) -> io::Result<()> {
         use self::header_container::write_header_container;
-        write_header_container(&mut self.inner, header)
+
+        let validate_md5_checksums = self.options.reference_mode != ReferenceMode::None;
+        write_header_container(&mut self.inner, header, validate_md5_checksums)
     }
 
     /// Writes a CRAM record.
@@ -231,13 +265,48 @@ where
 
         let data_container_builder = mem::replace(
             &mut self.data_container_builder,
-            DataContainer::builder(self.record_counter),
+            DataContainer::builder(self.record_counter, &self.options),
         );
 
+        if let Some(encoder) = self.encoder.as_mut() {
+            let reference_sequences = if self.options.reference_mode == ReferenceMode::None {
+                Vec::new()
+            } else {
+                fetch_reference_sequences(
+                    &self.reference_sequence_repository,
+                    header,
+                    &data_container_builder,
+                )?
+            };
+
+            let header = self
+                .header
+                .get_or_insert_with(|| Arc::new(header.clone()))
+                .clone();
+
+            encoder.send(
+                data_container_builder,
+                reference_sequences,
+                header,
+                self.block_compressor.clone(),
+            );
+
+            while encoder.is_full() {
+                if let Some(result) = encoder.recv() {
+                    let (buf, block_compressor) = result?;
+                    self.block_compressor = block_compressor;
+                    self.inner.write_all(&buf)?;
+                }
+            }
+
+            return Ok(());
+        }
+
         let base_count = data_container_builder.base_count();
 
         let data_container = data_container_builder.build(
             &self.options,
+            &mut self.block_compressor,
             &self.reference_sequence_repository,
             header,
         )?;
@@ -270,6 +339,46 @@ where
     }
 }
 
+fn fetch_reference_sequences(
+    reference_sequence_repository: &fasta::Repository,
+    header: &sam::Header,
+    data_container_builder: &crate::data_container::Builder,
+) -> io::Result<Vec<fasta::Record>> {
+    let mut reference_sequences: Vec<fasta::Record> = Vec::new();
+
+    for reference_sequence_id in data_container_builder.reference_sequence_ids() {
+        let name = header
+            .reference_sequences()
+            .get_index(reference_sequence_id)
+            .map(|(name, _)| name)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "invalid reference sequence ID")
+            })?;
+
+        if reference_sequences
+            .iter()
+            .any(|record| record.name() == name)
+        {
+            continue;
+        }
+
+        let sequence = reference_sequence_repository
+            .get(name)
+            .transpose()?
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("missing reference sequence: {}", name),
+                )
+            })?;
+
+        let definition = fasta::record::Definition::new(name, None);
+        reference_sequences.push(fasta::Record::new(definition, sequence));
+    }
+
+    Ok(reference_sequences)
+}
+
 fn write_file_definition<W>(writer: &mut W, file_definition: &FileDefinition) -> io::Result<()>
 where
     W: Write,
@@ -287,3 +396,357 @@ where
     let format = [version.major(), version.minor()];
     writer.write_all(&format)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_file_definition_with_unsupported_version() {
+        let mut writer = Writer::builder(Vec::new())
+            .set_version(Version::new(2, 1))
+            .build();
+
+        assert!(matches!(
+            writer.write_file_definition(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn test_write_record_with_reference_modes() -> Result<(), Box<dyn std::error::Error>> {
+        use md5::{Digest, Md5};
+        use noodles_core::Position;
+        use sam::header::{reference_sequence::Md5Checksum, ReferenceSequence};
+
+        use crate::Reader;
+
+        let reference_sequence = fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"GATCTTACTTTTT".to_vec()),
+        );
+
+        let md5_checksum = Md5::digest(reference_sequence.sequence());
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name("sq0".parse()?)
+                    .set_length(reference_sequence.sequence().len())
+                    .set_md5_checksum(Md5Checksum::from(<[u8; 16]>::from(md5_checksum)))
+                    .build()?,
+            )
+            .build();
+
+        let alignment_records = [
+            sam::alignment::Record::builder()
+                .set_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(3)?)
+                .set_cigar("2M1I1M1D2M".parse()?)
+                .set_sequence("TCGTAC".parse()?)
+                .set_quality_scores("NDLSND".parse()?)
+                .build(),
+            sam::alignment::Record::builder()
+                .set_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(8)?)
+                .set_cigar("1S4M".parse()?)
+                .set_sequence("ACTTA".parse()?)
+                .set_quality_scores("NDLSN".parse()?)
+                .build(),
+        ];
+
+        for reference_mode in [ReferenceMode::Embedded, ReferenceMode::None] {
+            let repository = match reference_mode {
+                ReferenceMode::Embedded => fasta::Repository::new(vec![reference_sequence.clone()]),
+                _ => fasta::Repository::default(),
+            };
+
+            let mut writer = Writer::builder(Vec::new())
+                .set_reference_sequence_repository(repository)
+                .set_reference_mode(reference_mode)
+                .build();
+
+            writer.write_file_definition()?;
+            writer.write_file_header(&header)?;
+
+            for record in &alignment_records {
+                let r = Record::try_from_alignment_record(&header, record)?;
+                writer.write_record(&header, r)?;
+            }
+
+            writer.try_finish(&header)?;
+
+            let mut reader = Reader::new(&writer.get_ref()[..]);
+            reader.read_file_definition()?;
+            reader.read_file_header()?;
+
+            let data_container = reader
+                .read_data_container()?
+                .expect("missing data container");
+
+            assert!(!data_container
+                .compression_header()
+                .preservation_map()
+                .is_reference_required());
+
+            assert_eq!(
+                data_container.slices()[0]
+                    .header()
+                    .embedded_reference_bases_block_content_id()
+                    .is_some(),
+                reference_mode == ReferenceMode::Embedded
+            );
+
+            // Records are decoded without the reference sequence.
+            let mut reader = Reader::new(&writer.get_ref()[..]);
+            reader.read_file_definition()?;
+            reader.read_file_header()?;
+
+            let repository = fasta::Repository::default();
+
+            let actual = reader
+                .records(&repository, &header)
+                .map(|result| result.and_then(|r| r.try_into_alignment_record(&header)))
+                .collect::<io::Result<Vec<_>>>()?;
+
+            assert_eq!(actual.len(), alignment_records.len());
+
+            for (a, b) in actual.iter().zip(&alignment_records) {
+                assert_eq!(a.alignment_start(), b.alignment_start());
+                assert_eq!(a.cigar(), b.cigar());
+                assert_eq!(a.sequence(), b.sequence());
+                assert_eq!(a.quality_scores(), b.quality_scores());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_file_header_without_md5_checksums() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::header::ReferenceSequence;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .build();
+
+        let mut writer = Writer::new(Vec::new());
+        assert!(writer.write_file_header(&header).is_err());
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_reference_mode(ReferenceMode::None)
+            .build();
+        writer.write_file_header(&header)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_compression_options() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::record::{quality_scores::Score, Flags, QualityScores, ReadName};
+
+        use crate::{record, Reader};
+
+        let header = sam::Header::default();
+
+        let records = (0..3)
+            .map(|i| {
+                let read_name = ReadName::try_from(format!("r{}", i).into_bytes())?;
+
+                let quality_scores = [40, 40, 35, 2]
+                    .iter()
+                    .map(|&n| Score::try_from(n + i))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(QualityScores::from)?;
+
+                let record = Record::builder()
+                    .set_bam_flags(Flags::UNMAPPED)
+                    .set_flags(record::Flags::QUALITY_SCORES_STORED_AS_ARRAY)
+                    .set_read_name(read_name)
+                    .set_read_length(4)
+                    .set_bases("ACGT".parse()?)
+                    .set_quality_scores(quality_scores)
+                    .build();
+
+                Ok(record)
+            })
+            .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+
+        for version in [Version::new(3, 0), Version::new(3, 1)] {
+            for profile in [
+                Profile::Fast,
+                Profile::Normal,
+                Profile::Small,
+                Profile::Archive,
+            ] {
+                for adaptive_block_compression in [false, true] {
+                    let mut writer = Writer::builder(Vec::new())
+                        .set_version(version)
+                        .set_profile(profile)
+                        .adaptive_block_compression(adaptive_block_compression)
+                        .build();
+
+                    writer.write_file_definition()?;
+                    writer.write_file_header(&header)?;
+
+                    for record in &records {
+                        writer.write_record(&header, record.clone())?;
+                    }
+
+                    writer.try_finish(&header)?;
+
+                    let mut reader = Reader::new(&writer.get_ref()[..]);
+                    let file_definition = reader.read_file_definition()?;
+                    assert_eq!(file_definition.version(), version);
+
+                    reader.read_file_header()?;
+
+                    let repository = fasta::Repository::default();
+                    let actual = reader
+                        .records(&repository, &header)
+                        .collect::<io::Result<Vec<_>>>()?;
+
+                    assert_eq!(actual.len(), records.len());
+
+                    for (a, b) in actual.iter().zip(&records) {
+                        assert_eq!(a.read_name(), b.read_name());
+                        assert_eq!(a.bases(), b.bases());
+                        assert_eq!(a.quality_scores(), b.quality_scores());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_multiple_workers() -> Result<(), Box<dyn std::error::Error>> {
+        use md5::{Digest, Md5};
+        use noodles_core::Position;
+        use sam::header::{reference_sequence::Md5Checksum, ReferenceSequence};
+
+        use crate::Reader;
+
+        // With the default profile, this spans 3 data containers.
+        const RECORD_COUNT: usize = 25000;
+
+        let reference_sequence = fasta::Record::new(
+            fasta::record::Definition::new("sq0", None),
+            fasta::record::Sequence::from(b"GATCTTACTTTTT".to_vec()),
+        );
+
+        let md5_checksum = Md5::digest(reference_sequence.sequence());
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name("sq0".parse()?)
+                    .set_length(reference_sequence.sequence().len())
+                    .set_md5_checksum(Md5Checksum::from(<[u8; 16]>::from(md5_checksum)))
+                    .build()?,
+            )
+            .build();
+
+        let alignment_records = (0..RECORD_COUNT)
+            .map(|i| {
+                let record = sam::alignment::Record::builder()
+                    .set_read_name(format!("r{}", i).parse()?)
+                    .set_flags(sam::record::Flags::empty())
+                    .set_reference_sequence_id(0)
+                    .set_alignment_start(Position::try_from(1 + i % 8)?)
+                    .set_cigar("4M".parse()?)
+                    .set_sequence("ACGT".parse()?)
+                    .set_quality_scores("NDLS".parse()?)
+                    .build();
+
+                Ok(record)
+            })
+            .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+
+        let repository = fasta::Repository::new(vec![reference_sequence]);
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_reference_sequence_repository(repository.clone())
+            .set_worker_count(3)
+            .build();
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for record in &alignment_records {
+            let r = Record::try_from_alignment_record(&header, record)?;
+            writer.write_record(&header, r)?;
+        }
+
+        writer.try_finish(&header)?;
+
+        let mut reader = Reader::builder(&writer.get_ref()[..])
+            .set_worker_count(3)
+            .build();
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let actual = reader
+            .records(&repository, &header)
+            .map(|result| result.and_then(|r| r.try_into_alignment_record(&header)))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(actual.len(), alignment_records.len());
+
+        for (a, b) in actual.iter().zip(&alignment_records) {
+            assert_eq!(a.read_name(), b.read_name());
+            assert_eq!(a.alignment_start(), b.alignment_start());
+            assert_eq!(a.sequence(), b.sequence());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_multiple_workers_and_adaptive_block_compression(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // With the default profile, this spans 3 data containers.
+        const RECORD_COUNT: usize = 25000;
+
+        let header = sam::Header::default();
+
+        let records = (0..RECORD_COUNT)
+            .map(|i| {
+                let record = sam::alignment::Record::builder()
+                    .set_read_name(format!("r{}", i).parse()?)
+                    .set_flags(sam::record::Flags::UNMAPPED)
+                    .set_sequence("ACGT".parse()?)
+                    .set_quality_scores("NDLS".parse()?)
+                    .build();
+
+                Record::try_from_alignment_record(&header, &record).map_err(|e| e.into())
+            })
+            .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+
+        let write = |records: &[Record]| -> io::Result<Vec<u8>> {
+            let mut writer = Writer::builder(Vec::new())
+                .set_version(Version::new(3, 1))
+                .adaptive_block_compression(true)
+                .set_worker_count(3)
+                .build();
+
+            writer.write_file_definition()?;
+            writer.write_file_header(&header)?;
+
+            for record in records {
+                writer.write_record(&header, record.clone())?;
+            }
+
+            writer.try_finish(&header)?;
+
+            Ok(writer.get_ref().clone())
+        };
+
+        assert_eq!(write(&records)?, write(&records)?);
+
+        Ok(())
+    }
+}
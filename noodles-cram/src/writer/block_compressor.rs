@@ -0,0 +1,191 @@
+use std::{collections::HashMap, io};
+
+use crate::container::{
+    block::{CompressionMethod, ContentType},
+    Block,
+};
+
+// The number of consecutive blocks compressed with every candidate method during a trial.
+const TRIAL_COUNT: usize = 3;
+
+// The number of blocks compressed with the selected method before the methods are re-evaluated.
+const TRIAL_SPAN: usize = 70;
+
+/// A block compressor that learns the best compression method of each block content ID.
+///
+/// The first blocks of each content ID are trial compressed with every candidate method, and the
+/// method with the smallest total output is used for the following blocks. Because the
+/// characteristics of the data may change over the course of a file, methods are periodically
+/// re-evaluated.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BlockCompressor {
+    metrics: HashMap<i32, Metrics>,
+}
+
+#[derive(Clone, Debug)]
+struct Metrics {
+    trials_remaining: usize,
+    blocks_until_trial: usize,
+    sizes: Vec<(CompressionMethod, u64)>,
+    method: Option<CompressionMethod>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            trials_remaining: TRIAL_COUNT,
+            blocks_until_trial: 0,
+            sizes: Vec::new(),
+            method: None,
+        }
+    }
+}
+
+impl BlockCompressor {
+    /// Compresses the given data into a block using one of the candidate methods.
+    pub fn compress(
+        &mut self,
+        content_type: ContentType,
+        content_id: i32,
+        data: Vec<u8>,
+        candidates: &[CompressionMethod],
+    ) -> io::Result<Block> {
+        self.compress_with_record_lengths(content_type, content_id, data, None, candidates)
+    }
+
+    /// Compresses the given data into a block using one of the candidate methods and the given
+    /// record lengths.
+    ///
+    /// See [`crate::container::block::Builder::compress_and_set_data`].
+    pub fn compress_with_record_lengths(
+        &mut self,
+        content_type: ContentType,
+        content_id: i32,
+        data: Vec<u8>,
+        record_lengths: Option<&[usize]>,
+        candidates: &[CompressionMethod],
+    ) -> io::Result<Block> {
+        let compress = |data, method| {
+            Block::builder()
+                .set_content_type(content_type)
+                .set_content_id(content_id)
+                .compress_and_set_data(data, method, record_lengths)
+                .map(|builder| builder.build())
+        };
+
+        let metrics = self.metrics.entry(content_id).or_default();
+
+        match (candidates, metrics.method) {
+            ([], _) => compress(data, CompressionMethod::None),
+            ([method], _) => compress(data, *method),
+            (_, Some(method)) if metrics.trials_remaining == 0 => {
+                metrics.blocks_until_trial -= 1;
+
+                if metrics.blocks_until_trial == 0 {
+                    metrics.trials_remaining = TRIAL_COUNT;
+                    metrics.sizes.clear();
+                }
+
+                compress(data, method)
+            }
+            _ => {
+                let mut best: Option<Block> = None;
+
+                for &method in candidates {
+                    let block = compress(data.clone(), method)?;
+                    let len = block.data().len() as u64;
+
+                    match metrics.sizes.iter_mut().find(|(m, _)| *m == method) {
+                        Some((_, size)) => *size += len,
+                        None => metrics.sizes.push((method, len)),
+                    }
+
+                    if best
+                        .as_ref()
+                        .map(|b| len < b.data().len() as u64)
+                        .unwrap_or(true)
+                    {
+                        best = Some(block);
+                    }
+                }
+
+                metrics.trials_remaining -= 1;
+
+                if metrics.trials_remaining == 0 {
+                    metrics.method = metrics
+                        .sizes
+                        .iter()
+                        .min_by_key(|(_, size)| *size)
+                        .map(|(method, _)| *method);
+
+                    metrics.blocks_until_trial = TRIAL_SPAN;
+                }
+
+                // `candidates` has at least 2 methods.
+                Ok(best.unwrap())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compress() -> io::Result<()> {
+        const CANDIDATES: [CompressionMethod; 2] =
+            [CompressionMethod::None, CompressionMethod::Gzip];
+
+        let mut compressor = BlockCompressor::default();
+
+        let compressible_data = vec![0; 1024];
+        let incompressible_data = b"ndls".to_vec();
+
+        for _ in 0..TRIAL_COUNT {
+            let block = compressor.compress(
+                ContentType::ExternalData,
+                1,
+                compressible_data.clone(),
+                &CANDIDATES,
+            )?;
+
+            assert_eq!(block.compression_method(), CompressionMethod::Gzip);
+        }
+
+        // The learned method is used even when it is not the best for a given block.
+        for _ in 0..TRIAL_SPAN {
+            let block = compressor.compress(
+                ContentType::ExternalData,
+                1,
+                incompressible_data.clone(),
+                &CANDIDATES,
+            )?;
+
+            assert_eq!(block.compression_method(), CompressionMethod::Gzip);
+            assert_eq!(&block.decompressed_data()?[..], &incompressible_data[..]);
+        }
+
+        // Methods are then re-evaluated.
+        let block = compressor.compress(
+            ContentType::ExternalData,
+            1,
+            incompressible_data.clone(),
+            &CANDIDATES,
+        )?;
+
+        assert_eq!(block.compression_method(), CompressionMethod::None);
+
+        // Each block content ID is evaluated separately.
+        let block = compressor.compress(
+            ContentType::ExternalData,
+            2,
+            incompressible_data,
+            &CANDIDATES,
+        )?;
+
+        assert_eq!(block.compression_method(), CompressionMethod::None);
+
+        Ok(())
+    }
+}
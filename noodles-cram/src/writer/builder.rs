@@ -2,14 +2,15 @@ use std::io::Write;
 
 use noodles_fasta as fasta;
 
-use super::{Options, Writer};
-use crate::DataContainer;
+use super::{BlockCompressor, Encoder, Options, Profile, ReferenceMode, Writer};
+use crate::{file_definition::Version, DataContainer};
 
 /// A CRAM writer builder.
 pub struct Builder<W> {
     inner: W,
     reference_sequence_repository: fasta::Repository,
     options: Options,
+    worker_count: usize,
 }
 
 impl<W> Builder<W>
@@ -21,6 +22,7 @@ where
             inner,
             reference_sequence_repository: fasta::Repository::default(),
             options: Options::default(),
+            worker_count: 1,
         }
     }
 
@@ -33,6 +35,27 @@ where
         self
     }
 
+    /// Sets the reference mode.
+    ///
+    /// This determines whether readers need an external reference sequence to decode records.
+    /// See [`ReferenceMode`] for the available modes.
+    ///
+    /// The default is [`ReferenceMode::External`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, writer::ReferenceMode};
+    ///
+    /// let writer = cram::Writer::builder(Vec::new())
+    ///     .set_reference_mode(ReferenceMode::None)
+    ///     .build();
+    /// ```
+    pub fn set_reference_mode(mut self, reference_mode: ReferenceMode) -> Self {
+        self.options.reference_mode = reference_mode;
+        self
+    }
+
     /// Sets whether to preserve read names.
     ///
     /// If `false`, read names are discarded.
@@ -53,6 +76,100 @@ where
         self
     }
 
+    /// Sets the CRAM format version.
+    ///
+    /// Only versions 3.0 and 3.1 can be written. Writing the file definition fails if any other
+    /// version is set. CRAM 3.1 enables the rANS Nx16, adaptive arithmetic coding, fqzcomp and
+    /// name tokenization codecs.
+    ///
+    /// The default is 3.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, file_definition::Version};
+    ///
+    /// let writer = cram::Writer::builder(Vec::new())
+    ///     .set_version(Version::new(3, 1))
+    ///     .build();
+    /// ```
+    pub fn set_version(mut self, version: Version) -> Self {
+        self.options.version = version;
+        self
+    }
+
+    /// Sets the compression profile.
+    ///
+    /// The profile picks the compression method of each block and the number of records per
+    /// slice.
+    ///
+    /// The default is [`Profile::Normal`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, writer::Profile};
+    ///
+    /// let writer = cram::Writer::builder(Vec::new())
+    ///     .set_profile(Profile::Archive)
+    ///     .build();
+    /// ```
+    pub fn set_profile(mut self, profile: Profile) -> Self {
+        self.options.profile = profile;
+        self
+    }
+
+    /// Sets whether to adaptively select the compression method of each block.
+    ///
+    /// If `true`, the first blocks of each data series are compressed with every candidate
+    /// method of the compression profile, and the method with the smallest output is used for
+    /// subsequent blocks. Methods are periodically re-evaluated. This improves the compression
+    /// ratio at the cost of slower encoding during trials.
+    ///
+    /// If `false`, each block is compressed with the single method chosen by the compression
+    /// profile.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    ///
+    /// let writer = cram::Writer::builder(Vec::new())
+    ///     .adaptive_block_compression(true)
+    ///     .build();
+    /// ```
+    pub fn adaptive_block_compression(mut self, value: bool) -> Self {
+        self.options.adaptive_block_compression = value;
+        self
+    }
+
+    /// Sets a worker count.
+    ///
+    /// When the worker count is greater than 1, data containers are built and compressed on a
+    /// pool of worker threads. Containers are still written in the order their records were
+    /// given.
+    ///
+    /// Adaptive block compression statistics (see [`Self::adaptive_block_compression`]) are kept
+    /// by the writer and given to the worker encoding the next container, so the output does not
+    /// depend on how the containers are scheduled. The reference sequences used by a container are
+    /// read from the reference sequence repository on the calling thread.
+    ///
+    /// By default, the worker count is set to 1, i.e., containers are encoded on the calling
+    /// thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let writer = cram::Writer::builder(Vec::new()).set_worker_count(8).build();
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: usize) -> Self {
+        self.worker_count = worker_count;
+        self
+    }
+
     /// Builds a CRAM writer.
     ///
     /// # Examples
@@ -62,12 +179,23 @@ where
     /// let writer = cram::Writer::builder(Vec::new()).build();
     /// ```
     pub fn build(self) -> Writer<W> {
+        let data_container_builder = DataContainer::builder(0, &self.options);
+
+        let encoder = if self.worker_count > 1 {
+            Some(Encoder::new(self.worker_count, &self.options))
+        } else {
+            None
+        };
+
         Writer {
             inner: self.inner,
             reference_sequence_repository: self.reference_sequence_repository,
             options: self.options,
-            data_container_builder: DataContainer::builder(0),
+            block_compressor: BlockCompressor::default(),
+            data_container_builder,
             record_counter: 0,
+            encoder,
+            header: None,
         }
     }
 }
@@ -0,0 +1,101 @@
+use std::{io, sync::Arc};
+
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+
+use super::{data_container::write_data_container, BlockCompressor, Options};
+use crate::{data_container, worker_pool::WorkerPool};
+
+struct Request {
+    data_container_builder: data_container::Builder,
+    reference_sequences: Vec<fasta::Record>,
+    header: Arc<sam::Header>,
+    block_compressor: BlockCompressor,
+}
+
+type Output = io::Result<(Vec<u8>, BlockCompressor)>;
+
+/// A data container encoder that builds and compresses containers on worker threads.
+///
+/// Each container is sent with the block compressor to use, which is returned with the encoded
+/// container. Encoded containers are received in the order they were sent.
+#[derive(Debug)]
+pub(super) struct Encoder {
+    pool: WorkerPool<Request, Output>,
+}
+
+impl Encoder {
+    pub(super) fn new(worker_count: usize, options: &Options) -> Self {
+        let options = options.clone();
+
+        let pool = WorkerPool::new(worker_count, move |request: Request| {
+            let mut block_compressor = request.block_compressor;
+
+            let buf = encode(
+                &options,
+                &mut block_compressor,
+                request.data_container_builder,
+                request.reference_sequences,
+                &request.header,
+            )?;
+
+            Ok((buf, block_compressor))
+        });
+
+        Self { pool }
+    }
+
+    /// Returns whether each worker has a queued container.
+    pub(super) fn is_full(&self) -> bool {
+        self.pool.is_full()
+    }
+
+    /// Queues a data container to be encoded.
+    ///
+    /// `reference_sequences` are the reference sequences the slices of the container refer to.
+    pub(super) fn send(
+        &mut self,
+        data_container_builder: data_container::Builder,
+        reference_sequences: Vec<fasta::Record>,
+        header: Arc<sam::Header>,
+        block_compressor: BlockCompressor,
+    ) {
+        self.pool.send(Request {
+            data_container_builder,
+            reference_sequences,
+            header,
+            block_compressor,
+        });
+    }
+
+    /// Receives the oldest queued container, waiting for it to be encoded.
+    ///
+    /// This returns the encoded container and the block compressor after encoding it.
+    pub(super) fn recv(&mut self) -> Option<Output> {
+        self.pool.recv().map(|result| result?)
+    }
+}
+
+fn encode(
+    options: &Options,
+    block_compressor: &mut BlockCompressor,
+    data_container_builder: data_container::Builder,
+    reference_sequences: Vec<fasta::Record>,
+    header: &sam::Header,
+) -> io::Result<Vec<u8>> {
+    let reference_sequence_repository = fasta::Repository::new(reference_sequences);
+
+    let base_count = data_container_builder.base_count();
+
+    let data_container = data_container_builder.build(
+        options,
+        block_compressor,
+        &reference_sequence_repository,
+        header,
+    )?;
+
+    let mut buf = Vec::new();
+    write_data_container(&mut buf, &data_container, base_count)?;
+
+    Ok(buf)
+}
@@ -9,11 +9,17 @@ use self::header::write_header;
 use super::container::write_block;
 use crate::container::{block::ContentType, Block};
 
-pub fn write_header_container<W>(writer: &mut W, header: &sam::Header) -> io::Result<()>
+pub fn write_header_container<W>(
+    writer: &mut W,
+    header: &sam::Header,
+    validate_md5_checksums: bool,
+) -> io::Result<()>
 where
     W: Write,
 {
-    validate_reference_sequences(header.reference_sequences())?;
+    if validate_md5_checksums {
+        validate_reference_sequences(header.reference_sequences())?;
+    }
 
     let header_data = header.to_string().into_bytes();
     let header_data_len = i32::try_from(header_data.len())
@@ -61,7 +67,7 @@ mod tests {
             .build();
 
         let mut buf = Vec::new();
-        write_header_container(&mut buf, &header)?;
+        write_header_container(&mut buf, &header, true)?;
 
         let mut expected = Vec::new();
 
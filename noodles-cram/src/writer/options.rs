@@ -1,7 +1,14 @@
+use super::{Profile, ReferenceMode};
+use crate::file_definition::Version;
+
 #[derive(Clone, Debug)]
 pub struct Options {
     pub preserve_read_names: bool,
     pub encode_alignment_start_positions_as_deltas: bool,
+    pub version: Version,
+    pub profile: Profile,
+    pub adaptive_block_compression: bool,
+    pub reference_mode: ReferenceMode,
 }
 
 impl Default for Options {
@@ -9,6 +16,10 @@ impl Default for Options {
         Self {
             preserve_read_names: true,
             encode_alignment_start_positions_as_deltas: true,
+            version: Version::default(),
+            profile: Profile::default(),
+            adaptive_block_compression: false,
+            reference_mode: ReferenceMode::default(),
         }
     }
 }
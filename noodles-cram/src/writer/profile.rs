@@ -0,0 +1,214 @@
+//! CRAM writer compression profile.
+
+use crate::{
+    container::block::CompressionMethod,
+    data_container::compression_header::data_series_encoding_map::DataSeries,
+    file_definition::Version,
+};
+
+/// A CRAM writer compression profile.
+///
+/// A profile trades encoding speed for output size. It picks the compression method of each
+/// block and the number of records per slice. These mirror the profiles of samtools.
+///
+/// Only the records per slice and the compression methods vary by profile. Every profile writes
+/// one slice per container.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Profile {
+    /// Fast encoding with larger output.
+    Fast,
+    /// A balance of encoding speed and output size.
+    Normal,
+    /// Smaller output with slower encoding.
+    Small,
+    /// The smallest output with the slowest encoding.
+    Archive,
+}
+
+impl Profile {
+    pub(crate) fn records_per_slice(self) -> usize {
+        match self {
+            Self::Fast | Self::Normal => 10000,
+            Self::Small => 25000,
+            Self::Archive => 100000,
+        }
+    }
+
+    /// Returns the compression method of a block.
+    ///
+    /// `data_series` is the data series stored in the block or `None` for the core data block
+    /// and tag blocks. Codecs introduced in CRAM 3.1 are only used when `version` is at least
+    /// 3.1.
+    pub(crate) fn compression_method(
+        self,
+        version: Version,
+        data_series: Option<DataSeries>,
+    ) -> CompressionMethod {
+        if !has_3_1_codecs(version) {
+            return self.compression_method_3_0(data_series);
+        }
+
+        match (self, data_series) {
+            (Self::Fast, _) => CompressionMethod::Gzip,
+            (_, Some(DataSeries::ReadNames)) => CompressionMethod::NameTokenizer,
+            (Self::Small | Self::Archive, Some(DataSeries::QualityScores)) => {
+                CompressionMethod::Fqzcomp
+            }
+            (Self::Normal, _) => CompressionMethod::RansNx16,
+            (Self::Small, _) => CompressionMethod::Bzip2,
+            (Self::Archive, _) => CompressionMethod::AdaptiveArithmeticCoding,
+        }
+    }
+
+    /// Returns the candidate compression methods of a block.
+    ///
+    /// This always includes the method given by [`Self::compression_method`]. When adaptive block
+    /// compression is enabled, the writer picks the method that produces the smallest output.
+    pub(crate) fn compression_methods(
+        self,
+        version: Version,
+        data_series: Option<DataSeries>,
+    ) -> Vec<CompressionMethod> {
+        let mut methods = match self {
+            Self::Fast => vec![CompressionMethod::Gzip],
+            Self::Normal => vec![CompressionMethod::Gzip],
+            Self::Small => vec![CompressionMethod::Gzip, CompressionMethod::Bzip2],
+            Self::Archive => vec![
+                CompressionMethod::Gzip,
+                CompressionMethod::Bzip2,
+                CompressionMethod::Lzma,
+            ],
+        };
+
+        if self != Self::Fast {
+            if has_3_1_codecs(version) {
+                methods.push(CompressionMethod::RansNx16);
+
+                if self == Self::Archive {
+                    methods.push(CompressionMethod::AdaptiveArithmeticCoding);
+                }
+
+                match data_series {
+                    Some(DataSeries::ReadNames) => methods.push(CompressionMethod::NameTokenizer),
+                    Some(DataSeries::QualityScores) if self != Self::Normal => {
+                        methods.push(CompressionMethod::Fqzcomp)
+                    }
+                    _ => {}
+                }
+            } else {
+                methods.push(CompressionMethod::Rans4x8);
+            }
+        }
+
+        methods
+    }
+
+    fn compression_method_3_0(self, data_series: Option<DataSeries>) -> CompressionMethod {
+        match (self, data_series) {
+            (Self::Fast, _) | (Self::Normal, Some(DataSeries::ReadNames)) => {
+                CompressionMethod::Gzip
+            }
+            (Self::Normal, _) | (_, Some(DataSeries::QualityScores)) => CompressionMethod::Rans4x8,
+            (Self::Small, _) => CompressionMethod::Bzip2,
+            (Self::Archive, _) => CompressionMethod::Lzma,
+        }
+    }
+}
+
+fn has_3_1_codecs(version: Version) -> bool {
+    (version.major(), version.minor()) >= (3, 1)
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_method() {
+        const METHODS_3_1: [CompressionMethod; 4] = [
+            CompressionMethod::RansNx16,
+            CompressionMethod::AdaptiveArithmeticCoding,
+            CompressionMethod::Fqzcomp,
+            CompressionMethod::NameTokenizer,
+        ];
+
+        let profiles = [
+            Profile::Fast,
+            Profile::Normal,
+            Profile::Small,
+            Profile::Archive,
+        ];
+
+        let data_series = DataSeries::ALL.iter().copied().map(Some).chain([None]);
+
+        for profile in profiles {
+            for ds in data_series.clone() {
+                let method = profile.compression_method(Version::new(3, 0), ds);
+                assert!(!METHODS_3_1.contains(&method));
+            }
+        }
+
+        let version = Version::new(3, 1);
+
+        assert_eq!(
+            Profile::Normal.compression_method(version, Some(DataSeries::ReadNames)),
+            CompressionMethod::NameTokenizer
+        );
+        assert_eq!(
+            Profile::Archive.compression_method(version, Some(DataSeries::QualityScores)),
+            CompressionMethod::Fqzcomp
+        );
+        assert_eq!(
+            Profile::Archive.compression_method(version, None),
+            CompressionMethod::AdaptiveArithmeticCoding
+        );
+        assert_eq!(
+            Profile::Fast.compression_method(version, Some(DataSeries::ReadNames)),
+            CompressionMethod::Gzip
+        );
+    }
+
+    #[test]
+    fn test_compression_methods() {
+        let profiles = [
+            Profile::Fast,
+            Profile::Normal,
+            Profile::Small,
+            Profile::Archive,
+        ];
+
+        let versions = [Version::new(3, 0), Version::new(3, 1)];
+        let data_series = DataSeries::ALL.iter().copied().map(Some).chain([None]);
+
+        for profile in profiles {
+            for version in versions {
+                for ds in data_series.clone() {
+                    let method = profile.compression_method(version, ds);
+                    let methods = profile.compression_methods(version, ds);
+                    assert!(methods.contains(&method));
+                }
+            }
+        }
+
+        assert_eq!(
+            Profile::Fast.compression_methods(Version::new(3, 1), Some(DataSeries::ReadNames)),
+            [CompressionMethod::Gzip]
+        );
+
+        assert_eq!(
+            Profile::Normal.compression_methods(Version::new(3, 0), None),
+            [CompressionMethod::Gzip, CompressionMethod::Rans4x8]
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Profile::default(), Profile::Normal);
+    }
+}
@@ -88,6 +88,10 @@ where
         }
     }
 
+    pub fn external_data_writers(&self) -> &HashMap<i32, X> {
+        self.external_data_writers
+    }
+
     pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
         self.write_bam_bit_flags(record.bam_flags())?;
         self.write_cram_bit_flags(record.cram_flags())?;
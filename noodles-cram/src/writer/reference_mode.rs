@@ -0,0 +1,36 @@
+//! CRAM writer reference mode.
+
+/// A CRAM writer reference mode.
+///
+/// The reference mode determines whether readers need an external reference sequence to decode
+/// the output.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReferenceMode {
+    /// Slices refer to an external reference sequence.
+    ///
+    /// Readers must have the reference sequence to decode records.
+    External,
+    /// Slices include the region of the reference sequence they cover in an embedded reference
+    /// block.
+    ///
+    /// The reference sequence repository is still used to read the reference sequence, but
+    /// readers do not need it.
+    Embedded,
+    /// Slices do not use a reference sequence.
+    ///
+    /// Bases are stored verbatim, and no reference sequence repository is needed to write or read
+    /// records. Header reference sequences are not required to have MD5 checksums.
+    None,
+}
+
+impl ReferenceMode {
+    pub(crate) fn is_reference_required(self) -> bool {
+        self == Self::External
+    }
+}
+
+impl Default for ReferenceMode {
+    fn default() -> Self {
+        Self::External
+    }
+}
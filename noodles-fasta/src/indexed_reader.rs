@@ -0,0 +1,201 @@
+//! Indexed FASTA reader.
+
+mod bgzf_or_uncompressed;
+mod builder;
+
+pub use self::{bgzf_or_uncompressed::BgzfOrUncompressed, builder::Builder};
+
+use std::io::{self, BufRead, Seek};
+
+use noodles_core::Region;
+
+use super::{fai, Reader, Record};
+
+/// An indexed FASTA reader.
+///
+/// This is a FASTA reader with its associated FASTA index (FAI). The underlying stream can be
+/// uncompressed or, when opened using [`Builder`], bgzip-compressed with an associated gzip index
+/// (GZI).
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+    index: fai::Index,
+}
+
+impl IndexedReader<BgzfOrUncompressed<std::fs::File>> {
+    /// Creates an indexed FASTA reader builder for paths on a filesystem.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta as fasta;
+    /// let builder = fasta::IndexedReader::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: BufRead,
+{
+    /// Creates an indexed FASTA reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fasta::{self as fasta, fai};
+    /// let index = fai::Index::default();
+    /// let reader = fasta::IndexedReader::new(io::empty(), index);
+    /// ```
+    pub fn new(inner: R, index: fai::Index) -> Self {
+        Self {
+            inner: Reader::new(inner),
+            index,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fasta::{self as fasta, fai};
+    /// let reader = fasta::IndexedReader::new(io::empty(), fai::Index::default());
+    /// let _inner = reader.get_ref();
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fasta::{self as fasta, fai};
+    /// let mut reader = fasta::IndexedReader::new(io::empty(), fai::Index::default());
+    /// let _inner = reader.get_mut();
+    /// ```
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fasta::{self as fasta, fai};
+    /// let reader = fasta::IndexedReader::new(io::empty(), fai::Index::default());
+    /// let _inner = reader.into_inner();
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Returns the associated FASTA index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fasta::{self as fasta, fai};
+    /// let reader = fasta::IndexedReader::new(io::empty(), fai::Index::default());
+    /// assert!(reader.index().is_empty());
+    /// ```
+    pub fn index(&self) -> &fai::Index {
+        &self.index
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: BufRead + Seek,
+{
+    /// Returns a record of the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// use noodles_fasta::{self as fasta, fai, record::{Definition, Sequence}};
+    ///
+    /// let data = b">sq0\nNNNN\n>sq1\nACGT\n";
+    /// let index = vec![
+    ///     fai::Record::new(String::from("sq0"), 4, 5, 4, 5),
+    ///     fai::Record::new(String::from("sq1"), 4, 15, 4, 5),
+    /// ];
+    ///
+    /// let mut reader = fasta::IndexedReader::new(Cursor::new(data), index);
+    ///
+    /// let region = "sq1:2-3".parse()?;
+    /// let record = reader.query(&region)?;
+    /// assert_eq!(record, fasta::Record::new(
+    ///     Definition::new("sq1:2-3", None),
+    ///     Sequence::from(b"CG".to_vec()),
+    /// ));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query(&mut self, region: &Region) -> io::Result<Record> {
+        self.inner.query(&self.index, region)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use noodles_bgzf::{self as bgzf, gzi};
+
+    use super::*;
+    use crate::record::{Definition, Sequence};
+
+    #[test]
+    fn test_query_with_bgzf_compressed_source() -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = bgzf::Writer::new(Vec::new());
+        writer.write_all(b">sq0\nNNNN\n")?;
+        writer.flush()?;
+        writer.write_all(b">sq1\nACGT\n")?;
+        let data = writer.finish()?;
+
+        let gzi_index = gzi::Indexer::new(&data[..]).index()?;
+
+        let index = vec![
+            fai::Record::new(String::from("sq0"), 4, 5, 4, 5),
+            fai::Record::new(String::from("sq1"), 4, 15, 4, 5),
+        ];
+
+        let inner =
+            BgzfOrUncompressed::Bgzf(bgzf::IndexedReader::new(Cursor::new(data), gzi_index));
+        let mut reader = IndexedReader::new(inner, index);
+
+        let region = "sq1:2-3".parse()?;
+        let record = reader.query(&region)?;
+
+        assert_eq!(
+            record,
+            Record::new(
+                Definition::new("sq1:2-3", None),
+                Sequence::from(b"CG".to_vec())
+            )
+        );
+
+        let region = "sq0".parse()?;
+        let record = reader.query(&region)?;
+
+        assert_eq!(
+            record,
+            Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"NNNN".to_vec())
+            )
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+
+use noodles_bgzf as bgzf;
+
+/// A buffered FASTA stream that is either uncompressed or bgzip-compressed.
+///
+/// Both variants implement [`std::io::Seek`] by uncompressed position, which is what a FASTA
+/// index (FAI) stores. A bgzip-compressed stream requires an associated gzip index (GZI) to seek.
+pub enum BgzfOrUncompressed<R> {
+    /// A bgzip-compressed stream.
+    Bgzf(bgzf::IndexedReader<R>),
+    /// An uncompressed stream.
+    Uncompressed(io::BufReader<R>),
+}
+
+impl<R> Read for BgzfOrUncompressed<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Bgzf(reader) => reader.read(buf),
+            Self::Uncompressed(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl<R> BufRead for BgzfOrUncompressed<R>
+where
+    R: Read,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match self {
+            Self::Bgzf(reader) => reader.fill_buf(),
+            Self::Uncompressed(reader) => reader.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match self {
+            Self::Bgzf(reader) => reader.consume(amt),
+            Self::Uncompressed(reader) => reader.consume(amt),
+        }
+    }
+}
+
+impl<R> Seek for BgzfOrUncompressed<R>
+where
+    R: Read + Seek,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            Self::Bgzf(reader) => reader.seek(pos),
+            Self::Uncompressed(reader) => reader.seek(pos),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::{
+    ffi::{OsStr, OsString},
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+};
+
+use noodles_bgzf::{self as bgzf, gzi};
+
+use super::{BgzfOrUncompressed, IndexedReader};
+use crate::fai;
+
+/// An indexed FASTA reader builder.
+///
+/// This is a convenience builder for creating an indexed FASTA reader from paths on a filesystem.
+///
+/// By default, it opens the source path (`src`) and reads its associated index at `<src>.fai`. If
+/// the source has a `.gz` or `.bgz` extension, it is treated as a bgzip-compressed FASTA, and its
+/// gzip index is read from `<src>.gzi`. The index locations can be overridden by calling
+/// [`Self::set_index_src`] and [`Self::set_gzi_index_src`].
+#[derive(Debug, Default)]
+pub struct Builder {
+    index_src: Option<PathBuf>,
+    gzi_index_src: Option<PathBuf>,
+}
+
+impl Builder {
+    /// Sets the FASTA index source path.
+    ///
+    /// When set, this path is used instead of inferring one from the given source path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta as fasta;
+    /// let builder = fasta::IndexedReader::builder().set_index_src("reference.fa.fai");
+    /// ```
+    pub fn set_index_src<P>(mut self, index_src: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.index_src = Some(index_src.into());
+        self
+    }
+
+    /// Sets the gzip index source path.
+    ///
+    /// When set, this path is used instead of inferring one from the given source path. Setting
+    /// this also marks the source as bgzip-compressed, regardless of its extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta as fasta;
+    /// let builder = fasta::IndexedReader::builder().set_gzi_index_src("reference.fa.gz.gzi");
+    /// ```
+    pub fn set_gzi_index_src<P>(mut self, gzi_index_src: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.gzi_index_src = Some(gzi_index_src.into());
+        self
+    }
+
+    /// Opens an indexed FASTA reader from the given path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::io;
+    /// use noodles_fasta as fasta;
+    /// let reader = fasta::IndexedReader::builder().open("reference.fa.gz")?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn open<P>(self, src: P) -> io::Result<IndexedReader<BgzfOrUncompressed<File>>>
+    where
+        P: AsRef<Path>,
+    {
+        let src = src.as_ref();
+
+        let index_src = self
+            .index_src
+            .unwrap_or_else(|| push_ext(src.to_path_buf(), "fai"));
+        let index = fai::read(index_src)?;
+
+        let file = File::open(src)?;
+
+        let gzi_index_src = self.gzi_index_src.or_else(|| {
+            if is_bgzip_compressed(src) {
+                Some(push_ext(src.to_path_buf(), "gzi"))
+            } else {
+                None
+            }
+        });
+
+        let inner = match gzi_index_src {
+            Some(gzi_index_src) => {
+                let gzi_index = gzi::read(gzi_index_src)?;
+                BgzfOrUncompressed::Bgzf(bgzf::IndexedReader::new(file, gzi_index))
+            }
+            None => BgzfOrUncompressed::Uncompressed(io::BufReader::new(file)),
+        };
+
+        Ok(IndexedReader::new(inner, index))
+    }
+}
+
+fn is_bgzip_compressed(src: &Path) -> bool {
+    matches!(
+        src.extension().and_then(|ext| ext.to_str()),
+        Some("gz" | "bgz")
+    )
+}
+
+fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
+where
+    S: AsRef<OsStr>,
+{
+    let mut s = OsString::from(path);
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_bgzip_compressed() {
+        assert!(is_bgzip_compressed(Path::new("reference.fa.gz")));
+        assert!(is_bgzip_compressed(Path::new("reference.fa.bgz")));
+        assert!(!is_bgzip_compressed(Path::new("reference.fa")));
+        assert!(!is_bgzip_compressed(Path::new("reference")));
+    }
+
+    #[test]
+    fn test_push_ext() {
+        assert_eq!(
+            push_ext(PathBuf::from("reference.fa.gz"), "gzi"),
+            PathBuf::from("reference.fa.gz.gzi")
+        );
+    }
+}
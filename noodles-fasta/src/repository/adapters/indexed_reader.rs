@@ -4,20 +4,19 @@ pub use self::builder::Builder;
 
 use std::{
     fs::File,
-    io::{self, BufRead, BufReader, Seek},
+    io::{self, BufRead, Seek},
 };
 
 use noodles_core::Region;
 
-use crate::{fai, repository::Adapter, Reader, Record};
+use crate::{fai, indexed_reader::BgzfOrUncompressed, repository::Adapter, Reader, Record};
 
 /// An indexed reader adapter.
 pub struct IndexedReader<R> {
-    reader: Reader<R>,
-    index: fai::Index,
+    inner: crate::IndexedReader<R>,
 }
 
-impl IndexedReader<BufReader<File>> {
+impl IndexedReader<BgzfOrUncompressed<File>> {
     /// Creates an indexed reader adapter builder for paths on a filesystem.
     ///
     /// # Examples
@@ -47,7 +46,13 @@ where
     /// let adapter = IndexedReader::new(reader, index);
     /// ```
     pub fn new(reader: Reader<R>, index: fai::Index) -> Self {
-        Self { reader, index }
+        Self::from(crate::IndexedReader::new(reader.into_inner(), index))
+    }
+}
+
+impl<R> From<crate::IndexedReader<R>> for IndexedReader<R> {
+    fn from(inner: crate::IndexedReader<R>) -> Self {
+        Self { inner }
     }
 }
 
@@ -57,6 +62,6 @@ where
 {
     fn get(&mut self, name: &str) -> Option<io::Result<Record>> {
         let region = Region::new(name, ..);
-        Some(self.reader.query(&self.index, &region))
+        Some(self.inner.query(&region))
     }
 }
@@ -1,23 +1,19 @@
-use std::{
-    ffi::{OsStr, OsString},
-    fs::File,
-    io::{self, BufReader},
-    path::{Path, PathBuf},
-};
+use std::{fs::File, io, path::Path};
 
 use super::IndexedReader;
-use crate::{fai, Reader};
+use crate::indexed_reader::{self, BgzfOrUncompressed};
 
 /// An indexed reader adapter builder.
 ///
 /// This is a convenience builder for creating an indexed reader adapter from paths on a
 /// filesystem.
 ///
-/// By default, it opens a [`Reader`] for a source path (`src`) and reads its associated index at
-/// `<src>.fai`. The index location can be overridden by calling [`set_index_src`].
+/// By default, it opens the source path (`src`) and reads its associated index at `<src>.fai`.
+/// Sources with a `.gz` or `.bgz` extension are read as bgzip-compressed FASTA using the gzip
+/// index at `<src>.gzi`. See [`crate::indexed_reader::Builder`].
 #[derive(Default)]
 pub struct Builder {
-    index_src: Option<PathBuf>,
+    inner: indexed_reader::Builder,
 }
 
 impl Builder {
@@ -33,16 +29,34 @@ impl Builder {
     /// ```
     pub fn set_index_src<P>(mut self, index_src: P) -> Self
     where
-        P: Into<PathBuf>,
+        P: Into<std::path::PathBuf>,
     {
-        self.index_src = Some(index_src.into());
+        self.inner = self.inner.set_index_src(index_src);
+        self
+    }
+
+    /// Sets the gzip index source path.
+    ///
+    /// When set, the source is read as bgzip-compressed FASTA using this gzip index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::repository::adapters::IndexedReader;
+    /// let builder = IndexedReader::builder().set_gzi_index_src("reference.fa.gz.gzi");
+    /// ```
+    pub fn set_gzi_index_src<P>(mut self, gzi_index_src: P) -> Self
+    where
+        P: Into<std::path::PathBuf>,
+    {
+        self.inner = self.inner.set_gzi_index_src(gzi_index_src);
         self
     }
 
     /// Creates an indexed reader adapter from the given path.
     ///
     /// By default, `<src>.fai` is used as the path to the associated index. This can be overridden
-    /// by calling [`set_index_src`].
+    /// by calling [`Self::set_index_src`].
     ///
     /// # Examples
     ///
@@ -52,42 +66,10 @@ impl Builder {
     /// let adapter = IndexedReader::builder().open("reference.fa")?;
     /// # Ok::<_, io::Error>(())
     /// ```
-    pub fn open<P>(self, src: P) -> io::Result<IndexedReader<BufReader<File>>>
+    pub fn open<P>(self, src: P) -> io::Result<IndexedReader<BgzfOrUncompressed<File>>>
     where
         P: AsRef<Path>,
     {
-        let src = src.as_ref();
-
-        let reader = File::open(src).map(BufReader::new).map(Reader::new)?;
-
-        let index_src = self
-            .index_src
-            .unwrap_or_else(|| push_ext(src.to_path_buf(), "fai"));
-        let index = fai::read(index_src)?;
-
-        Ok(IndexedReader::new(reader, index))
-    }
-}
-
-fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
-where
-    S: AsRef<OsStr>,
-{
-    let mut s = OsString::from(path);
-    s.push(".");
-    s.push(ext);
-    PathBuf::from(s)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_push_ext() {
-        assert_eq!(
-            push_ext(PathBuf::from("reference.fa"), "fai"),
-            PathBuf::from("reference.fa.fai")
-        );
+        self.inner.open(src).map(IndexedReader::from)
     }
 }
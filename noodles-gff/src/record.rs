@@ -12,7 +12,7 @@ pub use self::{
 
 use std::{error, fmt, num, str::FromStr};
 
-use noodles_core::Position;
+use noodles_core::{Position, Region};
 
 pub(crate) const NULL_FIELD: &str = ".";
 const FIELD_DELIMITER: char = '\t';
@@ -180,6 +180,15 @@ impl Default for Record {
     }
 }
 
+impl From<&Record> for Region {
+    fn from(record: &Record) -> Self {
+        Self::new(
+            record.reference_sequence_name(),
+            record.start()..=record.end(),
+        )
+    }
+}
+
 impl fmt::Display for Record {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -350,6 +359,17 @@ mod tests {
         assert_eq!(record.to_string(), ".\t.\t.\t1\t1\t.\t.\t.\t.");
     }
 
+    #[test]
+    fn test_from_record_for_region() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id=g0".parse()?;
+        let region = Region::from(&record);
+        assert_eq!(
+            region,
+            Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?)
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_from_str() -> Result<(), Box<dyn std::error::Error>> {
         let s = "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id=ndls0;gene_name=gene0";
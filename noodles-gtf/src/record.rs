@@ -9,7 +9,7 @@ pub use self::{attributes::Attributes, builder::Builder, frame::Frame, strand::S
 
 use std::{error, fmt, num, str::FromStr};
 
-use noodles_core::Position;
+use noodles_core::{Position, Region};
 
 pub(crate) const NULL_FIELD: &str = ".";
 
@@ -174,6 +174,15 @@ impl Default for Record {
     }
 }
 
+impl From<&Record> for Region {
+    fn from(record: &Record) -> Self {
+        Self::new(
+            record.reference_sequence_name(),
+            record.start()..=record.end(),
+        )
+    }
+}
+
 impl fmt::Display for Record {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -399,6 +408,17 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_from_record_for_region() -> Result<(), Box<dyn std::error::Error>> {
+        let record: Record = "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id \"g0\";".parse()?;
+        let region = Region::from(&record);
+        assert_eq!(
+            region,
+            Region::new("sq0", Position::try_from(8)?..=Position::try_from(13)?)
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_from_str() -> Result<(), noodles_core::position::TryFromIntError> {
         let s = "sq0\tNOODLES\tgene\t8\t13\t.\t+\t.\tgene_id \"g0\"; transcript_id \"t0\";";
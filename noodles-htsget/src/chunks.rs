@@ -1,19 +1,22 @@
 use std::pin::Pin;
 
 use bytes::Bytes;
-use futures::{stream, Stream, TryStreamExt};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use md5::{Digest, Md5};
+use reqwest::RequestBuilder;
 
-use super::{response::ticket::BlockUrl, Client, Error};
+use super::{request::Auth, response::ticket::BlockUrl, Client, Error};
 
 pub(crate) fn chunks<'a>(
     client: &'a Client,
     urls: &'a [BlockUrl],
+    auth: &'a Auth,
 ) -> impl Stream<Item = crate::Result<Bytes>> + 'a {
     Box::pin(
-        stream::try_unfold((client, urls, 0), |(client, urls, i)| async move {
+        stream::try_unfold((client, urls, 0), move |(client, urls, i)| async move {
             match urls.get(i) {
                 Some(url) => {
-                    let st = resolve_data(client, url).await;
+                    let st = resolve_data(client, url, auth).await;
                     Ok(Some((st, (client, urls, i + 1))))
                 }
                 None => Ok(None),
@@ -23,9 +26,60 @@ pub(crate) fn chunks<'a>(
     )
 }
 
+pub(crate) fn verify_md5<'a, S>(
+    chunks: S,
+    expected: Option<&'a str>,
+) -> impl Stream<Item = crate::Result<Bytes>> + 'a
+where
+    S: Stream<Item = crate::Result<Bytes>> + Unpin + 'a,
+{
+    let hasher = expected.map(|_| Md5::new());
+
+    // The hasher is taken after the last chunk or an error, which ends the stream.
+    stream::unfold(
+        (chunks, hasher, false),
+        move |(mut chunks, mut hasher, is_done)| async move {
+            if is_done {
+                return None;
+            }
+
+            match chunks.next().await {
+                Some(Ok(buf)) => {
+                    if let Some(hasher) = hasher.as_mut() {
+                        hasher.update(&buf);
+                    }
+
+                    Some((Ok(buf), (chunks, hasher, false)))
+                }
+                Some(Err(e)) => Some((Err(e), (chunks, None, true))),
+                None => {
+                    let (hasher, expected) = hasher.zip(expected)?;
+                    let actual = encode_hex(&hasher.finalize());
+
+                    if actual.eq_ignore_ascii_case(expected) {
+                        None
+                    } else {
+                        let e = Error::ChecksumMismatch {
+                            expected: expected.into(),
+                            actual,
+                        };
+
+                        Some((Err(e), (chunks, None, true)))
+                    }
+                }
+            }
+        },
+    )
+}
+
+fn encode_hex(buf: &[u8]) -> String {
+    buf.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 async fn resolve_data(
     client: &Client,
     block_url: &BlockUrl,
+    auth: &Auth,
 ) -> Pin<Box<dyn Stream<Item = crate::Result<Bytes>>>> {
     const DELIMITER: &str = ";base64,";
 
@@ -43,11 +97,7 @@ async fn resolve_data(
             Box::pin(stream::once(async { Err(Error::InvalidDataUrl) }))
         }
     } else {
-        let mut request = client.http_client().get(url.clone());
-
-        for (key, value) in block_url.headers() {
-            request = request.header(key, value);
-        }
+        let request = build_block_request(client, block_url, auth);
 
         match request.send().await.map_err(Error::Request) {
             Ok(response) => Box::pin(response.bytes_stream().map_err(Error::Request)),
@@ -55,3 +105,72 @@ async fn resolve_data(
         }
     }
 }
+
+// Request headers are only sent to data blocks on the same origin as the ticket endpoint, so that
+// credentials are not leaked to other hosts. Headers in the ticket are always sent and take
+// precedence over request headers of the same name.
+fn build_block_request(client: &Client, block_url: &BlockUrl, auth: &Auth) -> RequestBuilder {
+    let url = block_url.url();
+    let mut request = client.http_client().get(url.clone());
+
+    if url.origin() == client.base_url().origin() {
+        for (key, value) in auth.headers() {
+            let is_overridden = block_url
+                .headers()
+                .keys()
+                .any(|k| k.eq_ignore_ascii_case(key));
+
+            if !is_overridden {
+                request = request.header(key, value);
+            }
+        }
+    }
+
+    for (key, value) in block_url.headers() {
+        request = request.header(key, value);
+    }
+
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_block_request() -> Result<(), Box<dyn std::error::Error>> {
+        fn build_block_url(url: &str) -> serde_json::Result<BlockUrl> {
+            serde_json::from_str(&format!(
+                r#"{{ "url": "{}", "headers": {{ "Range": "bytes=0-1023" }} }}"#,
+                url
+            ))
+        }
+
+        let client = Client::new("https://localhost/htsget/".parse()?);
+
+        let mut auth = Auth::default();
+        auth.set_bearer_token("secret");
+        auth.add_header(String::from("range"), String::from("bytes=0-"));
+
+        let block_url = build_block_url("https://localhost/data/NDLS0001")?;
+        let request = build_block_request(&client, &block_url, &auth).build()?;
+        let headers = request.headers();
+        assert_eq!(headers.get("authorization").unwrap(), "Bearer secret");
+        assert_eq!(headers.get_all("range").iter().count(), 1);
+        assert_eq!(headers.get("range").unwrap(), "bytes=0-1023");
+
+        for url in [
+            "https://example.com/data/NDLS0001",
+            "http://localhost/data/NDLS0001",
+            "https://localhost:8443/data/NDLS0001",
+        ] {
+            let block_url = build_block_url(url)?;
+            let request = build_block_request(&client, &block_url, &auth).build()?;
+            let headers = request.headers();
+            assert!(headers.get("authorization").is_none());
+            assert_eq!(headers.get("range").unwrap(), "bytes=0-1023");
+        }
+
+        Ok(())
+    }
+}
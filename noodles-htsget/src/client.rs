@@ -1,6 +1,6 @@
 use url::Url;
 
-use super::{reads, request, request::Kind, variants};
+use super::{reads, request, variants, Error, Kind, ServiceInfo};
 
 /// A htsget client.
 #[derive(Clone, Debug)]
@@ -85,4 +85,41 @@ impl Client {
         let builder = request::Builder::new(self.clone(), Kind::Variants, id);
         variants::Builder::new(builder)
     }
+
+    /// Returns the service info for the given endpoint.
+    ///
+    /// This describes the capabilities of the endpoint, e.g., the supported data formats.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use noodles_htsget as htsget;
+    ///
+    /// let client = htsget::Client::new("https://localhost/".parse()?);
+    /// let service_info = client.service_info(htsget::Kind::Reads).await?;
+    ///
+    /// if let Some(htsget) = service_info.htsget() {
+    ///     println!("{:?}", htsget.formats());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn service_info(&self, kind: Kind) -> crate::Result<ServiceInfo> {
+        let endpoint = self
+            .base_url()
+            .join(&format!("{}/service-info", kind.as_ref()))
+            .map_err(Error::Url)?;
+
+        self.http_client()
+            .get(endpoint)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::Request)?
+            .json()
+            .await
+            .map_err(Error::Request)
+    }
 }
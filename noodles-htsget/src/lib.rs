@@ -7,12 +7,19 @@ mod client;
 mod format;
 pub mod reads;
 pub(crate) mod request;
-pub(crate) mod response;
+pub mod response;
+pub mod service_info;
 pub mod variants;
 
-pub use self::{client::Client, format::Format, response::Response};
+pub use self::{
+    client::Client,
+    format::Format,
+    request::{Kind, Method},
+    response::Response,
+    service_info::ServiceInfo,
+};
 
-use std::{error, fmt};
+use std::{error, fmt, io};
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -31,6 +38,17 @@ pub enum Error {
     Decode(base64::DecodeError),
     /// The data URL is invalid.
     InvalidDataUrl,
+    /// The ticket data format is not supported by the decoder.
+    UnexpectedFormat(Format),
+    /// An I/O error occurred while decoding the data.
+    Io(io::Error),
+    /// The MD5 checksum of the data does not match the ticket checksum.
+    ChecksumMismatch {
+        /// The checksum in the ticket.
+        expected: String,
+        /// The checksum of the received data.
+        actual: String,
+    },
 }
 
 impl error::Error for Error {}
@@ -44,6 +62,13 @@ impl fmt::Display for Error {
             Self::Response(e) => e.fmt(f),
             Self::Decode(e) => write!(f, "decode error: {}", e),
             Self::InvalidDataUrl => f.write_str("invalid data URL"),
+            Self::UnexpectedFormat(format) => write!(f, "unexpected format: {:?}", format),
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "checksum mismatch: expected {}, got {}",
+                expected, actual
+            ),
         }
     }
 }
@@ -1,9 +1,10 @@
 use noodles_core::Region;
+use noodles_sam::record::data::field::Tag;
 
-use super::Format;
+use super::{Field, Format};
 use crate::{
     request::{self, Class},
-    Response,
+    Method, Response,
 };
 
 /// A reads endpoint builder.
@@ -16,6 +17,14 @@ impl Builder {
         Self { inner }
     }
 
+    /// Sets the request method.
+    ///
+    /// By default, requests are sent using `POST`. A `GET` request can query at most one region.
+    pub fn set_method(mut self, method: Method) -> Self {
+        self.inner = self.inner.set_method(method);
+        self
+    }
+
     /// Sets the data format.
     pub fn set_format(mut self, format: Format) -> Self {
         self.inner = self.inner.set_format(format.into());
@@ -36,6 +45,62 @@ impl Builder {
         self
     }
 
+    /// Sets the fields to include.
+    ///
+    /// By default, all fields are included.
+    pub fn set_fields<I>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = Field>,
+    {
+        let fields = fields.into_iter().map(|field| field.to_string());
+        self.inner = self.inner.set_fields(fields);
+        self
+    }
+
+    /// Sets the SAM data field tags to include.
+    ///
+    /// By default, all tags are included. An empty list excludes all tags.
+    pub fn set_tags<I>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = Tag>,
+    {
+        let tags = tags.into_iter().map(|tag| tag.to_string());
+        self.inner = self.inner.set_tags(tags);
+        self
+    }
+
+    /// Sets the SAM data field tags to exclude.
+    pub fn set_notags<I>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = Tag>,
+    {
+        let tags = tags.into_iter().map(|tag| tag.to_string());
+        self.inner = self.inner.set_notags(tags);
+        self
+    }
+
+    /// Sets a bearer token to authorize the request.
+    ///
+    /// The token is also sent when fetching response data from the same origin as the htsget
+    /// server. It is never sent to other hosts.
+    pub fn set_bearer_token(mut self, token: &str) -> Self {
+        self.inner = self.inner.set_bearer_token(token);
+        self
+    }
+
+    /// Adds a header to send with the request.
+    ///
+    /// The header is also sent when fetching response data from the same origin as the htsget
+    /// server. It is never sent to other hosts.
+    pub fn add_header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.inner = self.inner.add_header(key.into(), value.into());
+        self
+    }
+
     /// Sends the request.
     pub async fn send(self) -> crate::Result<Response> {
         self.inner.send().await
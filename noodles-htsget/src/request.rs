@@ -1,10 +1,12 @@
 //! Htsget request.
 
+mod auth;
 mod builder;
 mod class;
 mod kind;
+mod method;
 mod payload;
 
-pub use self::class::Class;
+pub use self::{class::Class, kind::Kind, method::Method};
 
-pub(crate) use self::{builder::Builder, kind::Kind, payload::Payload};
+pub(crate) use self::{auth::Auth, builder::Builder, payload::Payload};
@@ -0,0 +1,73 @@
+use std::fmt;
+
+use reqwest::{header::AUTHORIZATION, RequestBuilder};
+
+/// Headers sent with the ticket request and data block requests on the same origin.
+#[derive(Clone, Default)]
+pub(crate) struct Auth {
+    authorization: Option<String>,
+    headers: Vec<(String, String)>,
+}
+
+impl Auth {
+    pub fn set_bearer_token(&mut self, token: &str) {
+        self.authorization = Some(format!("Bearer {}", token));
+    }
+
+    pub fn add_header(&mut self, key: String, value: String) {
+        self.headers.push((key, value));
+    }
+
+    pub fn headers(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.authorization
+            .iter()
+            .map(|value| (AUTHORIZATION.as_str(), value.as_str()))
+            .chain(
+                self.headers
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.as_str())),
+            )
+    }
+
+    pub fn apply(&self, mut request: RequestBuilder) -> RequestBuilder {
+        for (key, value) in self.headers() {
+            request = request.header(key, value);
+        }
+
+        request
+    }
+}
+
+// Header values are omitted so that credentials are not leaked.
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.headers().map(|(key, _)| key))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headers() {
+        let mut auth = Auth::default();
+        assert_eq!(auth.headers().count(), 0);
+
+        auth.add_header(String::from("x-noodles"), String::from("ndls"));
+        auth.set_bearer_token("secret");
+
+        let actual: Vec<_> = auth.headers().collect();
+        let expected = [("authorization", "Bearer secret"), ("x-noodles", "ndls")];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_fmt() {
+        let mut auth = Auth::default();
+        auth.set_bearer_token("secret");
+        assert_eq!(format!("{:?}", auth), r#"["authorization"]"#);
+    }
+}
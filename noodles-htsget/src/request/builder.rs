@@ -2,7 +2,7 @@ use noodles_core::Region;
 use serde::Deserialize;
 use url::Url;
 
-use super::{Class, Kind, Payload};
+use super::{Auth, Class, Kind, Method, Payload};
 use crate::{response::Ticket, Client, Error, Format, Response};
 
 /// A request builder.
@@ -10,7 +10,9 @@ pub struct Builder {
     client: Client,
     kind: Kind,
     id: String,
+    method: Method,
     payload: Payload,
+    auth: Auth,
 }
 
 impl Builder {
@@ -22,10 +24,17 @@ impl Builder {
             client,
             kind,
             id: id.into(),
+            method: Method::default(),
             payload: Payload::from(kind),
+            auth: Auth::default(),
         }
     }
 
+    pub fn set_method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
     pub fn set_format(mut self, format: Format) -> Self {
         *self.payload.format_mut() = format;
         self
@@ -41,19 +50,63 @@ impl Builder {
         self
     }
 
+    pub fn set_fields<I>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        *self.payload.fields_mut() = Some(fields.into_iter().collect());
+        self
+    }
+
+    pub fn set_tags<I>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        *self.payload.tags_mut() = Some(tags.into_iter().collect());
+        self
+    }
+
+    pub fn set_notags<I>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        *self.payload.notags_mut() = Some(tags.into_iter().collect());
+        self
+    }
+
+    pub fn set_bearer_token(mut self, token: &str) -> Self {
+        self.auth.set_bearer_token(token);
+        self
+    }
+
+    pub fn add_header(mut self, key: String, value: String) -> Self {
+        self.auth.add_header(key, value);
+        self
+    }
+
     pub async fn send(self) -> crate::Result<Response> {
         let endpoint = build_endpoint(self.client.base_url(), self.kind, &self.id)?;
-        let mut request = self.client.http_client().post(endpoint);
 
-        request = match self.payload.class() {
+        let payload = match self.payload.class() {
             Some(Class::Header) => {
                 let mut payload = Payload::from(self.kind);
+                *payload.format_mut() = self.payload.format();
                 *payload.class_mut() = Some(Class::Header);
-                request.json(&payload)
+                payload
             }
-            None => request.json(&self.payload),
+            None => self.payload,
         };
 
+        let mut request = match self.method {
+            Method::Get => {
+                let query = payload.query_pairs()?;
+                self.client.http_client().get(endpoint).query(&query)
+            }
+            Method::Post => self.client.http_client().post(endpoint).json(&payload),
+        };
+
+        request = self.auth.apply(request);
+
         let response = request.send().await.map_err(Error::Request)?;
 
         if response.status().is_client_error() {
@@ -61,7 +114,7 @@ impl Builder {
             Err(Error::Response(data.htsget))
         } else {
             let data: TicketResponse = response.json().await.map_err(Error::Request)?;
-            Ok(Response::new(self.client, self.id, data.htsget))
+            Ok(Response::new(self.client, self.id, data.htsget, self.auth))
         }
     }
 }
@@ -77,10 +130,7 @@ pub struct ErrorResponse {
 }
 
 fn build_endpoint(base_url: &Url, kind: Kind, id: &str) -> crate::Result<Url> {
-    let k = match kind {
-        Kind::Reads => "reads",
-        Kind::Variants => "variants",
-    };
-
-    base_url.join(&format!("{}/{}", k, id)).map_err(Error::Url)
+    base_url
+        .join(&format!("{}/{}", kind.as_ref(), id))
+        .map_err(Error::Url)
 }
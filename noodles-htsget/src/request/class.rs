@@ -6,3 +6,11 @@ use serde::{Deserialize, Serialize};
 pub enum Class {
     Header,
 }
+
+impl AsRef<str> for Class {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Header => "header",
+        }
+    }
+}
@@ -1,5 +1,20 @@
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+use serde::Deserialize;
+
+/// An htsget endpoint kind.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
 pub enum Kind {
+    /// Reads (`reads`).
     Reads,
+    /// Variants (`variants`).
     Variants,
 }
+
+impl AsRef<str> for Kind {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Reads => "reads",
+            Self::Variants => "variants",
+        }
+    }
+}
@@ -0,0 +1,16 @@
+/// An htsget ticket request method.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Method {
+    /// `GET`, where the request parameters are sent as query parameters.
+    ///
+    /// This only supports querying at most one region.
+    Get,
+    /// `POST`, where the request parameters are sent as a JSON payload.
+    Post,
+}
+
+impl Default for Method {
+    fn default() -> Self {
+        Self::Post
+    }
+}
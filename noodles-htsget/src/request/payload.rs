@@ -3,9 +3,9 @@ mod regions;
 use noodles_core::Region;
 use serde::Serialize;
 
-use self::regions::Regions;
+use self::regions::{resolve_interval, Regions};
 use super::{Class, Kind};
-use crate::Format;
+use crate::{Error, Format};
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct Payload {
@@ -16,9 +16,22 @@ pub struct Payload {
 
     #[serde(skip_serializing_if = "Regions::is_empty")]
     regions: Regions,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fields: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notags: Option<Vec<String>>,
 }
 
 impl Payload {
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
     pub fn format_mut(&mut self) -> &mut Format {
         &mut self.format
     }
@@ -34,6 +47,60 @@ impl Payload {
     pub fn regions_mut(&mut self) -> &mut Vec<Region> {
         &mut self.regions.0
     }
+
+    pub fn fields_mut(&mut self) -> &mut Option<Vec<String>> {
+        &mut self.fields
+    }
+
+    pub fn tags_mut(&mut self) -> &mut Option<Vec<String>> {
+        &mut self.tags
+    }
+
+    pub fn notags_mut(&mut self) -> &mut Option<Vec<String>> {
+        &mut self.notags
+    }
+
+    // _Htsget retrieval API spec v1.3.0_ § 1.3 "Query parameters": GET requests can only query
+    // at most one region, and lists are comma-separated.
+    pub fn query_pairs(&self) -> crate::Result<Vec<(&'static str, String)>> {
+        let mut pairs = vec![("format", self.format.as_ref().into())];
+
+        if let Some(class) = self.class {
+            pairs.push(("class", class.as_ref().into()));
+        }
+
+        match self.regions.0.as_slice() {
+            [] => {}
+            [region] => {
+                pairs.push(("referenceName", region.name().into()));
+
+                let (start, end) = resolve_interval(region.interval());
+
+                if let Some(position) = start {
+                    pairs.push(("start", position.to_string()));
+                }
+
+                if let Some(position) = end {
+                    pairs.push(("end", position.to_string()));
+                }
+            }
+            _ => return Err(Error::Input),
+        }
+
+        let lists = [
+            ("fields", &self.fields),
+            ("tags", &self.tags),
+            ("notags", &self.notags),
+        ];
+
+        for (key, list) in lists {
+            if let Some(values) = list {
+                pairs.push((key, values.join(",")));
+            }
+        }
+
+        Ok(pairs)
+    }
 }
 
 impl From<Kind> for Payload {
@@ -47,6 +114,9 @@ impl From<Kind> for Payload {
             format,
             class: None,
             regions: Regions::default(),
+            fields: None,
+            tags: None,
+            notags: None,
         }
     }
 }
@@ -127,5 +197,87 @@ mod tests {
                 Token::StructEnd,
             ],
         );
+
+        let mut payload = Payload::from(Kind::Reads);
+        *payload.fields_mut() = Some(vec![String::from("QNAME"), String::from("FLAG")]);
+        *payload.tags_mut() = Some(Vec::new());
+        *payload.notags_mut() = Some(vec![String::from("NH")]);
+
+        assert_ser_tokens(
+            &payload,
+            &[
+                Token::Struct {
+                    name: "Payload",
+                    len: 4,
+                },
+                Token::Str("format"),
+                Token::UnitVariant {
+                    name: "Format",
+                    variant: "BAM",
+                },
+                Token::Str("fields"),
+                Token::Some,
+                Token::Seq { len: Some(2) },
+                Token::Str("QNAME"),
+                Token::Str("FLAG"),
+                Token::SeqEnd,
+                Token::Str("tags"),
+                Token::Some,
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::Str("notags"),
+                Token::Some,
+                Token::Seq { len: Some(1) },
+                Token::Str("NH"),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_query_pairs() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_core::Position;
+
+        let payload = Payload::from(Kind::Variants);
+        assert_eq!(payload.query_pairs()?, [("format", String::from("VCF"))]);
+
+        let mut payload = Payload::from(Kind::Reads);
+        *payload.class_mut() = Some(Class::Header);
+        assert_eq!(
+            payload.query_pairs()?,
+            [
+                ("format", String::from("BAM")),
+                ("class", String::from("header"))
+            ]
+        );
+
+        let mut payload = Payload::from(Kind::Reads);
+        let start = Position::try_from(8)?;
+        let end = Position::try_from(13)?;
+        payload.regions_mut().push(Region::new("sq0", start..=end));
+        *payload.fields_mut() = Some(vec![String::from("QNAME"), String::from("FLAG")]);
+        *payload.tags_mut() = Some(Vec::new());
+        *payload.notags_mut() = Some(vec![String::from("NH"), String::from("HI")]);
+
+        assert_eq!(
+            payload.query_pairs()?,
+            [
+                ("format", String::from("BAM")),
+                ("referenceName", String::from("sq0")),
+                ("start", String::from("7")),
+                ("end", String::from("13")),
+                ("fields", String::from("QNAME,FLAG")),
+                ("tags", String::new()),
+                ("notags", String::from("NH,HI")),
+            ]
+        );
+
+        let mut payload = Payload::from(Kind::Reads);
+        payload.regions_mut().push(Region::new("sq0", ..));
+        payload.regions_mut().push(Region::new("sq1", ..));
+        assert!(matches!(payload.query_pairs(), Err(Error::Input)));
+
+        Ok(())
     }
 }
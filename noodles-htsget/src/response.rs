@@ -1,13 +1,22 @@
+//! Htsget response.
+
 mod error;
-pub(crate) mod ticket;
+#[cfg(feature = "records")]
+mod records;
+pub mod ticket;
 
-pub use self::error::Error;
-pub(crate) use self::ticket::Ticket;
+pub use self::{error::Error, ticket::Ticket};
 
 use bytes::Bytes;
 use futures::Stream;
+#[cfg(feature = "records")]
+use noodles_fasta as fasta;
+#[cfg(feature = "records")]
+use noodles_sam as sam;
+#[cfg(feature = "records")]
+use noodles_vcf as vcf;
 
-use super::Client;
+use super::{request::Auth, Client};
 
 /// An htsget response.
 #[derive(Debug)]
@@ -15,11 +24,17 @@ pub struct Response {
     client: Client,
     id: String,
     ticket: Ticket,
+    auth: Auth,
 }
 
 impl Response {
-    pub(crate) fn new(client: Client, id: String, ticket: Ticket) -> Self {
-        Self { client, id, ticket }
+    pub(crate) fn new(client: Client, id: String, ticket: Ticket, auth: Auth) -> Self {
+        Self {
+            client,
+            id,
+            ticket,
+            auth,
+        }
     }
 
     /// Returns the record ID.
@@ -27,9 +42,173 @@ impl Response {
         &self.id
     }
 
+    /// Returns the ticket.
+    pub fn ticket(&self) -> &Ticket {
+        &self.ticket
+    }
+
     /// Returns the data from the ticket URLs.
+    ///
+    /// Headers set on the request, e.g., a bearer token, are also sent with each data block
+    /// request on the same origin as the htsget server, unless the ticket overrides them. They
+    /// are not sent to other origins.
     pub fn chunks(&self) -> impl Stream<Item = crate::Result<Bytes>> + '_ {
         use super::chunks::chunks;
-        chunks(&self.client, self.ticket.urls())
+        chunks(&self.client, self.ticket.urls(), &self.auth)
+    }
+
+    /// Returns the data from the ticket URLs and verifies its MD5 checksum.
+    ///
+    /// This is the same as [`Self::chunks`], but the checksum of the data is computed while it is
+    /// streamed. If the ticket has a checksum and it does not match, the stream ends with an
+    /// [`crate::Error::ChecksumMismatch`] error. If the ticket has no checksum, the data is not
+    /// verified.
+    pub fn verified_chunks(&self) -> impl Stream<Item = crate::Result<Bytes>> + '_ {
+        use super::chunks::{chunks, verify_md5};
+
+        let chunks = chunks(&self.client, self.ticket.urls(), &self.auth);
+        verify_md5(chunks, self.ticket.md5())
+    }
+
+    /// Decodes the data as a SAM header and alignment records.
+    ///
+    /// The decoder is selected by the ticket format, which must be BAM or CRAM. The reference
+    /// sequence repository is only used to decode CRAM records.
+    ///
+    /// The data is verified as it is streamed (see [`Self::verified_chunks`]).
+    ///
+    /// This requires the `records` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::TryStreamExt;
+    /// use noodles_fasta as fasta;
+    /// use noodles_htsget as htsget;
+    ///
+    /// let client = htsget::Client::new("https://localhost/".parse()?);
+    /// let response = client.reads("NDLS0001").send().await?;
+    ///
+    /// let reference_sequence_repository = fasta::Repository::default();
+    /// let (header, mut records) = response
+    ///     .alignment_records(&reference_sequence_repository)
+    ///     .await?;
+    ///
+    /// while let Some(record) = records.try_next().await? {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "records")]
+    pub async fn alignment_records<'a>(
+        &'a self,
+        reference_sequence_repository: &'a fasta::Repository,
+    ) -> crate::Result<(
+        sam::Header,
+        impl Stream<Item = crate::Result<sam::alignment::Record>> + 'a,
+    )> {
+        records::alignment_records(self, reference_sequence_repository).await
+    }
+
+    /// Decodes the data as a VCF header and records.
+    ///
+    /// The decoder is selected by the ticket format, which must be VCF or BCF.
+    ///
+    /// The data is verified as it is streamed (see [`Self::verified_chunks`]).
+    ///
+    /// This requires the `records` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::TryStreamExt;
+    /// use noodles_htsget as htsget;
+    ///
+    /// let client = htsget::Client::new("https://localhost/".parse()?);
+    /// let response = client.variants("NDLS0001").send().await?;
+    ///
+    /// let (header, mut records) = response.variant_records().await?;
+    ///
+    /// while let Some(record) = records.try_next().await? {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "records")]
+    pub async fn variant_records(
+        &self,
+    ) -> crate::Result<(
+        vcf::Header,
+        impl Stream<Item = crate::Result<vcf::Record>> + '_,
+    )> {
+        records::variant_records(self).await
+    }
+}
+
+#[cfg(test)]
+fn build_response(
+    format: &str,
+    chunks: &[&[u8]],
+    md5: Option<&str>,
+) -> Result<Response, Box<dyn std::error::Error>> {
+    use serde_json::json;
+
+    let urls: Vec<_> = chunks
+        .iter()
+        .map(|chunk| json!({ "url": format!("data:;base64,{}", base64::encode(chunk)) }))
+        .collect();
+
+    let mut ticket = json!({ "format": format, "urls": urls });
+
+    if let Some(md5) = md5 {
+        ticket["md5"] = json!(md5);
+    }
+
+    let client = Client::new("https://localhost/".parse()?);
+
+    Ok(Response::new(
+        client,
+        String::from("NDLS0001"),
+        serde_json::from_value(ticket)?,
+        Auth::default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::Error;
+
+    const CHUNKS: [&[u8]; 2] = [b"noodles", b"-htsget"];
+
+    #[tokio::test]
+    async fn test_verified_chunks() -> Result<(), Box<dyn std::error::Error>> {
+        // MD5("noodles-htsget")
+        let response = build_response("VCF", &CHUNKS, Some("82d43a711271b51f23d72baa8189484c"))?;
+        let data: Vec<_> = response.verified_chunks().try_collect().await?;
+        assert_eq!(data, CHUNKS);
+
+        let response = build_response("VCF", &CHUNKS, None)?;
+        let data: Vec<_> = response.verified_chunks().try_collect().await?;
+        assert_eq!(data.len(), 2);
+
+        let response = build_response("VCF", &CHUNKS, Some("d41d8cd98f00b204e9800998ecf8427e"))?;
+        let result: crate::Result<Vec<_>> = response.verified_chunks().try_collect().await;
+        assert!(matches!(
+            result,
+            Err(Error::ChecksumMismatch { expected, actual })
+                if expected == "d41d8cd98f00b204e9800998ecf8427e"
+                    && actual == "82d43a711271b51f23d72baa8189484c"
+        ));
+
+        Ok(())
     }
 }
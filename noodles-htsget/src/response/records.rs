@@ -0,0 +1,318 @@
+use std::{collections::VecDeque, io, pin::Pin};
+
+use bytes::Bytes;
+use futures::{stream, Stream, TryStreamExt};
+use noodles_bam as bam;
+use noodles_bcf as bcf;
+use noodles_bgzf as bgzf;
+use noodles_cram as cram;
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+use noodles_vcf as vcf;
+use tokio_util::io::StreamReader;
+
+use super::Response;
+use crate::{Error, Format};
+
+type DataReader<'a> = StreamReader<Pin<Box<dyn Stream<Item = io::Result<Bytes>> + 'a>>, Bytes>;
+
+pub(super) type AlignmentRecords<'a> =
+    Pin<Box<dyn Stream<Item = crate::Result<sam::alignment::Record>> + 'a>>;
+
+pub(super) type VariantRecords<'a> = Pin<Box<dyn Stream<Item = crate::Result<vcf::Record>> + 'a>>;
+
+pub(super) async fn alignment_records<'a>(
+    response: &'a Response,
+    reference_sequence_repository: &'a fasta::Repository,
+) -> crate::Result<(sam::Header, AlignmentRecords<'a>)> {
+    let reader = data_reader(response);
+
+    match response.ticket().format() {
+        Format::Bam => bam_records(reader).await,
+        Format::Cram => cram_records(reader, reference_sequence_repository).await,
+        format => Err(Error::UnexpectedFormat(format)),
+    }
+}
+
+pub(super) async fn variant_records(
+    response: &Response,
+) -> crate::Result<(vcf::Header, VariantRecords<'_>)> {
+    let reader = data_reader(response);
+
+    match response.ticket().format() {
+        Format::Vcf => vcf_records(reader).await,
+        Format::Bcf => bcf_records(reader).await,
+        format => Err(Error::UnexpectedFormat(format)),
+    }
+}
+
+fn data_reader(response: &Response) -> DataReader<'_> {
+    let chunks = response
+        .verified_chunks()
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e));
+
+    StreamReader::new(Box::pin(chunks))
+}
+
+async fn bam_records(reader: DataReader<'_>) -> crate::Result<(sam::Header, AlignmentRecords<'_>)> {
+    let mut reader = bam::AsyncReader::new(reader);
+
+    let raw_header = reader.read_header().await.map_err(from_io_error)?;
+    let reference_sequences = reader
+        .read_reference_sequences()
+        .await
+        .map_err(from_io_error)?;
+
+    let header = if raw_header.is_empty() {
+        sam::Header::builder()
+            .set_reference_sequences(reference_sequences)
+            .build()
+    } else {
+        raw_header.parse().map_err(invalid_data)?
+    };
+
+    let records = stream::try_unfold(reader, |mut reader| async move {
+        let mut record = sam::alignment::Record::default();
+
+        match reader.read_record(&mut record).await {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some((record, reader))),
+            Err(e) => Err(from_io_error(e)),
+        }
+    });
+
+    Ok((header, Box::pin(records)))
+}
+
+async fn cram_records<'a>(
+    reader: DataReader<'a>,
+    reference_sequence_repository: &'a fasta::Repository,
+) -> crate::Result<(sam::Header, AlignmentRecords<'a>)> {
+    let mut reader = cram::AsyncReader::new(reader);
+
+    reader.read_file_definition().await.map_err(from_io_error)?;
+
+    let header: sam::Header = reader
+        .read_file_header()
+        .await
+        .map_err(from_io_error)?
+        .parse()
+        .map_err(invalid_data)?;
+
+    let records = stream::try_unfold(
+        (reader, header.clone(), VecDeque::new()),
+        move |(mut reader, header, mut records)| async move {
+            loop {
+                if let Some(record) = records.pop_front() {
+                    let record = cram::Record::try_into_alignment_record(record, &header)
+                        .map_err(from_io_error)?;
+
+                    return Ok(Some((record, (reader, header, records))));
+                }
+
+                let container = match reader.read_data_container().await {
+                    Ok(Some(container)) => container,
+                    Ok(None) => return Ok(None),
+                    Err(e) => return Err(from_io_error(e)),
+                };
+
+                let compression_header = container.compression_header();
+
+                for slice in container.slices() {
+                    let mut slice_records =
+                        slice.records(compression_header).map_err(from_io_error)?;
+
+                    slice
+                        .resolve_records(
+                            reference_sequence_repository,
+                            &header,
+                            compression_header,
+                            &mut slice_records,
+                        )
+                        .map_err(from_io_error)?;
+
+                    records.extend(slice_records);
+                }
+            }
+        },
+    );
+
+    Ok((header, Box::pin(records)))
+}
+
+async fn vcf_records(reader: DataReader<'_>) -> crate::Result<(vcf::Header, VariantRecords<'_>)> {
+    let mut reader = vcf::AsyncReader::new(bgzf::AsyncReader::new(reader));
+
+    let header: vcf::Header = reader
+        .read_header()
+        .await
+        .map_err(from_io_error)?
+        .parse()
+        .map_err(invalid_data)?;
+
+    let records = stream::try_unfold(
+        (reader, header.clone(), String::new()),
+        |(mut reader, header, mut buf)| async move {
+            buf.clear();
+
+            match reader.read_record(&mut buf).await {
+                Ok(0) => Ok(None),
+                Ok(_) => {
+                    let record = vcf::Record::try_from_str(&buf, &header).map_err(invalid_data)?;
+                    Ok(Some((record, (reader, header, buf))))
+                }
+                Err(e) => Err(from_io_error(e)),
+            }
+        },
+    );
+
+    Ok((header, Box::pin(records)))
+}
+
+async fn bcf_records(reader: DataReader<'_>) -> crate::Result<(vcf::Header, VariantRecords<'_>)> {
+    let mut reader = bcf::AsyncReader::new(reader);
+
+    reader.read_file_format().await.map_err(from_io_error)?;
+
+    let raw_header = reader.read_header().await.map_err(from_io_error)?;
+    let header: vcf::Header = raw_header.parse().map_err(invalid_data)?;
+    let string_maps: bcf::header::StringMaps = raw_header.parse().map_err(invalid_data)?;
+
+    let records = stream::try_unfold(
+        (reader, header.clone(), string_maps),
+        |(mut reader, header, string_maps)| async move {
+            let mut record = bcf::Record::default();
+
+            match reader.read_record(&mut record).await {
+                Ok(0) => Ok(None),
+                Ok(_) => {
+                    let record = record
+                        .try_into_vcf_record(&header, &string_maps)
+                        .map_err(from_io_error)?;
+
+                    Ok(Some((record, (reader, header, string_maps))))
+                }
+                Err(e) => Err(from_io_error(e)),
+            }
+        },
+    );
+
+    Ok((header, Box::pin(records)))
+}
+
+fn invalid_data<E>(e: E) -> Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// Errors from fetching the data are passed through the reader as I/O errors and are unwrapped
+// here.
+fn from_io_error(e: io::Error) -> Error {
+    let kind = e.kind();
+    let raw_os_error = e.raw_os_error();
+
+    match e.into_inner().map(|inner| inner.downcast::<Error>()) {
+        Some(Ok(e)) => *e,
+        Some(Err(inner)) => Error::Io(io::Error::new(kind, inner)),
+        None => Error::Io(
+            raw_os_error
+                .map(io::Error::from_raw_os_error)
+                .unwrap_or_else(|| io::Error::from(kind)),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::response::build_response;
+
+    #[test]
+    fn test_from_io_error() {
+        let e = io::Error::new(io::ErrorKind::Other, Error::InvalidDataUrl);
+        assert!(matches!(from_io_error(e), Error::InvalidDataUrl));
+
+        let e = io::Error::new(io::ErrorKind::InvalidData, "noodles");
+        assert!(matches!(
+            from_io_error(e),
+            Error::Io(e) if e.kind() == io::ErrorKind::InvalidData && e.to_string() == "noodles"
+        ));
+
+        let e = io::Error::from(io::ErrorKind::UnexpectedEof);
+        assert!(matches!(
+            from_io_error(e),
+            Error::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_alignment_records() -> Result<(), Box<dyn std::error::Error>> {
+        use sam::header::ReferenceSequence;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .build();
+
+        let mut writer = bam::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(header.reference_sequences())?;
+        writer.write_record(&header, &sam::alignment::Record::default())?;
+        let data = writer.into_inner().finish()?;
+
+        let response = build_response("BAM", &[&data], None)?;
+        let reference_sequence_repository = fasta::Repository::default();
+        let (actual_header, records) = response
+            .alignment_records(&reference_sequence_repository)
+            .await?;
+        let records: Vec<_> = records.try_collect().await?;
+
+        assert_eq!(actual_header, header);
+        assert_eq!(records, [sam::alignment::Record::default()]);
+
+        let response = build_response("VCF", &[&data], None)?;
+        assert!(matches!(
+            response
+                .alignment_records(&reference_sequence_repository)
+                .await,
+            Err(Error::UnexpectedFormat(Format::Vcf))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_variant_records() -> Result<(), Box<dyn std::error::Error>> {
+        let raw_header = "##fileformat=VCFv4.3\n##contig=<ID=sq0>\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n";
+        let header: vcf::Header = raw_header.parse()?;
+
+        let record = vcf::Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(vcf::record::Position::from(1))
+            .set_reference_bases("A".parse()?)
+            .build()?;
+
+        let mut writer = vcf::Writer::new(bgzf::Writer::new(Vec::new()));
+        writer.write_header(&header)?;
+        writer.write_record(&record)?;
+        let data = writer.into_inner().finish()?;
+
+        let response = build_response("VCF", &[&data], None)?;
+        let (actual_header, records) = response.variant_records().await?;
+        let records: Vec<_> = records.try_collect().await?;
+
+        assert_eq!(actual_header, header);
+        assert_eq!(records, [record]);
+
+        let response = build_response("BAM", &[&data], None)?;
+        assert!(matches!(
+            response.variant_records().await,
+            Err(Error::UnexpectedFormat(Format::Bam))
+        ));
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+//! Htsget response ticket.
+
 use std::collections::HashMap;
 
 use serde::Deserialize;
@@ -5,15 +7,19 @@ use url::Url;
 
 use crate::Format;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+/// The class of data in a block.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
 #[serde(rename_all = "lowercase")]
-enum Class {
+pub enum Class {
+    /// The block contains header data.
     Header,
+    /// The block contains body data.
     Body,
 }
 
+/// A data block URL.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
-pub(crate) struct BlockUrl {
+pub struct BlockUrl {
     url: Url,
     #[serde(default)]
     headers: HashMap<String, String>,
@@ -21,24 +27,90 @@ pub(crate) struct BlockUrl {
 }
 
 impl BlockUrl {
+    /// Returns the URL of the data block.
+    ///
+    /// This is either a URL to fetch or an inline data URI (`data:`).
     pub fn url(&self) -> &Url {
         &self.url
     }
 
+    /// Returns the headers to send when fetching the data block.
     pub fn headers(&self) -> &HashMap<String, String> {
         &self.headers
     }
+
+    /// Returns the class of data in the block.
+    ///
+    /// This is only set when the server provides it.
+    pub fn class(&self) -> Option<Class> {
+        self.class
+    }
 }
 
+/// An htsget ticket.
+///
+/// A ticket lists the data blocks that, when concatenated, make up the requested data.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
-pub(crate) struct Ticket {
+pub struct Ticket {
     format: Format,
     urls: Vec<BlockUrl>,
     md5: Option<String>,
 }
 
 impl Ticket {
+    /// Returns the data format.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the data block URLs.
     pub fn urls(&self) -> &[BlockUrl] {
         &self.urls
     }
+
+    /// Returns the hex-encoded MD5 checksum of the concatenated data, if set.
+    pub fn md5(&self) -> Option<&str> {
+        self.md5.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() -> Result<(), serde_json::Error> {
+        let data = r#"{
+            "format": "BAM",
+            "urls": [
+                { "url": "data:application/vnd.ga4gh.bam;base64,QkFNAQ==", "class": "header" },
+                {
+                    "url": "https://localhost/sample.bam",
+                    "headers": { "Range": "bytes=65536-1003750" },
+                    "class": "body"
+                },
+                { "url": "https://localhost/sample.eof.bam" }
+            ],
+            "md5": "8a6049cfd2bf5ab6e6c53b0bfac8b6a0"
+        }"#;
+
+        let ticket: Ticket = serde_json::from_str(data)?;
+
+        assert_eq!(ticket.format(), Format::Bam);
+        assert_eq!(ticket.md5(), Some("8a6049cfd2bf5ab6e6c53b0bfac8b6a0"));
+
+        let urls = ticket.urls();
+        assert_eq!(urls.len(), 3);
+        assert_eq!(urls[0].url().scheme(), "data");
+        assert!(urls[0].headers().is_empty());
+        assert_eq!(urls[0].class(), Some(Class::Header));
+        assert_eq!(
+            urls[1].headers().get("Range").map(|s| s.as_str()),
+            Some("bytes=65536-1003750")
+        );
+        assert_eq!(urls[1].class(), Some(Class::Body));
+        assert!(urls[2].class().is_none());
+
+        Ok(())
+    }
 }
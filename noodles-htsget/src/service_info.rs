@@ -0,0 +1,138 @@
+//! Htsget service info.
+
+mod htsget;
+mod organization;
+mod ty;
+
+pub use self::{htsget::Htsget, organization::Organization, ty::Type};
+
+use serde::Deserialize;
+use url::Url;
+
+/// An htsget service info document.
+///
+/// This is a GA4GH service-info document with the htsget extension.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceInfo {
+    id: String,
+    name: String,
+    #[serde(rename = "type")]
+    ty: Type,
+    description: Option<String>,
+    organization: Organization,
+    contact_url: Option<String>,
+    documentation_url: Option<Url>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+    environment: Option<String>,
+    version: String,
+    htsget: Option<Htsget>,
+}
+
+impl ServiceInfo {
+    /// Returns the service ID.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the service name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the service type.
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Returns the service description.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the organization providing the service.
+    pub fn organization(&self) -> &Organization {
+        &self.organization
+    }
+
+    /// Returns the contact URL.
+    ///
+    /// This is typically a URL or an email URI (`mailto:`).
+    pub fn contact_url(&self) -> Option<&str> {
+        self.contact_url.as_deref()
+    }
+
+    /// Returns the documentation URL.
+    pub fn documentation_url(&self) -> Option<&Url> {
+        self.documentation_url.as_ref()
+    }
+
+    /// Returns the timestamp when the service was first available.
+    pub fn created_at(&self) -> Option<&str> {
+        self.created_at.as_deref()
+    }
+
+    /// Returns the timestamp when the service was last updated.
+    pub fn updated_at(&self) -> Option<&str> {
+        self.updated_at.as_deref()
+    }
+
+    /// Returns the environment the service is running in, e.g., `prod`.
+    pub fn environment(&self) -> Option<&str> {
+        self.environment.as_deref()
+    }
+
+    /// Returns the service version.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the htsget extension.
+    ///
+    /// This describes the capabilities of the htsget endpoint.
+    pub fn htsget(&self) -> Option<&Htsget> {
+        self.htsget.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Format, Kind};
+
+    #[test]
+    fn test_deserialize() -> Result<(), serde_json::Error> {
+        let data = r#"{
+            "id": "org.example.htsget",
+            "name": "noodles htsget",
+            "type": { "group": "org.ga4gh", "artifact": "htsget", "version": "1.3.0" },
+            "organization": { "name": "noodles", "url": "https://example.com/" },
+            "version": "0.1.0",
+            "htsget": {
+                "datatype": "reads",
+                "formats": ["BAM", "CRAM"],
+                "fieldsParametersEffective": true,
+                "tagsParametersEffective": false
+            }
+        }"#;
+
+        let service_info: ServiceInfo = serde_json::from_str(data)?;
+
+        assert_eq!(service_info.id(), "org.example.htsget");
+        assert_eq!(service_info.name(), "noodles htsget");
+        assert_eq!(service_info.ty().artifact(), "htsget");
+        assert!(service_info.description().is_none());
+        assert_eq!(service_info.organization().name(), "noodles");
+        assert!(service_info.contact_url().is_none());
+        assert_eq!(service_info.version(), "0.1.0");
+
+        let htsget = service_info.htsget().expect("missing htsget extension");
+        assert_eq!(htsget.datatype(), Some(Kind::Reads));
+        assert_eq!(htsget.formats(), [Format::Bam, Format::Cram]);
+        assert!(htsget.fields_parameters_effective());
+        assert!(!htsget.tags_parameters_effective());
+
+        Ok(())
+    }
+}
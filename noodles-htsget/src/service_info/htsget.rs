@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+use crate::{Format, Kind};
+
+/// The htsget extension of a service info document.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Htsget {
+    datatype: Option<Kind>,
+    #[serde(default)]
+    formats: Vec<Format>,
+    #[serde(default)]
+    fields_parameters_effective: bool,
+    #[serde(default)]
+    tags_parameters_effective: bool,
+}
+
+impl Htsget {
+    /// Returns the type of data served by the endpoint.
+    pub fn datatype(&self) -> Option<Kind> {
+        self.datatype
+    }
+
+    /// Returns the data formats supported by the endpoint.
+    pub fn formats(&self) -> &[Format] {
+        &self.formats
+    }
+
+    /// Returns whether the `fields` parameter is honored.
+    ///
+    /// If this is false, all fields are returned regardless of the requested fields.
+    pub fn fields_parameters_effective(&self) -> bool {
+        self.fields_parameters_effective
+    }
+
+    /// Returns whether the `tags` and `notags` parameters are honored.
+    ///
+    /// If this is false, all tags are returned regardless of the requested tags.
+    pub fn tags_parameters_effective(&self) -> bool {
+        self.tags_parameters_effective
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() -> Result<(), serde_json::Error> {
+        let htsget: Htsget = serde_json::from_str("{}")?;
+        assert!(htsget.datatype().is_none());
+        assert!(htsget.formats().is_empty());
+        assert!(!htsget.fields_parameters_effective());
+        assert!(!htsget.tags_parameters_effective());
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use serde::Deserialize;
+use url::Url;
+
+/// The organization providing a service.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct Organization {
+    name: String,
+    url: Url,
+}
+
+impl Organization {
+    /// Returns the organization name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the organization URL.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+}
@@ -0,0 +1,28 @@
+use serde::Deserialize;
+
+/// A service type.
+///
+/// For htsget, this is `org.ga4gh:htsget:<version>`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct Type {
+    group: String,
+    artifact: String,
+    version: String,
+}
+
+impl Type {
+    /// Returns the namespace of the specification, e.g., `org.ga4gh`.
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    /// Returns the name of the specification, e.g., `htsget`.
+    pub fn artifact(&self) -> &str {
+        &self.artifact
+    }
+
+    /// Returns the version of the specification.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
@@ -34,6 +34,8 @@ pub use self::genotypes::Keys as Format;
 
 use std::{error, fmt, num, str::FromStr};
 
+use noodles_core::Region;
+
 use super::Header;
 
 pub(crate) const MISSING_FIELD: &str = ".";
@@ -714,6 +716,46 @@ impl Record {
     }
 }
 
+/// An error returned when a VCF record fails to convert to a region.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TryIntoRegionError {
+    /// The start position is invalid.
+    InvalidStartPosition(num::TryFromIntError),
+    /// The end position is invalid.
+    InvalidEndPosition(EndError),
+}
+
+impl error::Error for TryIntoRegionError {}
+
+impl fmt::Display for TryIntoRegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidStartPosition(e) => write!(f, "invalid start position: {}", e),
+            Self::InvalidEndPosition(e) => write!(f, "invalid end position: {}", e),
+        }
+    }
+}
+
+impl TryFrom<&Record> for Region {
+    type Error = TryIntoRegionError;
+
+    fn try_from(record: &Record) -> Result<Self, Self::Error> {
+        use noodles_core::Position;
+
+        let start = Position::try_from(usize::from(record.position()))
+            .map_err(TryIntoRegionError::InvalidStartPosition)?;
+
+        let end = record
+            .end()
+            .and_then(|position| {
+                Position::try_from(usize::from(position)).map_err(EndError::InvalidPosition)
+            })
+            .map_err(TryIntoRegionError::InvalidEndPosition)?;
+
+        Ok(Self::new(record.chromosome().to_string(), start..=end))
+    }
+}
+
 impl fmt::Display for Record {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -802,6 +844,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_try_from_record_for_region() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_core::Position as CorePosition;
+
+        let record = Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(Position::from(8))
+            .set_reference_bases("ACGTA".parse()?)
+            .build()?;
+
+        assert_eq!(
+            Region::try_from(&record),
+            Ok(Region::new(
+                "sq0",
+                CorePosition::try_from(8)?..=CorePosition::try_from(12)?
+            ))
+        );
+
+        let record = Record::builder()
+            .set_chromosome("sq0".parse()?)
+            .set_position(Position::from(0))
+            .set_reference_bases("A".parse()?)
+            .build()?;
+
+        assert!(matches!(
+            Region::try_from(&record),
+            Err(TryIntoRegionError::InvalidStartPosition(_))
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
         let record = Record::builder()